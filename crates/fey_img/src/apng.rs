@@ -0,0 +1,96 @@
+use crate::{Image, ImageError, PngPixel};
+use fey_grid::Grid;
+use fey_math::Vec2U;
+use png::Encoder;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Collects frames and encodes them as an animated PNG.
+///
+/// The APNG format requires the frame count up front, so frames are kept in memory until the
+/// animation is saved.
+#[derive(Debug, Clone)]
+pub struct ApngEncoder<Px: PngPixel> {
+    size: Vec2U,
+    delay: (u16, u16),
+    plays: u32,
+    frames: Vec<Image<Px>>,
+}
+
+impl<Px: PngPixel> ApngEncoder<Px> {
+    /// Create a new encoder for frames of the provided size, where each frame is shown for
+    /// `1 / fps` seconds. The animation loops forever.
+    #[inline]
+    pub fn new(size: impl Into<Vec2U>, fps: u16) -> Self {
+        assert!(fps > 0);
+        Self {
+            size: size.into(),
+            delay: (1, fps),
+            plays: 0,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Set how long each frame is shown, in `numerator / denominator` seconds.
+    #[inline]
+    pub fn with_frame_delay(mut self, numerator: u16, denominator: u16) -> Self {
+        self.delay = (numerator, denominator);
+        self
+    }
+
+    /// Set how many times the animation plays. Zero means it loops forever.
+    #[inline]
+    pub fn with_plays(mut self, plays: u32) -> Self {
+        self.plays = plays;
+        self
+    }
+
+    /// Size of the animation's frames.
+    #[inline]
+    pub fn size(&self) -> Vec2U {
+        self.size
+    }
+
+    /// How many frames have been added.
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Add a frame to the end of the animation. It must be the same size as the animation.
+    pub fn push_frame(&mut self, frame: Image<Px>) -> Result<(), ImageError> {
+        if frame.size() != self.size {
+            return Err(ImageError::FrameSizeMismatch {
+                expected: self.size,
+                got: frame.size(),
+            });
+        }
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    /// Save the animation as an APNG.
+    pub fn save<W: Write>(&self, w: W) -> Result<(), ImageError> {
+        if self.frames.is_empty() {
+            return Err(ImageError::NoFrames);
+        }
+        let mut enc = Encoder::new(w, self.size.x, self.size.y);
+        enc.set_depth(Px::bit_depth());
+        enc.set_color(Px::color_type());
+        enc.set_animated(self.frames.len() as u32, self.plays)?;
+        enc.set_frame_delay(self.delay.0, self.delay.1)?;
+        let mut writer = enc.write_header()?;
+        for frame in &self.frames {
+            writer.write_image_data(frame.bytes())?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Save the animation as an APNG file.
+    #[inline]
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ImageError> {
+        self.save(BufWriter::new(File::create(path)?))
+    }
+}
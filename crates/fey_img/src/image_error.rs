@@ -1,3 +1,4 @@
+use fey_math::Vec2U;
 use thiserror::Error;
 
 /// An image processing error.
@@ -20,4 +21,10 @@ pub enum ImageError {
 
     #[error("unsupported or missing file extension: {0:?}")]
     UnsupportedExtension(String),
+
+    #[error("animation frame of size ({got}) does not match animation size ({expected})")]
+    FrameSizeMismatch { expected: Vec2U, got: Vec2U },
+
+    #[error("animation has no frames")]
+    NoFrames,
 }
@@ -1,5 +1,6 @@
 //! Image encoding, decoding, and manipulation.
 
+mod apng;
 mod dyn_image;
 mod image;
 mod image_error;
@@ -11,6 +12,7 @@ mod qoi_impl;
 #[cfg(feature = "lua")]
 mod image_lua;
 
+pub use apng::*;
 pub use dyn_image::*;
 pub use image::*;
 pub use image_error::*;
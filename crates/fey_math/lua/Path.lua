@@ -0,0 +1,159 @@
+---@meta
+
+---A shape made of lines, curves and arcs, built up by moving a pen around. A path is made of
+---subpaths, each starting where the pen was moved to, which can be closed to connect them back
+---to their start.
+---@class (exact) Path: PathMethods
+
+---@class PathClass : PathMethods
+---@overload fun(): Path
+local module = {}
+
+---@class PathMethods
+local methods = {}
+
+---A series of connected points, produced by flattening a path.
+---@class (exact) LineStrip
+---@field points Vec2[]
+---@field closed boolean
+
+---A flattened path that can find points by their distance along it, for moving objects along
+---the path at a steady speed.
+---@class (exact) PathMeasure
+local PathMeasure = {}
+
+---Create a new empty path, with the pen at the origin.
+---@return Path
+---@nodiscard
+function module.new() end
+
+---Returns a clone of this path.
+---@param self Path
+---@return Path
+---@nodiscard
+function methods.clone(self) end
+
+---If the path has no segments.
+---@param self Path
+---@return boolean
+---@nodiscard
+function methods.is_empty(self) end
+
+---Remove all segments from the path and move the pen back to the origin.
+---@param self Path
+function methods.clear(self) end
+
+---Where the pen currently is, which is where the next segment will start.
+---@param self Path
+---@return Vec2
+---@nodiscard
+function methods.pen(self) end
+
+---Move the pen to the point, starting a new subpath.
+---@param self Path
+---@param p Vec2
+---@return Path
+function methods.move_to(self, p) end
+
+---Add a straight line from the pen to the point.
+---@param self Path
+---@param p Vec2
+---@return Path
+function methods.line_to(self, p) end
+
+---Add a quadratic bezier curve from the pen to the point.
+---@param self Path
+---@param control Vec2
+---@param p Vec2
+---@return Path
+function methods.quad_to(self, control, p) end
+
+---Add a cubic bezier curve from the pen to the point.
+---@param self Path
+---@param control1 Vec2
+---@param control2 Vec2
+---@param p Vec2
+---@return Path
+function methods.cubic_to(self, control1, control2, p) end
+
+---Add a circular arc that starts at the pen and sweeps the angle around the center. Positive
+---angles sweep in the direction that turns the x-axis towards the y-axis.
+---@param self Path
+---@param center Vec2
+---@param sweep number Angle (in radians).
+---@return Path
+function methods.arc_to(self, center, sweep) end
+
+---Close the subpath with a straight line back to its start. Drawing after this starts a new
+---subpath from there.
+---@param self Path
+---@return Path
+function methods.close(self) end
+
+---Flatten the path into a line strip for each subpath. Curves and arcs are split into lines
+---that stray from them by at most `tolerance`, which defaults to `0.25`.
+---@param self Path
+---@param tolerance number?
+---@return LineStrip[]
+---@nodiscard
+function methods.flatten(self, tolerance) end
+
+---Flatten the path into a polygon for each subpath, treating them all as closed.
+---@param self Path
+---@param tolerance number?
+---@return Polygon[]
+---@nodiscard
+function methods.to_polygons(self, tolerance) end
+
+---Flatten and triangulate the area inside the path. Subpaths are filled with the even-odd rule,
+---so a subpath inside another one cuts a hole out of it.
+---@param self Path
+---@param tolerance number?
+---@return Triangle[]
+---@nodiscard
+function methods.triangulate(self, tolerance) end
+
+---Length of the flattened path.
+---@param self Path
+---@param tolerance number?
+---@return number
+---@nodiscard
+function methods.length(self, tolerance) end
+
+---Measure the flattened path, so that points can be found by their distance along it.
+---@param self Path
+---@param tolerance number?
+---@return PathMeasure
+---@nodiscard
+function methods.measure(self, tolerance) end
+
+---Total length of the path.
+---@param self PathMeasure
+---@return number
+---@nodiscard
+function PathMeasure.length(self) end
+
+---The point at the distance along the path and the direction the path is heading there. The
+---distance is clamped to the path's length.
+---@param self PathMeasure
+---@param dist number
+---@return Vec2 point
+---@return Vec2 direction
+---@nodiscard
+function PathMeasure.sample(self, dist) end
+
+---The point at the distance along the path, clamped to the path's length.
+---@param self PathMeasure
+---@param dist number
+---@return Vec2
+---@nodiscard
+function PathMeasure.point_at(self, dist) end
+
+---The direction the path is heading at the distance along it, clamped to the path's length.
+---@param self PathMeasure
+---@param dist number
+---@return Vec2
+---@nodiscard
+function PathMeasure.tangent_at(self, dist) end
+
+return module
@@ -144,4 +144,14 @@ function methods.transform_in_place(self, mat) end
 ---@param amount Vec2
 function methods.translate(self, amount) end
 
+---Split the polygon into triangles that cover it, leaving out the holes. Unlike the polygon's
+---other methods, this works on concave polygons as well as convex ones. If `fill` is set, will
+---fill that table and return it instead of creating a new one.
+---@param self Polygon
+---@param holes Polygon[]?
+---@param fill Triangle[]?
+---@return Triangle[]
+---@nodiscard
+function methods.triangulate(self, holes, fill) end
+
 return module
\ No newline at end of file
@@ -14,6 +14,7 @@ mod mat2;
 mod mat3;
 mod mat4;
 mod octal;
+mod path;
 mod polygon;
 mod projection;
 mod quad;
@@ -26,6 +27,7 @@ mod shape;
 mod traits;
 mod transform;
 mod triangle;
+mod triangulate;
 mod vec2;
 mod vec3;
 mod vec4;
@@ -47,6 +49,7 @@ pub use mat2::*;
 pub use mat3::*;
 pub use mat4::*;
 pub use octal::*;
+pub use path::*;
 pub use polygon::*;
 pub use projection::*;
 pub use quad::*;
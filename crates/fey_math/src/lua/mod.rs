@@ -8,6 +8,7 @@ mod mat3_lua;
 mod mat4_lua;
 mod num_lua;
 mod octal_lua;
+mod path_lua;
 mod polygon_lua;
 mod projection_lua;
 mod quad_lua;
@@ -31,6 +32,7 @@ pub use mat3_lua::*;
 pub use mat4_lua::*;
 pub use num_lua::*;
 pub use octal_lua::*;
+pub use path_lua::*;
 pub use polygon_lua::*;
 pub use projection_lua::*;
 pub use quad_lua::*;
@@ -77,6 +79,7 @@ pub type MathModules = (
     Mat4Module,
     NumModule,
     OctalModule,
+    PathModule,
     PolygonModule,
     ProjectionModule,
     QuadModule,
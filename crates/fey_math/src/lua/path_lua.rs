@@ -0,0 +1,116 @@
+use crate::{PathF, PathMeasureF, RadiansF, Vec2F};
+use fey_lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{AnyUserData, Lua, UserData, UserDataMethods, UserDataRef, UserDataRefMut, Value};
+
+pub type PathRef = UserDataRef<PathF>;
+pub type PathMut = UserDataRefMut<PathF>;
+pub type PathMeasureRef = UserDataRef<PathMeasureF>;
+
+/// Tolerance used to flatten paths when Lua doesn't provide one.
+const DEFAULT_TOLERANCE: f32 = 0.25;
+
+pub struct PathModule;
+
+impl LuaModule for PathModule {
+    const PATH: &'static str = "Path";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for PathModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods, true);
+    }
+}
+
+impl UserData for PathF {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods, false);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M, module: bool) {
+    if module {
+        methods.add_function("new", |_, ()| Ok(PathF::new()));
+    }
+    methods.add_function("clone", |_, this: PathRef| Ok(this.clone()));
+    methods.add_function("is_empty", |_, this: PathRef| Ok(this.is_empty()));
+    methods.add_function("clear", |_, mut this: PathMut| {
+        this.clear();
+        Ok(())
+    });
+    methods.add_function("pen", |_, this: PathRef| Ok(this.pen()));
+    methods.add_function("move_to", |_, (this, p): (AnyUserData, Vec2F)| {
+        this.borrow_mut::<PathF>()?.move_to(p);
+        Ok(this)
+    });
+    methods.add_function("line_to", |_, (this, p): (AnyUserData, Vec2F)| {
+        this.borrow_mut::<PathF>()?.line_to(p);
+        Ok(this)
+    });
+    methods.add_function(
+        "quad_to",
+        |_, (this, control, p): (AnyUserData, Vec2F, Vec2F)| {
+            this.borrow_mut::<PathF>()?.quad_to(control, p);
+            Ok(this)
+        },
+    );
+    methods.add_function(
+        "cubic_to",
+        |_, (this, control1, control2, p): (AnyUserData, Vec2F, Vec2F, Vec2F)| {
+            this.borrow_mut::<PathF>()?.cubic_to(control1, control2, p);
+            Ok(this)
+        },
+    );
+    methods.add_function(
+        "arc_to",
+        |_, (this, center, sweep): (AnyUserData, Vec2F, RadiansF)| {
+            this.borrow_mut::<PathF>()?.arc_to(center, sweep);
+            Ok(this)
+        },
+    );
+    methods.add_function("close", |_, this: AnyUserData| {
+        this.borrow_mut::<PathF>()?.close();
+        Ok(this)
+    });
+    methods.add_function("flatten", |lua, (this, tol): (PathRef, Option<f32>)| {
+        let strips = lua.create_table()?;
+        for strip in this.flatten(tol.unwrap_or(DEFAULT_TOLERANCE)) {
+            let table = lua.create_table()?;
+            table.set("points", strip.points)?;
+            table.set("closed", strip.closed)?;
+            strips.raw_push(table)?;
+        }
+        Ok(strips)
+    });
+    methods.add_function("to_polygons", |_, (this, tol): (PathRef, Option<f32>)| {
+        Ok(this.to_polygons(tol.unwrap_or(DEFAULT_TOLERANCE)))
+    });
+    methods.add_function("triangulate", |_, (this, tol): (PathRef, Option<f32>)| {
+        Ok(this.triangulate(tol.unwrap_or(DEFAULT_TOLERANCE)))
+    });
+    methods.add_function("length", |_, (this, tol): (PathRef, Option<f32>)| {
+        Ok(this.length(tol.unwrap_or(DEFAULT_TOLERANCE)))
+    });
+    methods.add_function("measure", |_, (this, tol): (PathRef, Option<f32>)| {
+        Ok(this.measure(tol.unwrap_or(DEFAULT_TOLERANCE)))
+    });
+}
+
+impl UserData for PathMeasureF {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("length", |_, this: PathMeasureRef| Ok(this.length()));
+        methods.add_function("sample", |_, (this, dist): (PathMeasureRef, f32)| {
+            Ok(this.sample(dist))
+        });
+        methods.add_function("point_at", |_, (this, dist): (PathMeasureRef, f32)| {
+            Ok(this.point_at(dist))
+        });
+        methods.add_function("tangent_at", |_, (this, dist): (PathMeasureRef, f32)| {
+            Ok(this.tangent_at(dist))
+        });
+    }
+}
@@ -136,6 +136,23 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M, module: bool) {
         this.translate(off);
         Ok(())
     });
+    methods.add_function(
+        "triangulate",
+        |lua, (this, holes, fill): (PolygonRef, Option<Vec<PolygonRef>>, Option<Table>)| {
+            let fill = match fill {
+                Some(fill) => {
+                    fill.clear()?;
+                    fill
+                }
+                None => lua.create_table()?,
+            };
+            let holes: Vec<PolygonF> = holes.into_iter().flatten().map(|h| h.clone()).collect();
+            for tri in this.triangulate_with_holes(&holes) {
+                fill.raw_push(tri)?;
+            }
+            Ok(fill)
+        },
+    );
     methods.add_function("centroid", |_, this: PolygonRef| Ok(this.centroid()));
     methods.add_function("contains", |_, (this, p): (PolygonRef, Vec2F)| {
         Ok(this.contains(p))
@@ -0,0 +1,473 @@
+use crate::triangulate::triangulate;
+use crate::{Angle, Float, Interp, Polygon, Radians, Triangle, Vec2, triangle, vec2};
+use serde::{Deserialize, Serialize};
+
+pub type PathF = Path<f32>;
+pub type PathMeasureF = PathMeasure<f32>;
+
+/// A command of a [`Path`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PathSegment<T> {
+    /// Start a new subpath at the point.
+    MoveTo(Vec2<T>),
+
+    /// A straight line to the point.
+    LineTo(Vec2<T>),
+
+    /// A quadratic bezier curve, bending towards the control point, to the point.
+    QuadTo(Vec2<T>, Vec2<T>),
+
+    /// A cubic bezier curve, bending towards the two control points, to the point.
+    CubicTo(Vec2<T>, Vec2<T>, Vec2<T>),
+
+    /// A circular arc around the center, sweeping the angle.
+    ArcTo(Vec2<T>, Radians<T>),
+
+    /// A straight line back to the start of the subpath, closing it.
+    Close,
+}
+
+/// A series of connected points, produced by [flattening](Path::flatten) a path.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LineStrip<T> {
+    /// The points of the strip.
+    pub points: Vec<Vec2<T>>,
+
+    /// If the last point connects back to the first.
+    pub closed: bool,
+}
+
+/// A shape made of lines, curves and arcs, built up by moving a pen around.
+///
+/// A path is made of subpaths. Each one starts where the pen was [moved](Self::move_to) to and
+/// can be [closed](Self::close) to connect it back to its start. Paths can be
+/// [flattened](Self::flatten) into line strips, [filled](Self::triangulate), or
+/// [measured](Self::measure) to move objects along them.
+///
+/// ```ignore
+/// let mut path = PathF::new();
+/// path.move_to(vec2(0.0, 0.0))
+///     .line_to(vec2(100.0, 0.0))
+///     .quad_to(vec2(150.0, 0.0), vec2(150.0, 50.0))
+///     .arc_to(vec2(150.0, 100.0), Degrees(180.0))
+///     .close();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Path<T> {
+    segments: Vec<PathSegment<T>>,
+    start: Vec2<T>,
+    pen: Vec2<T>,
+    open: bool,
+}
+
+/// Most segments a single curve or arc will be flattened into.
+#[inline]
+fn max_segs<T: Float>() -> T {
+    T::NUM_255 * T::FOUR
+}
+
+impl<T: Float> Path<T> {
+    /// Create a new empty path, with the pen at the origin.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            start: Vec2::ZERO,
+            pen: Vec2::ZERO,
+            open: false,
+        }
+    }
+
+    /// The path's segments.
+    #[inline]
+    pub fn segments(&self) -> &[PathSegment<T>] {
+        &self.segments
+    }
+
+    /// If the path has no segments.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Remove all segments from the path and move the pen back to the origin.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Where the pen currently is, which is where the next segment will start.
+    #[inline]
+    pub fn pen(&self) -> Vec2<T> {
+        self.pen
+    }
+
+    /// Start a subpath at the pen if the last one was closed.
+    #[inline]
+    fn begin(&mut self) {
+        if !self.open {
+            self.segments.push(PathSegment::MoveTo(self.pen));
+            self.start = self.pen;
+            self.open = true;
+        }
+    }
+
+    /// Move the pen to the point, starting a new subpath.
+    pub fn move_to(&mut self, p: Vec2<T>) -> &mut Self {
+        if let Some(PathSegment::MoveTo(last)) = self.segments.last_mut() {
+            *last = p;
+        } else {
+            self.segments.push(PathSegment::MoveTo(p));
+        }
+        self.start = p;
+        self.pen = p;
+        self.open = true;
+        self
+    }
+
+    /// Add a straight line from the pen to the point.
+    #[inline]
+    pub fn line_to(&mut self, p: Vec2<T>) -> &mut Self {
+        self.begin();
+        self.segments.push(PathSegment::LineTo(p));
+        self.pen = p;
+        self
+    }
+
+    /// Add a quadratic bezier curve from the pen to the point.
+    #[inline]
+    pub fn quad_to(&mut self, control: Vec2<T>, p: Vec2<T>) -> &mut Self {
+        self.begin();
+        self.segments.push(PathSegment::QuadTo(control, p));
+        self.pen = p;
+        self
+    }
+
+    /// Add a cubic bezier curve from the pen to the point.
+    #[inline]
+    pub fn cubic_to(&mut self, control1: Vec2<T>, control2: Vec2<T>, p: Vec2<T>) -> &mut Self {
+        self.begin();
+        self.segments
+            .push(PathSegment::CubicTo(control1, control2, p));
+        self.pen = p;
+        self
+    }
+
+    /// Add a circular arc that starts at the pen and sweeps the angle around the center. Positive
+    /// angles sweep in the direction that turns the x-axis towards the y-axis.
+    pub fn arc_to(&mut self, center: Vec2<T>, sweep: impl Angle<T>) -> &mut Self {
+        let sweep = sweep.to_radians();
+        self.begin();
+        self.segments.push(PathSegment::ArcTo(center, sweep));
+        self.pen = center + rotate(self.pen - center, sweep.0);
+        self
+    }
+
+    /// Close the subpath with a straight line back to its start. Drawing after this starts a new
+    /// subpath from there.
+    #[inline]
+    pub fn close(&mut self) -> &mut Self {
+        if self.open {
+            self.segments.push(PathSegment::Close);
+            self.pen = self.start;
+            self.open = false;
+        }
+        self
+    }
+}
+
+impl<T: Float + Interp<Factor = T>> Path<T> {
+    /// Flatten the path, calling `f` with the points of each subpath and if it is closed.
+    /// Curves and arcs are split into lines that stray from them by at most `tolerance`.
+    pub fn flatten_with(&self, tolerance: T, mut f: impl FnMut(&[Vec2<T>], bool)) {
+        let mut points = Vec::new();
+        let mut pen = Vec2::ZERO;
+        let mut start = Vec2::ZERO;
+        for &seg in &self.segments {
+            match seg {
+                PathSegment::MoveTo(p) => {
+                    if points.len() > 1 {
+                        f(&points, false);
+                    }
+                    points.clear();
+                    points.push(p);
+                    start = p;
+                    pen = p;
+                }
+                PathSegment::LineTo(p) => {
+                    points.push(p);
+                    pen = p;
+                }
+                PathSegment::QuadTo(c, p) => {
+                    let dev = (pen - c * T::TWO + p).len() * T::QUARTER;
+                    let n = curve_segs(dev, tolerance);
+                    for t in steps(n) {
+                        points.push(pen.quad_bezier(c, p, t));
+                    }
+                    points.push(p);
+                    pen = p;
+                }
+                PathSegment::CubicTo(c1, c2, p) => {
+                    let dev = T::max((pen - c1 * T::TWO + c2).len(), (c1 - c2 * T::TWO + p).len())
+                        * T::THREE
+                        * T::QUARTER;
+                    let n = curve_segs(dev, tolerance);
+                    for t in steps(n) {
+                        points.push(pen.cubic_bezier(c1, c2, p, t));
+                    }
+                    points.push(p);
+                    pen = p;
+                }
+                PathSegment::ArcTo(center, Radians(sweep)) => {
+                    // the largest step whose chord strays from the arc by at most the tolerance
+                    let offset = pen - center;
+                    let step = T::TWO * T::sqrt(T::TWO * tolerance / offset.len());
+                    let n = T::clamp(
+                        T::ceil(T::abs(sweep) / T::min(step, T::PI_OVER_2)),
+                        T::ONE,
+                        max_segs(),
+                    );
+                    for t in steps(n) {
+                        points.push(center + rotate(offset, sweep * t));
+                    }
+                    pen = center + rotate(offset, sweep);
+                    points.push(pen);
+                }
+                PathSegment::Close => {
+                    if points.len() > 1 && points.first() == points.last() {
+                        points.pop();
+                    }
+                    if points.len() > 1 {
+                        f(&points, true);
+                    }
+                    points.clear();
+                    pen = start;
+                }
+            }
+        }
+        if points.len() > 1 {
+            f(&points, false);
+        }
+    }
+
+    /// Flatten the path into a line strip for each subpath. Curves and arcs are split into lines
+    /// that stray from them by at most `tolerance`.
+    pub fn flatten(&self, tolerance: T) -> Vec<LineStrip<T>> {
+        let mut strips = Vec::new();
+        self.flatten_with(tolerance, |points, closed| {
+            strips.push(LineStrip {
+                points: points.to_vec(),
+                closed,
+            });
+        });
+        strips
+    }
+
+    /// Flatten the path into a polygon for each subpath, treating them all as closed. Subpaths
+    /// with fewer than 3 points are skipped.
+    pub fn to_polygons(&self, tolerance: T) -> Vec<Polygon<T>> {
+        let mut polys = Vec::new();
+        self.flatten_with(tolerance, |points, _| {
+            if points.len() > 2 {
+                polys.push(Polygon::from_vec(points.to_vec()));
+            }
+        });
+        polys
+    }
+
+    /// Flatten and triangulate the area inside the path, appending the points to `points` and
+    /// the point indices of each triangle to `indices`.
+    ///
+    /// Subpaths are treated as closed and filled with the even-odd rule, so a subpath inside
+    /// another one cuts a hole out of it, a subpath inside that hole is filled again, and so on.
+    pub fn triangulate_into(
+        &self,
+        tolerance: T,
+        points: &mut Vec<Vec2<T>>,
+        indices: &mut Vec<usize>,
+    ) {
+        let polys = self.to_polygons(tolerance);
+
+        // how many other subpaths each subpath is inside of
+        let depths: Vec<usize> = polys
+            .iter()
+            .enumerate()
+            .map(|(i, poly)| {
+                let p = poly.points()[0];
+                polys
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, other)| j != i && contains(other.points(), p))
+                    .count()
+            })
+            .collect();
+
+        let mut tris = Vec::new();
+        for (i, outline) in polys.iter().enumerate() {
+            if depths[i] % 2 == 1 {
+                continue;
+            }
+
+            // the outline's holes are the subpaths one level deeper inside of it
+            let holes: Vec<&[Vec2<T>]> = polys
+                .iter()
+                .enumerate()
+                .filter(|&(j, hole)| {
+                    depths[j] == depths[i] + 1 && contains(outline.points(), hole.points()[0])
+                })
+                .map(|(_, hole)| hole.points())
+                .collect();
+
+            tris.clear();
+            triangulate(outline.points(), &holes, &mut tris);
+            let start = points.len();
+            points.extend_from_slice(outline.points());
+            for hole in holes {
+                points.extend_from_slice(hole);
+            }
+            indices.extend(tris.iter().map(|&i| start + i));
+        }
+    }
+
+    /// Flatten and triangulate the area inside the path. See
+    /// [`triangulate_into`](Self::triangulate_into) for how subpaths are filled.
+    pub fn triangulate(&self, tolerance: T) -> Vec<Triangle<T>> {
+        let mut points = Vec::new();
+        let mut indices = Vec::new();
+        self.triangulate_into(tolerance, &mut points, &mut indices);
+        indices
+            .chunks_exact(3)
+            .map(|tri| triangle(points[tri[0]], points[tri[1]], points[tri[2]]))
+            .collect()
+    }
+
+    /// Measure the flattened path, so that points can be found by their distance along it.
+    #[inline]
+    pub fn measure(&self, tolerance: T) -> PathMeasure<T> {
+        PathMeasure::new(self, tolerance)
+    }
+
+    /// Length of the flattened path.
+    pub fn length(&self, tolerance: T) -> T {
+        let mut len = T::ZERO;
+        self.flatten_with(tolerance, |points, closed| {
+            for pair in points.windows(2) {
+                len += pair[0].dist(pair[1]);
+            }
+            if closed {
+                len += points[points.len() - 1].dist(points[0]);
+            }
+        });
+        len
+    }
+}
+
+/// A flattened [`Path`] that can find points by their distance along it, for moving objects
+/// along the path at a steady speed.
+///
+/// ```ignore
+/// let rail = path.measure(0.1);
+/// self.dist = (self.dist + speed * ctx.time.delta()) % rail.length();
+/// let (pos, dir) = rail.sample(self.dist);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathMeasure<T> {
+    points: Vec<Vec2<T>>,
+    dists: Vec<T>,
+}
+
+impl<T: Float + Interp<Factor = T>> PathMeasure<T> {
+    /// Measure the path, flattening it with the tolerance. Gaps between subpaths have no
+    /// length, so the end of one subpath leads straight into the start of the next.
+    pub fn new(path: &Path<T>, tolerance: T) -> Self {
+        let mut points: Vec<Vec2<T>> = Vec::new();
+        let mut dists = Vec::new();
+        let mut len = T::ZERO;
+        path.flatten_with(tolerance, |strip, closed| {
+            let end = closed.then(|| strip[0]);
+            for (i, &p) in strip.iter().chain(end.iter()).enumerate() {
+                if i > 0 {
+                    len += points[points.len() - 1].dist(p);
+                }
+                points.push(p);
+                dists.push(len);
+            }
+        });
+        Self { points, dists }
+    }
+
+    /// Total length of the path.
+    #[inline]
+    pub fn length(&self) -> T {
+        self.dists.last().copied().unwrap_or(T::ZERO)
+    }
+
+    /// The point at the distance along the path and the direction the path is heading there.
+    /// The distance is clamped to the path's length.
+    pub fn sample(&self, dist: T) -> (Vec2<T>, Vec2<T>) {
+        match self.points.len() {
+            0 => return (Vec2::ZERO, Vec2::ZERO),
+            1 => return (self.points[0], Vec2::ZERO),
+            _ => {}
+        }
+        let dist = T::clamp(dist, T::ZERO, self.length());
+        let i = self.dists.partition_point(|&d| d < dist).max(1);
+        let (a, b) = (self.points[i - 1], self.points[i]);
+        let seg_len = self.dists[i] - self.dists[i - 1];
+        let t = match seg_len > T::ZERO {
+            true => (dist - self.dists[i - 1]) / seg_len,
+            false => T::ZERO,
+        };
+        (a.lerp(b, t), (b - a).norm_safe())
+    }
+
+    /// The point at the distance along the path, clamped to the path's length.
+    #[inline]
+    pub fn point_at(&self, dist: T) -> Vec2<T> {
+        self.sample(dist).0
+    }
+
+    /// The direction the path is heading at the distance along it, clamped to the path's length.
+    #[inline]
+    pub fn tangent_at(&self, dist: T) -> Vec2<T> {
+        self.sample(dist).1
+    }
+}
+
+/// Rotate the vector by the angle in radians.
+#[inline]
+fn rotate<T: Float>(v: Vec2<T>, angle: T) -> Vec2<T> {
+    let (sin, cos) = T::sin_cos(angle);
+    vec2(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+/// How many lines to split a curve into so it strays by at most the tolerance, using Wang's
+/// formula with the curve's deviation from a straight line.
+#[inline]
+fn curve_segs<T: Float>(dev: T, tolerance: T) -> T {
+    T::clamp(T::ceil(T::sqrt(dev / tolerance)), T::ONE, max_segs())
+}
+
+/// The parameters splitting `0..1` into `n` steps, leaving out both ends.
+#[inline]
+fn steps<T: Float>(n: T) -> impl Iterator<Item = T> {
+    let step = T::ONE / n;
+    (1..n.to_usize()).scan(T::ZERO, move |t, _| {
+        *t += step;
+        Some(*t)
+    })
+}
+
+/// If the point is inside the polygon, using the even-odd rule so it works on any polygon.
+fn contains<T: Float>(poly: &[Vec2<T>], p: Vec2<T>) -> bool {
+    let mut inside = false;
+    let mut j = poly.len() - 1;
+    for i in 0..poly.len() {
+        let (a, b) = (poly[i], poly[j]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
@@ -1,4 +1,5 @@
-use crate::{Float, Line, Num, Numeric, Quad, Rect, Triangle, Vec2, line};
+use crate::triangulate::triangulate;
+use crate::{Float, Line, Num, Numeric, Quad, Rect, Triangle, Vec2, line, triangle};
 use serde::{Deserialize, Serialize};
 
 pub type PolygonF = Polygon<f32>;
@@ -192,6 +193,38 @@ impl<T: Float> Polygon<T> {
         poly
     }
 
+    /// Split the polygon into triangles that cover it. Unlike the polygon's other methods, this
+    /// works on concave polygons as well as convex ones.
+    #[inline]
+    pub fn triangulate(&self) -> Vec<Triangle<T>> {
+        self.triangulate_with_holes(&[])
+    }
+
+    /// Split the polygon into triangles that cover it, leaving out the holes. The holes should
+    /// be inside the polygon and not overlap each other.
+    pub fn triangulate_with_holes(&self, holes: &[Self]) -> Vec<Triangle<T>> {
+        let mut inds = Vec::new();
+        self.triangulate_indices(holes, &mut inds);
+        let points: Vec<Vec2<T>> = self
+            .points()
+            .iter()
+            .chain(holes.iter().flat_map(Self::points))
+            .copied()
+            .collect();
+        inds.chunks_exact(3)
+            .map(|tri| triangle(points[tri[0]], points[tri[1]], points[tri[2]]))
+            .collect()
+    }
+
+    /// Split the polygon into triangles, leaving out the holes, and append the point indices of
+    /// each triangle to `into`. Indices refer to the polygon's points followed by the points of
+    /// each hole in order.
+    #[inline]
+    pub fn triangulate_indices(&self, holes: &[Self], into: &mut Vec<usize>) {
+        let holes: Vec<&[Vec2<T>]> = holes.iter().map(Self::points).collect();
+        triangulate(self.points(), &holes, into);
+    }
+
     // /// Transform the polygon by the matrix.
     // #[inline]
     // pub fn transform_in_place(&mut self, mat: &Affine2<T>) {
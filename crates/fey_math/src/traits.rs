@@ -1,5 +1,6 @@
 //! Helper traits for scalar and math types.
 
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display};
 use std::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
@@ -306,6 +307,13 @@ pub trait Float: Signed {
 
     /// Returns e^(x), (the exponential function).
     fn exp(x: Self) -> Self;
+
+    /// Returns true if the number is neither infinite nor NaN.
+    fn is_finite(x: Self) -> bool;
+
+    /// Returns the ordering between the two numbers, ordering every value including NaN. See
+    /// [`f32::total_cmp`].
+    fn total_cmp(x: &Self, y: &Self) -> Ordering;
 }
 
 macro_rules! impl_num {
@@ -491,6 +499,12 @@ macro_rules! impl_float {
 
                 #[inline]
                 fn exp(x: Self) -> Self { x.exp() }
+
+                #[inline]
+                fn is_finite(x: Self) -> bool { x.is_finite() }
+
+                #[inline]
+                fn total_cmp(x: &Self, y: &Self) -> Ordering { x.total_cmp(y) }
             }
         )*
     };
@@ -0,0 +1,241 @@
+use crate::{Float, Vec2, vec2};
+
+/// Triangulate the outline and its holes by ear clipping, appending the point indices of each
+/// triangle to `into`. Indices refer to the outline's points, followed by the points of each
+/// hole in order.
+pub(crate) fn triangulate<T: Float>(
+    outline: &[Vec2<T>],
+    holes: &[&[Vec2<T>]],
+    into: &mut Vec<usize>,
+) {
+    if outline.len() < 3 {
+        return;
+    }
+
+    let mut points = outline.to_vec();
+    let mut ring: Vec<usize> = (0..outline.len()).collect();
+    let area = signed_area(&points, &ring);
+    if area == T::ZERO || !T::is_finite(area) {
+        return;
+    }
+    if area < T::ZERO {
+        ring.reverse();
+    }
+
+    // holes wind the opposite way to the outline
+    let mut hole_rings = Vec::with_capacity(holes.len());
+    for hole in holes {
+        let start = points.len();
+        points.extend_from_slice(hole);
+        if hole.len() < 3 {
+            continue;
+        }
+        let mut hole_ring: Vec<usize> = (start..points.len()).collect();
+        let area = signed_area(&points, &hole_ring);
+        if area == T::ZERO || !T::is_finite(area) {
+            continue;
+        }
+        if area > T::ZERO {
+            hole_ring.reverse();
+        }
+        hole_rings.push(hole_ring);
+    }
+
+    // connect the holes to the outline from left to right, so each becomes part of it
+    let leftmost = |ring: &[usize]| {
+        (0..ring.len())
+            .min_by(|&a, &b| {
+                let (a, b) = (points[ring[a]], points[ring[b]]);
+                T::total_cmp(&a.x, &b.x).then(T::total_cmp(&a.y, &b.y))
+            })
+            .unwrap()
+    };
+    let mut hole_rings: Vec<(usize, Vec<usize>)> = hole_rings
+        .into_iter()
+        .map(|ring| (leftmost(&ring), ring))
+        .collect();
+    hole_rings.sort_by(|(a, ra), (b, rb)| T::total_cmp(&points[ra[*a]].x, &points[rb[*b]].x));
+    for (start, hole_ring) in hole_rings {
+        bridge_hole(&points, &mut ring, &hole_ring, start);
+    }
+
+    clip_ears(&points, ring, into);
+}
+
+/// Twice the signed area of the ring, positive if it winds the same way as the x axis turns
+/// towards the y axis.
+fn signed_area<T: Float>(points: &[Vec2<T>], ring: &[usize]) -> T {
+    let mut area = T::ZERO;
+    for i in 0..ring.len() {
+        let a = points[ring[i]];
+        let b = points[ring[(i + 1) % ring.len()]];
+        area += a.cross(b);
+    }
+    area
+}
+
+/// If the corner at `b` turns the same way as the outline, making it convex.
+#[inline]
+fn is_convex<T: Float>(a: Vec2<T>, b: Vec2<T>, c: Vec2<T>) -> bool {
+    (b - a).cross(c - b) > T::ZERO
+}
+
+/// If `p` is inside or on the edge of the triangle, which winds the same way as the outline.
+#[inline]
+fn in_triangle<T: Float>(a: Vec2<T>, b: Vec2<T>, c: Vec2<T>, p: Vec2<T>) -> bool {
+    (b - a).cross(p - a) >= T::ZERO
+        && (c - b).cross(p - b) >= T::ZERO
+        && (a - c).cross(p - c) >= T::ZERO
+}
+
+/// If the diagonal from `a` to `p` starts inside the polygon, where `prev` and `next` are the
+/// points before and after `a`.
+#[inline]
+fn locally_inside<T: Float>(prev: Vec2<T>, a: Vec2<T>, next: Vec2<T>, p: Vec2<T>) -> bool {
+    if is_convex(prev, a, next) {
+        (p - a).cross(next - p) <= T::ZERO && (prev - a).cross(p - prev) <= T::ZERO
+    } else {
+        (p - a).cross(prev - p) > T::ZERO || (next - a).cross(p - next) > T::ZERO
+    }
+}
+
+/// Splice the hole into the ring by connecting the hole's leftmost point to a visible point of
+/// the ring with a pair of coincident edges.
+fn bridge_hole<T: Float>(points: &[Vec2<T>], ring: &mut Vec<usize>, hole: &[usize], start: usize) {
+    let n = ring.len();
+    let m = points[hole[start]];
+
+    // cast a ray left from the hole to find the nearest edge of the ring that it hits
+    let mut best_x = T::MIN;
+    let mut found = None;
+    for i in 0..n {
+        let a = points[ring[i]];
+        let b = points[ring[(i + 1) % n]];
+        if m.y <= a.y && m.y >= b.y && a.y != b.y {
+            let x = a.x + (m.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if x <= m.x && x > best_x {
+                best_x = x;
+                found = Some(if a.x < b.x { i } else { (i + 1) % n });
+                if x == m.x {
+                    // the hole touches the edge
+                    break;
+                }
+            }
+        }
+    }
+    let Some(mut pos) = found else {
+        return;
+    };
+
+    // a point of the ring inside the triangle formed by the hole, the hit, and the chosen end
+    // of the edge would block the bridge, so connect to the one closest in angle instead
+    if best_x != m.x {
+        let hit = vec2(best_x, m.y);
+        let p = points[ring[pos]];
+        let (a, c) = match m.y < p.y {
+            true => (m, hit),
+            false => (hit, m),
+        };
+        let mut min_tan = T::MAX;
+        for i in 0..n {
+            let q = points[ring[i]];
+            if i == pos || q.x > m.x || q.x < p.x || q.x == m.x {
+                continue;
+            }
+            if !in_triangle(a, p, c, q) && !in_triangle(c, p, a, q) {
+                continue;
+            }
+            let prev = points[ring[(i + n - 1) % n]];
+            let next = points[ring[(i + 1) % n]];
+            let tan = T::abs(m.y - q.y) / (m.x - q.x);
+            let closer = tan < min_tan || (tan == min_tan && q.x > points[ring[pos]].x);
+            if closer && locally_inside(prev, q, next, m) {
+                min_tan = tan;
+                pos = i;
+            }
+        }
+    }
+
+    let mut spliced = Vec::with_capacity(n + hole.len() + 2);
+    spliced.extend_from_slice(&ring[..=pos]);
+    spliced.extend_from_slice(&hole[start..]);
+    spliced.extend_from_slice(&hole[..start]);
+    spliced.push(hole[start]);
+    spliced.push(ring[pos]);
+    spliced.extend_from_slice(&ring[pos + 1..]);
+    *ring = spliced;
+}
+
+/// If the corner at position `i` of the ring is an ear, meaning no other point of the ring is
+/// inside the triangle it forms with its neighbors.
+fn is_ear<T: Float>(points: &[Vec2<T>], ring: &[usize], i: usize) -> bool {
+    let n = ring.len();
+    let a = points[ring[(i + n - 1) % n]];
+    let b = points[ring[i]];
+    let c = points[ring[(i + 1) % n]];
+    for j in 0..n {
+        let p = points[ring[j]];
+        if p == a || p == b || p == c {
+            continue;
+        }
+
+        // only reflex points can poke into the ear
+        let prev = points[ring[(j + n - 1) % n]];
+        let next = points[ring[(j + 1) % n]];
+        if !is_convex(prev, p, next) && in_triangle(a, b, c, p) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Clip ears off the ring until only a triangle remains.
+fn clip_ears<T: Float>(points: &[Vec2<T>], mut ring: Vec<usize>, into: &mut Vec<usize>) {
+    let corner = |ring: &[usize], i: usize| {
+        let n = ring.len();
+        (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n])
+    };
+    let mut i = 0;
+    let mut stalled = 0;
+    while ring.len() > 3 {
+        let n = ring.len();
+        let (a, b, c) = corner(&ring, i);
+        let cross = (points[b] - points[a]).cross(points[c] - points[b]);
+
+        // points that form no area with their neighbors are dropped
+        if cross == T::ZERO || (cross > T::ZERO && is_ear(points, &ring, i)) {
+            if cross != T::ZERO {
+                into.extend_from_slice(&[a, b, c]);
+            }
+            ring.remove(i);
+            i = (i + n - 2) % (n - 1);
+            stalled = 0;
+            continue;
+        }
+
+        i = (i + 1) % n;
+        stalled += 1;
+        if stalled < n {
+            continue;
+        }
+
+        // no ear was found, so the polygon must intersect itself; clip any convex corner to
+        // keep going
+        let Some(j) = (0..n).find(|&j| {
+            let (a, b, c) = corner(&ring, j);
+            is_convex(points[a], points[b], points[c])
+        }) else {
+            return;
+        };
+        let (a, b, c) = corner(&ring, j);
+        into.extend_from_slice(&[a, b, c]);
+        ring.remove(j);
+        i = (j + n - 2) % (n - 1);
+        stalled = 0;
+    }
+
+    let (a, b, c) = (points[ring[0]], points[ring[1]], points[ring[2]]);
+    if (b - a).cross(c - b) != T::ZERO {
+        into.extend_from_slice(&ring);
+    }
+}
@@ -8,6 +8,8 @@ const COLLISION_RECT: RectF = RectF::new(-4.0, -5.0, 8.0, 6.0);
 const JUMP_HEIGHT: f32 = TILE_SIZE.y as f32 * 4.2;
 const MOVE_SPEED: f32 = 800.0;
 const MAX_X_SPEED: f32 = 80.0;
+const JUMP_BUFFER: f32 = 0.1;
+const COYOTE_TIME: f32 = 0.1;
 
 pub struct Guy {
     pub pos: Vec2F,
@@ -19,6 +21,7 @@ pub struct Guy {
     pub is_face_smushed: bool,
     pub x_axis: VirtualAxis,
     pub jump_btn: VirtualButton,
+    pub coyote: BufferedInput,
 }
 
 impl Guy {
@@ -38,13 +41,18 @@ impl Guy {
             is_face_smushed: false,
             x_axis,
             jump_btn,
+            coyote: BufferedInput::new(COYOTE_TIME),
         }
     }
 
     pub fn update(&mut self, ctx: &Context, level: &crate::Level) {
         let input = vec2(self.x_axis.value(), 0.0);
-        if self.jump_btn.pressed() {
-            if self.is_grounded {
+        // jumping still works if pressed just before landing or just after walking off a ledge
+        self.coyote.update(&ctx.time, self.is_grounded);
+        if self.jump_btn.pressed_buffered(JUMP_BUFFER) {
+            if self.coyote.active(&ctx.time) {
+                self.jump_btn.consume_press();
+                self.coyote.consume();
                 self.vel.y = -GRAVITY.y * (2.0 * JUMP_HEIGHT / GRAVITY.y).sqrt();
                 self.squash_timer = 0.0;
             }
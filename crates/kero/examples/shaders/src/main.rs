@@ -24,7 +24,7 @@ impl Game for ShadersExample {
         // load the shader file
         let invert_shader = ctx
             .graphics
-            .create_shader(include_str!("../assets/invert_shader.wgsl"));
+            .create_shader(include_str!("../assets/invert_shader.wgsl"))?;
 
         // load a perlin noise texture
         let perlin = ctx
@@ -0,0 +1,109 @@
+---@meta
+
+---A named set of actions, such as the controls for gameplay, a menu, or a dialog box. A map only
+---receives input while it is pushed onto the `InputContexts` stack and no map above it is
+---blocking.
+---@class (exact) ActionMap: ActionMapMethods
+
+---@class ActionMapModule
+local module = {}
+
+---@class ActionMapMethods
+local methods = {}
+
+---Load action maps from a JSON file, returning them by name. The maps listen to the last active
+---gamepad.
+---@param path string
+---@return table<string, ActionMap>
+---@nodiscard
+function module.load(path) end
+
+---Load action maps from a JSON file in the game's config directory, returning them by name.
+---@param file_name string
+---@return table<string, ActionMap>
+---@nodiscard
+function module.load_config(file_name) end
+
+---Load action maps from a JSON string, returning them by name.
+---@param json string
+---@return table<string, ActionMap>
+---@nodiscard
+function module.parse(json) end
+
+---The map's name.
+---@return string
+---@nodiscard
+function methods:name() end
+
+---If the map is receiving input, meaning it is pushed onto the stack and no map above it is
+---blocking.
+---@return boolean
+---@nodiscard
+function methods:is_active() end
+
+---If the maps below this one receive no input while it is active.
+---@return boolean
+---@nodiscard
+function methods:blocking() end
+
+---Set whether the maps below this one receive no input while it is active, such as for a pause
+---menu. The default is false.
+---@param blocking boolean
+function methods:set_blocking(blocking) end
+
+---If the map hides the inputs its actions are bound to from the maps below it.
+---@return boolean
+---@nodiscard
+function methods:consumes() end
+
+---Set whether the map hides the inputs its actions are bound to from the maps below it while it
+---is active. The default is true.
+---@param consumes boolean
+function methods:set_consumes(consumes) end
+
+---Names of all the map's buttons, in sorted order.
+---@return string[]
+---@nodiscard
+function methods:button_names() end
+
+---Names of all the map's axes, in sorted order.
+---@return string[]
+---@nodiscard
+function methods:axis_names() end
+
+---If the button with the name is down.
+---@param name string
+---@return boolean
+---@nodiscard
+function methods:down(name) end
+
+---If the button with the name was pressed this frame.
+---@param name string
+---@return boolean
+---@nodiscard
+function methods:pressed(name) end
+
+---If the button with the name was released this frame.
+---@param name string
+---@return boolean
+---@nodiscard
+function methods:released(name) end
+
+---Value of the button with the name, which is the highest value of any of its bindings.
+---@param name string
+---@return number
+---@nodiscard
+function methods:value(name) end
+
+---Value of the axis with the name from `-1.0` to `1.0`.
+---@param name string
+---@return number
+---@nodiscard
+function methods:axis_value(name) end
+
+---Consume the button's press for the rest of the frame, hiding the bindings that pressed it from
+---the maps below this one.
+---@param name string
+function methods:consume(name) end
+
+return module
@@ -0,0 +1,196 @@
+---@meta
+
+---@class (exact) VoiceParams
+---@field volume number?
+---@field pan number?
+---@field pitch number?
+---@field looping boolean?
+---@field paused boolean?
+---@field bus Bus?
+
+---@class (exact) Voice: VoiceMethods
+
+---@class VoiceMethods
+local voice = {}
+
+---@class (exact) Bus: BusMethods
+
+---@class BusMethods
+local bus = {}
+
+---@class Audio
+local Audio = {}
+
+---Output sample rate of the mixer.
+---@return integer
+---@nodiscard
+function Audio.sample_rate() end
+
+---If the audio is using the null output instead of a sound device.
+---@return boolean
+---@nodiscard
+function Audio.is_null() end
+
+---The master bus, which all other buses feed into.
+---@return Bus
+---@nodiscard
+function Audio.master() end
+
+---Get the bus with the provided name, creating it if it doesn't exist.
+---@param name string
+---@return Bus
+---@nodiscard
+function Audio.bus(name) end
+
+---Volume multiplier of the master bus.
+---@return number
+---@nodiscard
+function Audio.master_volume() end
+
+---Set the volume multiplier of the master bus.
+---@param volume number
+function Audio.set_master_volume(volume) end
+
+---Play a sound or music track.
+---@param src Sound|Music
+---@param params VoiceParams?
+---@return Voice
+function Audio.play(src, params) end
+
+---Stop all playing voices.
+function Audio.stop_all() end
+
+---How many voices are currently playing (or paused).
+---@return integer
+---@nodiscard
+function Audio.voice_count() end
+
+---If the voice is still playing (or paused) and hasn't finished or been stopped.
+---@param self Voice
+---@return boolean
+---@nodiscard
+function voice.is_playing(self) end
+
+---Stop the voice. It cannot be resumed afterwards.
+---@param self Voice
+function voice.stop(self) end
+
+---If the voice is paused.
+---@param self Voice
+---@return boolean
+---@nodiscard
+function voice.is_paused(self) end
+
+---Pause the voice.
+---@param self Voice
+function voice.pause(self) end
+
+---Resume the voice if it was paused.
+---@param self Voice
+function voice.resume(self) end
+
+---Set whether the voice is paused.
+---@param self Voice
+---@param paused boolean
+function voice.set_paused(self, paused) end
+
+---Volume multiplier of the voice.
+---@param self Voice
+---@return number
+---@nodiscard
+function voice.volume(self) end
+
+---Set the voice's volume multiplier.
+---@param self Voice
+---@param volume number
+function voice.set_volume(self, volume) end
+
+---Stereo pan of the voice, from `-1` (left) to `1` (right).
+---@param self Voice
+---@return number
+---@nodiscard
+function voice.pan(self) end
+
+---Set the voice's stereo pan, from `-1` (left) to `1` (right).
+---@param self Voice
+---@param pan number
+function voice.set_pan(self, pan) end
+
+---Playback speed multiplier of the voice.
+---@param self Voice
+---@return number
+---@nodiscard
+function voice.pitch(self) end
+
+---Set the voice's playback speed multiplier.
+---@param self Voice
+---@param pitch number
+function voice.set_pitch(self, pitch) end
+
+---If the voice loops when it reaches the end.
+---@param self Voice
+---@return boolean
+---@nodiscard
+function voice.looping(self) end
+
+---Set whether the voice loops when it reaches the end.
+---@param self Voice
+---@param looping boolean
+function voice.set_looping(self, looping) end
+
+---Playback position of the voice in seconds.
+---@param self Voice
+---@return number
+---@nodiscard
+function voice.position(self) end
+
+---Name of the bus.
+---@param self Bus
+---@return string
+---@nodiscard
+function bus.name(self) end
+
+---If this is the master bus.
+---@param self Bus
+---@return boolean
+---@nodiscard
+function bus.is_master(self) end
+
+---Volume multiplier of the bus.
+---@param self Bus
+---@return number
+---@nodiscard
+function bus.volume(self) end
+
+---Set the bus' volume multiplier.
+---@param self Bus
+---@param volume number
+function bus.set_volume(self, volume) end
+
+---If the bus is muted.
+---@param self Bus
+---@return boolean
+---@nodiscard
+function bus.muted(self) end
+
+---Set whether the bus is muted.
+---@param self Bus
+---@param muted boolean
+function bus.set_muted(self, muted) end
+
+---If the bus is paused.
+---@param self Bus
+---@return boolean
+---@nodiscard
+function bus.paused(self) end
+
+---Set whether the bus is paused.
+---@param self Bus
+---@param paused boolean
+function bus.set_paused(self, paused) end
+
+---Stop all voices playing through this bus.
+---@param self Bus
+function bus.stop_all(self) end
+
+return Audio
@@ -0,0 +1,77 @@
+---@meta
+
+---@class (exact) BufferedInput: BufferedInputMethods
+
+---@class BufferedInputModule: BufferedInputMethods
+local module = {}
+
+---@class BufferedInputMethods
+local methods = {}
+
+---Create a buffer that stays active for `seconds` after it was last triggered.
+---@param seconds number
+---@return BufferedInput
+---@nodiscard
+function module.new(seconds) end
+
+---Create a buffer that stays active for `frames` frames after it was last triggered.
+---@param frames integer
+---@return BufferedInput
+---@nodiscard
+function module.new_frames(frames) end
+
+---How long the buffer stays active after it was last triggered, as either seconds or frames. The
+---other value is `nil`.
+---@param self BufferedInput
+---@return number? seconds
+---@return integer? frames
+---@nodiscard
+function methods.window(self) end
+
+---Set the buffer to stay active for `seconds` after it was last triggered.
+---@param self BufferedInput
+---@param seconds number
+function methods.set_window(self, seconds) end
+
+---Set the buffer to stay active for `frames` frames after it was last triggered.
+---@param self BufferedInput
+---@param frames integer
+function methods.set_window_frames(self, frames) end
+
+---Trigger the buffer if `condition` is true. Call this every update to keep the buffer active
+---for a short time after the condition stops being true, eg. for coyote time.
+---@param self BufferedInput
+---@param condition boolean
+function methods.update(self, condition) end
+
+---Trigger the buffer, making it active for its window of time starting now.
+---@param self BufferedInput
+function methods.trigger(self) end
+
+---If the buffer was triggered within its window of time and hasn't been consumed.
+---@param self BufferedInput
+---@return boolean
+---@nodiscard
+function methods.active(self) end
+
+---If the buffer was triggered within the last `seconds` and hasn't been consumed, ignoring its own
+---window.
+---@param self BufferedInput
+---@param seconds number
+---@return boolean
+---@nodiscard
+function methods.active_within(self, seconds) end
+
+---If the buffer was triggered within the last `frames` frames and hasn't been consumed, ignoring
+---its own window.
+---@param self BufferedInput
+---@param frames integer
+---@return boolean
+---@nodiscard
+function methods.active_within_frames(self, frames) end
+
+---Consume the buffer, making it inactive until it is triggered again.
+---@param self BufferedInput
+function methods.consume(self) end
+
+return module
@@ -0,0 +1,37 @@
+---@meta
+
+---@class (exact) RecordParams
+---@field path string
+---@field format ("png"|"apng")?
+---@field fps integer?
+---@field every integer?
+---@field source Surface?
+
+---@class Capture
+local Capture = {}
+
+---Save a screenshot to a PNG file once the current frame has been rendered. If a surface is
+---provided, it is captured instead of the window. The surface must have an `Rgba8` format, or
+---an error is raised.
+---@param path string
+---@param surface Surface?
+function Capture.screenshot(path, surface) end
+
+---Start recording presented frames, stopping and saving any recording already in progress.
+---With the `"png"` format (the default), `path` is a directory of numbered PNG files. With
+---`"apng"`, it is a single animated PNG that plays back at `fps` (default 60). Only every
+---`every`th frame is captured, and if `source` is provided it is captured instead of the window.
+---Raises an error without starting if `every` or `fps` is 0, or `source` isn't `Rgba8`.
+---@param params RecordParams
+function Capture.start_recording(params) end
+
+---Stop recording and save the recording, returning how many frames were captured.
+---@return integer
+function Capture.stop_recording() end
+
+---If frames are currently being recorded.
+---@return boolean
+---@nodiscard
+function Capture.is_recording() end
+
+return Capture
@@ -0,0 +1,25 @@
+---@meta
+
+---Text is shared with other applications through the system clipboard. If the system clipboard
+---can't be opened (eg. when running headless), the game keeps its own clipboard instead.
+---@class Clipboard
+local Clipboard = {}
+
+---The text on the clipboard, if there is any. Raises an error if the clipboard can't be read.
+---@return string?
+---@nodiscard
+function Clipboard.text() end
+
+---If the clipboard is shared with other applications, or false if the game is keeping its own.
+---@return boolean
+---@nodiscard
+function Clipboard.is_system() end
+
+---Put the text on the clipboard. Raises an error if the clipboard can't be written to.
+---@param text string
+function Clipboard.set_text(text) end
+
+---Empty the clipboard. Raises an error if the clipboard can't be written to.
+function Clipboard.clear() end
+
+return Clipboard
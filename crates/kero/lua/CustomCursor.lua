@@ -0,0 +1,17 @@
+---@meta
+
+---A cursor made from an image.
+---@class (exact) CustomCursor
+
+---@class CustomCursorModule
+local module = {}
+
+---Create a cursor from the image. The hotspot is the pixel that points at the mouse position.
+---@param img Image
+---@param hotspot_x integer
+---@param hotspot_y integer
+---@return CustomCursor
+---@nodiscard
+function module.new(img, hotspot_x, hotspot_y) end
+
+return module
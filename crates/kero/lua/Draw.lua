@@ -1,7 +1,20 @@
 ---@meta
 
 ---@alias BlendMode "normal"|"add"|"subtract"|"multiply"
+---@alias MaskMode "inside"|"outside"
 ---@alias Topology "triangles"|"lines"|"points"
+---@alias LineJoin "miter"|"round"|"bevel"
+---@alias LineCap "butt"|"round"|"square"
+
+---How a path is stroked. Any field left out uses its default, and a number can be passed
+---instead of a table to stroke with just that width.
+---@class Stroke
+---@field width number? Width of the stroke, defaults to `1`.
+---@field join LineJoin? Shape of the corners, defaults to `"miter"`.
+---@field cap LineCap? Shape of the open ends, defaults to `"butt"`.
+---@field miter_limit number? Maximum length of a miter join as a multiple of the width, defaults to `4`.
+---@field dashes number[]? Alternating lengths of dashes and gaps, solid if empty or `nil`, or if the dashes are too short to draw.
+---@field dash_offset number? Distance into the dash pattern that the stroke starts at.
 
 ---@class DrawModule
 local Draw = {}
@@ -110,6 +123,27 @@ function Draw.clip_rect(self) end
 ---@param value Rect
 function Draw.set_clip_rect(value) end
 
+---Start drawing a mask. Until `end_mask` is called, anything drawn is added to the mask instead
+---of appearing on the surface. With the default shader, fully transparent pixels are left out of
+---the mask. Masks are ignored on surfaces created without a stencil buffer.
+function Draw.begin_mask() end
+
+---Stop drawing the mask, so that anything drawn afterward is only drawn where the mode allows.
+---@param mode MaskMode
+function Draw.end_mask(mode) end
+
+---The current mask mode, or `nil` if drawing isn't masked.
+---@return MaskMode?
+---@nodiscard
+function Draw.mask_mode() end
+
+---Set where drawing is allowed by the mask, or pass `nil` to stop masking.
+---@param mode MaskMode?
+function Draw.set_mask_mode(mode) end
+
+---Erase the whole mask, ignoring the clip rectangle.
+function Draw.clear_mask() end
+
 ---The current transform.
 ---@return Affine2
 ---@nodiscard
@@ -235,6 +269,20 @@ function Draw.triangle_outline(a, b, c, color) end
 ---@param color Color
 function Draw.triangle_obj_outline(tri, color) end
 
+---Draw a triangle outline with the thickness.
+---@param a Vec2
+---@param b Vec2
+---@param c Vec2
+---@param color Color
+---@param thickness number
+function Draw.triangle_outline_thick(a, b, c, color, thickness) end
+
+---Draw a triangle outline with the thickness.
+---@param tri Triangle
+---@param color Color
+---@param thickness number
+function Draw.triangle_obj_outline_thick(tri, color, thickness) end
+
 ---Draw a filled quad.
 ---@param a Vec2
 ---@param b Vec2
@@ -261,6 +309,21 @@ function Draw.quad_outline(a, b, c, d, color) end
 ---@param color Color
 function Draw.quad_obj_outline(quad, color) end
 
+---Draw a quad outline with the thickness.
+---@param a Vec2
+---@param b Vec2
+---@param c Vec2
+---@param d Vec2
+---@param color Color
+---@param thickness number
+function Draw.quad_outline_thick(a, b, c, d, color, thickness) end
+
+---Draw a quad outline with the thickness.
+---@param quad Quad
+---@param color Color
+---@param thickness number
+function Draw.quad_obj_outline_thick(quad, color, thickness) end
+
 ---Draw a filled rectangle.
 ---@param x number
 ---@param y number
@@ -287,16 +350,44 @@ function Draw.rect_outline(x, y, w, h, color) end
 ---@param color Color
 function Draw.rect_obj_outline(rect, color) end
 
----Draw a filled polygon.
+---Draw a rectangle outline with the thickness, centered on the rectangle's edges.
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@param color Color
+---@param thickness number
+function Draw.rect_outline_thick(x, y, w, h, color, thickness) end
+
+---Draw a rectangle outline with the thickness, centered on the rectangle's edges.
+---@param rect Rect
+---@param color Color
+---@param thickness number
+function Draw.rect_obj_outline_thick(rect, color, thickness) end
+
+---Draw a filled polygon. If the polygon is concave, it is triangulated first.
 ---@param poly Polygon
 ---@param color Color
 function Draw.polygon(poly, color) end
 
+---Draw a filled polygon with the holes cut out of it. The holes should be inside the polygon
+---and not overlap each other.
+---@param poly Polygon
+---@param holes Polygon[]
+---@param color Color
+function Draw.polygon_with_holes(poly, holes, color) end
+
 ---Draw a polygon outline.
 ---@param poly Polygon
 ---@param color Color
 function Draw.polygon_outline(poly, color) end
 
+---Draw a polygon outline with the thickness.
+---@param poly Polygon
+---@param color Color
+---@param thickness number
+function Draw.polygon_outline_thick(poly, color, thickness) end
+
 ---Draw a filled circle.
 ---@param x number
 ---@param y number
@@ -339,6 +430,50 @@ function Draw.circle_outline(center, radius, color, seg_count) end
 ---@param seg_count integer?
 function Draw.circle_obj_outline(circ, color, seg_count) end
 
+---Draw a circle outline with the thickness.
+---@param x number
+---@param y number
+---@param radius number
+---@param color Color
+---@param thickness number
+---@param seg_count integer?
+function Draw.circle_outline_thick(x, y, radius, color, thickness, seg_count) end
+
+---Draw a circle outline with the thickness.
+---@param center Vec2
+---@param radius number
+---@param color Color
+---@param thickness number
+---@param seg_count integer?
+function Draw.circle_outline_thick(center, radius, color, thickness, seg_count) end
+
+---Draw a circle outline with the thickness.
+---@param circ Circle
+---@param color Color
+---@param thickness number
+---@param seg_count integer?
+function Draw.circle_obj_outline_thick(circ, color, thickness, seg_count) end
+
+---Draw a stroke along the series of points, optionally looping back to the start. Unlike
+---`lines`, the stroke is made of triangles, so it can be any width and have joins, caps and dashes.
+---@param points Vec2[]
+---@param stroke Stroke|number
+---@param color Color
+---@param loops boolean?
+function Draw.stroke_lines(points, stroke, color, loops) end
+
+---Draw a stroke along each subpath of the path. Curves are flattened finely enough that they
+---stay smooth at the current transform.
+---@param path Path
+---@param stroke Stroke|number
+---@param color Color
+function Draw.stroke_path(path, stroke, color) end
+
+---Fill the area inside the path. Subpaths inside of other subpaths cut holes out of them.
+---@param path Path
+---@param color Color
+function Draw.fill_path(path, color) end
+
 ---Draw a subtexture.
 ---@param sub SubTexture
 ---@param dst Quad
@@ -388,4 +523,15 @@ function Draw.custom(texture, topology, vertices, indices) end
 ---@param indices IndexBuffer
 function Draw.buffers(texture, topology, vertices, indices) end
 
+---Draw the provided vertex & index buffers once for every instance in the instance buffer. Each
+---instance transforms the vertices, maps their texture coordinates into its texture rect and
+---tints them, all on the GPU. Like `buffers`, the vertices are not transformed by the draw
+---transform.
+---@param texture Texture?
+---@param topology Topology
+---@param vertices VertexBuffer
+---@param indices IndexBuffer
+---@param instances InstanceBuffer
+function Draw.instanced(texture, topology, vertices, indices, instances) end
+
 return Draw
\ No newline at end of file
@@ -0,0 +1,44 @@
+---@meta
+
+---Something that happened to the game's window.
+---@class AppEvent
+---@field type "file_hovered"|"file_hover_cancelled"|"file_dropped"|"focused"|"occluded"|"theme_changed"
+---@field path string? The file being hovered or dropped.
+---@field focused boolean? If the window gained or lost focus.
+---@field occluded boolean? If the window became hidden or visible again.
+---@field theme "light"|"dark"|nil The system's new color theme.
+
+---@class Events
+local Events = {}
+
+---All events that happened this frame, in the order they happened.
+---@return AppEvent[]
+---@nodiscard
+function Events.all() end
+
+---Files that were dropped onto the window this frame.
+---@return string[]
+---@nodiscard
+function Events.dropped_files() end
+
+---Files that are currently being dragged over the window.
+---@return string[]
+---@nodiscard
+function Events.hovered_files() end
+
+---If the window gained focus this frame.
+---@return boolean
+---@nodiscard
+function Events.focus_gained() end
+
+---If the window lost focus this frame.
+---@return boolean
+---@nodiscard
+function Events.focus_lost() end
+
+---If the window is fully hidden from view.
+---@return boolean
+---@nodiscard
+function Events.occluded() end
+
+return Events
@@ -1,7 +1,26 @@
 ---@meta
 
+---@class (exact) AxisResponseParams
+---@field radial number?
+---@field axial number?
+---@field outer number?
+---@field curve ("linear"|"quadratic"|"cubic")?
+
 ---@alias GamepadStatus "wired"|"draining"|"charging"|"charged"
 
+---@class (exact) RumbleEffect
+local RumbleEffect = {}
+
+---If the effect is still playing.
+---@param self RumbleEffect
+---@return boolean
+---@nodiscard
+function RumbleEffect.is_playing(self) end
+
+---Stop the effect.
+---@param self RumbleEffect
+function RumbleEffect.stop(self) end
+
 ---@class (exact) Gamepad: GamepadMethods
 
 ---@class GamepadModule: GamepadMethods
@@ -32,6 +51,18 @@ function module.newly_connected(fill) end
 ---@nodiscard
 function module.last_active() end
 
+---How strongly gamepads rumble, from `0` (never) to `1` (full strength).
+---@return number
+---@nodiscard
+function module.rumble_scale() end
+
+---Set how strongly gamepads rumble, from `0` (never) to `1` (full strength).
+---@param scale number
+function module.set_rumble_scale(scale) end
+
+---Stop all rumble effects on every gamepad.
+function module.stop_all_rumble() end
+
 ---The gamepad name.
 ---@param self Gamepad
 ---@return string
@@ -93,7 +124,7 @@ function methods.btn_changed(self, btn) end
 ---@nodiscard
 function methods.value(self, btn) end
 
----The axis value from `-1.0` to `1.0`.
+---The raw axis value from `-1.0` to `1.0`, without any deadzones applied.
 ---@param self Gamepad
 ---@param axis GamepadAxis
 ---@return number
@@ -107,4 +138,38 @@ function methods.axis(self, axis) end
 ---@nodiscard
 function methods.axis_changed(self, axis) end
 
+---The stick's value with its response applied. Its length is never more than `1`.
+---@param self Gamepad
+---@param stick GamepadStick
+---@return Vec2
+---@nodiscard
+function methods.stick(self, stick) end
+
+---If the gamepad supports rumble.
+---@param self Gamepad
+---@return boolean
+---@nodiscard
+function methods.supports_rumble(self) end
+
+---Rumble the gamepad for `duration` seconds. The `strong` (low frequency) and `weak` (high
+---frequency) motors each go from `0` to `1`, and are scaled by `Gamepad.rumble_scale()`. Returns
+---`nil` if the gamepad doesn't support rumble, or if `duration` is not finite or too long.
+---@param self Gamepad
+---@param strong number
+---@param weak number
+---@param duration number
+---@return RumbleEffect?
+function methods.rumble(self, strong, weak, duration) end
+
+---Stop all of the gamepad's rumble effects.
+---@param self Gamepad
+function methods.stop_rumble(self) end
+
+---Set the deadzones and response curve applied to the stick. Missing deadzones default to `0`,
+---and the curve defaults to `"linear"`.
+---@param self Gamepad
+---@param stick GamepadStick
+---@param response AxisResponseParams
+function methods.set_stick_response(self, stick, response) end
+
 return module
\ No newline at end of file
@@ -0,0 +1,9 @@
+---@meta
+
+---@enum GamepadStick
+local GamepadStick = {
+    LEFT = 0,
+    RIGHT = 1,
+}
+
+return GamepadStick
\ No newline at end of file
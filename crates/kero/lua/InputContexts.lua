@@ -0,0 +1,51 @@
+---@meta
+
+---The stack of action maps receiving input. Maps higher in the stack take priority over the ones
+---below them.
+---@class InputContexts
+local InputContexts = {}
+
+---Push the map onto the top of the stack, moving it if it is already in the stack.
+---@param map ActionMap
+function InputContexts.push(map) end
+
+---Remove the map at the top of the stack and return it.
+---@return ActionMap?
+function InputContexts.pop() end
+
+---Remove the map from the stack, returning false if it wasn't in the stack.
+---@param map ActionMap
+---@return boolean
+function InputContexts.remove(map) end
+
+---Remove all maps from the stack.
+function InputContexts.clear() end
+
+---The map at the top of the stack.
+---@return ActionMap?
+---@nodiscard
+function InputContexts.top() end
+
+---The highest map in the stack with the name.
+---@param name string
+---@return ActionMap?
+---@nodiscard
+function InputContexts.get(name) end
+
+---If the map is in the stack.
+---@param map ActionMap
+---@return boolean
+---@nodiscard
+function InputContexts.contains(map) end
+
+---How many maps are in the stack.
+---@return integer
+---@nodiscard
+function InputContexts.len() end
+
+---All maps in the stack, from bottom to top.
+---@return ActionMap[]
+---@nodiscard
+function InputContexts.maps() end
+
+return InputContexts
@@ -0,0 +1,71 @@
+---@meta
+
+---Per-instance data for instanced drawing. Each instance draws the whole vertex buffer again,
+---with the vertex positions transformed by `transform`, texture coordinates mapped into `tex`,
+---and colors multiplied by `col`.
+---@class (exact) Instance: InstanceMethods
+---@field transform Affine2
+---@field tex Rect
+---@field col Color
+
+---@class InstanceModule : InstanceMethods
+local module = {}
+
+---@class InstanceMethods
+local methods = {}
+
+---Create a new instance.
+---@param transform Affine2
+---@param tex Rect
+---@param col Color
+---@return Instance
+---@nodiscard
+function module.new(transform, tex, col) end
+
+---Create an instance that uses the whole texture and leaves the vertex colors as they are.
+---@param transform Affine2
+---@return Instance
+---@nodiscard
+function module.simple(transform) end
+
+---Create an instance that uses the whole texture and tints the vertex colors.
+---@param transform Affine2
+---@param col Color
+---@return Instance
+---@nodiscard
+function module.tinted(transform, col) end
+
+---Returns a temporary copy of this value.
+---@param self Instance
+---@return Instance
+---@nodiscard
+function methods.clone(self) end
+
+---Boxes the value if not already boxed.
+---@param self Instance
+---@return Instance
+---@nodiscard
+function methods.box(self) end
+
+---Boxes a copy of this value.
+---@param self Instance
+---@return Instance
+---@nodiscard
+function methods.box_clone(self) end
+
+---Sets this instance's transform.
+---@param self Instance
+---@param transform Affine2
+function methods.set_transform(self, transform) end
+
+---Sets this instance's texture rect.
+---@param self Instance
+---@param tex Rect
+function methods.set_tex(self, tex) end
+
+---Sets this instance's color.
+---@param self Instance
+---@param col Color
+function methods.set_col(self, col) end
+
+return module
@@ -0,0 +1,40 @@
+---@meta
+
+---@class (exact) InstanceBuffer: InstanceBufferMethods
+
+---@class InstanceBufferModule: InstanceBufferMethods
+local module = {}
+
+---@class InstanceBufferMethods
+local methods = {}
+
+---Create a new instance buffer with the provided capacity.
+---@param capacity integer
+---@return InstanceBuffer
+---@nodiscard
+function module.new(capacity) end
+
+---Create a new instance buffer with the provided instances.
+---@param instances Instance[]
+---@return InstanceBuffer
+---@nodiscard
+function module.with(instances) end
+
+---How many instances are in the buffer.
+---@param self InstanceBuffer
+---@return integer
+---@nodiscard
+function methods.len(self) end
+
+---Currently allocated instance capacity the buffer has allocated.
+---@param self InstanceBuffer
+---@return integer
+---@nodiscard
+function methods.capacity(self) end
+
+---Set the buffer's instances.
+---@param self InstanceBuffer
+---@param instances Instance[]
+function methods.upload(self, instances) end
+
+return module
\ No newline at end of file
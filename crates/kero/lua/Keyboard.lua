@@ -1,5 +1,7 @@
 ---@meta
 
+---Presses, releases and repeats are seen once per frame by `update` and `render`, and separately
+---by exactly one `fixed_update`, so read them in whichever callback acts on them.
 ---@class Keyboard
 local Keyboard = {}
 
@@ -38,6 +40,16 @@ function Keyboard.pressed_or_repeated(key) end
 ---@nodiscard
 function Keyboard.text_input() end
 
+---If the input method editor is enabled, allowing the player to compose text.
+---@return boolean
+---@nodiscard
+function Keyboard.ime_enabled() end
+
+---Text being composed with the input method editor that hasn't been committed yet.
+---@return string
+---@nodiscard
+function Keyboard.preedit() end
+
 ---If left or right control is down.
 ---@return boolean
 ---@nodiscard
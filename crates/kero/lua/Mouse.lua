@@ -23,6 +23,12 @@ function Mouse.y() end
 ---@nodiscard
 function Mouse.scroll() end
 
+---How far the mouse moved this frame, straight from the device. This isn't stopped by the edges
+---of the window and keeps reporting while the cursor is locked.
+---@return Vec2
+---@nodiscard
+function Mouse.motion() end
+
 ---If the button is held down this frame.
 ---@param btn MouseButton
 ---@return boolean
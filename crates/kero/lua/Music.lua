@@ -0,0 +1,23 @@
+---@meta
+
+---@class (exact) Music: MusicMethods
+
+---@class MusicModule: MusicMethods
+local module = {}
+
+---@class MusicMethods
+local methods = {}
+
+---Load a WAV or OGG Vorbis music file. The music is decoded while it plays.
+---@param path string
+---@return Music
+---@nodiscard
+function module.load(path) end
+
+---Sample rate of the music.
+---@param self Music
+---@return integer
+---@nodiscard
+function methods.sample_rate(self) end
+
+return module
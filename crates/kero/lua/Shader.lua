@@ -26,12 +26,20 @@ local methods = {}
 ---@nodiscard
 function module.default() end
 
----Compile a shader from the source code.
+---Compile a shader from the source code. Raises an error if the shader fails to compile.
 ---@param source string
 ---@return Shader
 ---@nodiscard
 function module.new(source) end
 
+---Load a shader from a file, and reload it whenever the file changes. If a reload fails to
+---compile, the shader keeps using its last working version. Raises an error if the shader fails
+---to compile the first time.
+---@param path string
+---@return Shader
+---@nodiscard
+function module.watch(path) end
+
 ---Return a table of all the shader's parameters.
 ---@param self Shader
 ---@return { [string]: ParamType }
@@ -45,4 +53,16 @@ function methods.params(self) end
 ---@nodiscard
 function methods.param_type(self, name) end
 
+---The file the shader is watching, or `nil` if it isn't watching one.
+---@param self Shader
+---@return string?
+---@nodiscard
+function methods.path(self) end
+
+---If the most recent reload of a watched shader failed, the error it failed with.
+---@param self Shader
+---@return string?
+---@nodiscard
+function methods.reload_error(self) end
+
 return module
\ No newline at end of file
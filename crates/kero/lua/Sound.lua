@@ -0,0 +1,35 @@
+---@meta
+
+---@class (exact) Sound: SoundMethods
+
+---@class SoundModule: SoundMethods
+local module = {}
+
+---@class SoundMethods
+local methods = {}
+
+---Load and decode a WAV or OGG Vorbis file.
+---@param path string
+---@return Sound
+---@nodiscard
+function module.load(path) end
+
+---Sample rate of the sound.
+---@param self Sound
+---@return integer
+---@nodiscard
+function methods.sample_rate(self) end
+
+---How many stereo frames the sound contains.
+---@param self Sound
+---@return integer
+---@nodiscard
+function methods.frame_count(self) end
+
+---Duration of the sound in seconds.
+---@param self Sound
+---@return number
+---@nodiscard
+function methods.duration(self) end
+
+return module
@@ -8,13 +8,15 @@ local module = {}
 ---@class SurfaceMethods
 local methods = {}
 
----Create a new surface. If no format is provided, will default to `"rgba8"`.
+---Create a new surface. If no format is provided, will default to `"rgba8"`. Pass `true` for
+---`stencil` to give the surface a stencil buffer so masks can be drawn to it.
 ---@param width integer
 ---@param height integer
 ---@param format TextureFormat?
+---@param stencil boolean?
 ---@return Surface
 ---@nodiscard
-function module.new(width, height, format) end
+function module.new(width, height, format, stencil) end
 
 ---The surface's target texture.
 ---@param self Surface
@@ -46,6 +48,12 @@ function methods.height(self) end
 ---@nodiscard
 function methods.format(self) end
 
+---If the surface has a stencil buffer, so masks can be drawn to it.
+---@param self Surface
+---@return boolean
+---@nodiscard
+function methods.has_stencil(self) end
+
 ---The surface's texture.
 ---@param self Surface
 ---@return Texture
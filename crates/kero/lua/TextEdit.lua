@@ -0,0 +1,168 @@
+---@meta
+
+---The editable contents of a text field, with a caret, selection, and undo history. Positions are
+---byte offsets into the text, so `text:sub(1, caret)` is the text before the caret.
+---@class (exact) TextEdit: TextEditMethods
+
+---@class TextEditModule: TextEditMethods
+local module = {}
+
+---@class TextEditMethods
+local methods = {}
+
+---Create a text field.
+---@param text string?
+---@param multiline boolean?
+---@param max_chars integer?
+---@return TextEdit
+---@nodiscard
+function module.new(text, multiline, max_chars) end
+
+---The field's text.
+---@return string
+---@nodiscard
+function methods:text() end
+
+---Replace the field's text, placing the caret at the end and clearing the undo history.
+---@param text string
+function methods:set_text(text) end
+
+---Position of the caret.
+---@return integer
+---@nodiscard
+function methods:caret() end
+
+---Position the selection extends from to the caret.
+---@return integer
+---@nodiscard
+function methods:anchor() end
+
+---Start and end of the selected range.
+---@return integer start
+---@return integer end
+---@nodiscard
+function methods:selection() end
+
+---If any text is selected.
+---@return boolean
+---@nodiscard
+function methods:has_selection() end
+
+---The selected text.
+---@return string
+---@nodiscard
+function methods:selected_text() end
+
+---Move the caret to the position, optionally extending the selection to it.
+---@param pos integer
+---@param select boolean?
+function methods:set_caret(pos, select) end
+
+---Select the range, placing the caret at the end of it.
+---@param start integer
+---@param finish integer
+function methods:select(start, finish) end
+
+---Select all of the text.
+function methods:select_all() end
+
+---Move the caret one character left.
+---@param select boolean?
+function methods:move_left(select) end
+
+---Move the caret one character right.
+---@param select boolean?
+function methods:move_right(select) end
+
+---Move the caret to the start of the word before it.
+---@param select boolean?
+function methods:move_word_left(select) end
+
+---Move the caret to the start of the word after it.
+---@param select boolean?
+function methods:move_word_right(select) end
+
+---Move the caret up a line.
+---@param select boolean?
+function methods:move_up(select) end
+
+---Move the caret down a line.
+---@param select boolean?
+function methods:move_down(select) end
+
+---Move the caret to the start of its line.
+---@param select boolean?
+function methods:move_line_start(select) end
+
+---Move the caret to the end of its line.
+---@param select boolean?
+function methods:move_line_end(select) end
+
+---Move the caret to the start of the text.
+---@param select boolean?
+function methods:move_to_start(select) end
+
+---Move the caret to the end of the text.
+---@param select boolean?
+function methods:move_to_end(select) end
+
+---Insert text at the caret, replacing the selection.
+---@param text string
+function methods:insert(text) end
+
+---Delete the selection, or the character before the caret.
+function methods:backspace() end
+
+---Delete the selection, or the character after the caret.
+function methods:delete() end
+
+---Delete the selection, or the word before the caret.
+function methods:delete_word_left() end
+
+---Delete the selection, or the word after the caret.
+function methods:delete_word_right() end
+
+---Delete all of the text.
+function methods:clear() end
+
+---The selected text to put on the clipboard.
+---@return string?
+---@nodiscard
+function methods:copy() end
+
+---Delete the selected text and return it to put on the clipboard.
+---@return string?
+function methods:cut() end
+
+---Insert text from the clipboard at the caret, replacing the selection.
+---@param text string
+function methods:paste(text) end
+
+---Undo the last edit.
+---@return boolean
+function methods:undo() end
+
+---Redo the last undone edit.
+---@return boolean
+function methods:redo() end
+
+---Text being composed with the input method editor.
+---@return string
+---@nodiscard
+function methods:preedit() end
+
+---The text to draw, with any text being composed inserted at the caret.
+---@return string
+---@nodiscard
+function methods:display_text() end
+
+---Position of the caret in the display text.
+---@return integer
+---@nodiscard
+function methods:display_caret() end
+
+---Apply the keyboard's typing and editing keys, returning true if the text changed.
+---@return boolean
+function methods:update() end
+
+return module
@@ -8,11 +8,21 @@ local Time = {}
 ---@nodiscard
 function Time.fps() end
 
----Delta time since the last frame.
+---Delta time since the last frame, or the fixed step during `fixed_update`.
 ---@return number
 ---@nodiscard
 function Time.delta() end
 
+---Delta time of each fixed update.
+---@return number
+---@nodiscard
+function Time.fixed_delta() end
+
+---How far (0 to 1) the current frame is between the last fixed update and the next one.
+---@return number
+---@nodiscard
+function Time.alpha() end
+
 ---Total time passed since the app started.
 ---@return number
 ---@nodiscard
@@ -0,0 +1,76 @@
+---@meta
+
+---@alias TouchPhase "started"|"moved"|"ended"|"cancelled"
+
+---A single finger touching the screen.
+---@class Touch
+---@field id integer Identifies the finger until the touch ends.
+---@field pos Vec2 Position of the touch in window coordinates.
+---@field start_pos Vec2 Position the touch started at in window coordinates.
+---@field phase TouchPhase Stage of the touch as of its most recent event.
+---@field pressure number? How hard the screen is being pressed from `0` to `1`, if supported.
+
+---@class Touches
+local Touches = {}
+
+---How many fingers are touching the screen.
+---@return integer
+---@nodiscard
+function Touches.count() end
+
+---All fingers touching the screen, in the order they started touching it.
+---@return Touch[]
+---@nodiscard
+function Touches.all() end
+
+---The touch with the id, if that finger is touching the screen.
+---@param id integer
+---@return Touch?
+---@nodiscard
+function Touches.get(id) end
+
+---The finger that has been touching the screen the longest.
+---@return Touch?
+---@nodiscard
+function Touches.first() end
+
+---Touches that started this frame.
+---@return Touch[]
+---@nodiscard
+function Touches.pressed() end
+
+---Touches that ended or were cancelled this frame.
+---@return Touch[]
+---@nodiscard
+function Touches.released() end
+
+---How much the view was pinched this frame. Positive values zoom in.
+---@return number
+---@nodiscard
+function Touches.pinch() end
+
+---How far two fingers panned this frame, in window coordinates.
+---@return Vec2
+---@nodiscard
+function Touches.pan() end
+
+---How far two fingers rotated this frame in radians. Positive values are counter-clockwise.
+---@return number
+---@nodiscard
+function Touches.rotation() end
+
+---If a double-tap gesture happened this frame.
+---@return boolean
+---@nodiscard
+function Touches.double_tapped() end
+
+---If touches drive the mouse.
+---@return boolean
+---@nodiscard
+function Touches.mouse_emulation() end
+
+---Set whether the first finger to touch the screen moves the mouse and holds its left button.
+---@param enabled boolean
+function Touches.set_mouse_emulation(enabled) end
+
+return Touches
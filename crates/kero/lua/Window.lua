@@ -184,4 +184,33 @@ function Window.set_minimized(minimized) end
 ---@param cursor CursorIcon
 function Window.set_cursor(cursor) end
 
+---Set a custom cursor image to display when the mouse is over the window.
+---@param cursor CustomCursor
+function Window.set_custom_cursor(cursor) end
+
+---Set whether the cursor is visible while it's over the window.
+---@param visible boolean
+function Window.set_cursor_visible(visible) end
+
+---Set how the cursor is held by the window, returning false if the platform doesn't support it.
+---`"confined"` keeps it inside the window, and `"locked"` holds it in place.
+---@param grab "none"|"confined"|"locked"
+---@return boolean
+function Window.set_cursor_grab(grab) end
+
+---The system's color theme, if it's known.
+---@return "light"|"dark"|nil
+---@nodiscard
+function Window.theme() end
+
+---Set whether the input method editor is allowed, letting the player compose text in languages
+---such as Japanese. Only allow it while a text field is focused.
+---@param allowed boolean
+function Window.set_ime_allowed(allowed) end
+
+---Set the area of the window the text being composed is in, so the input method editor can avoid
+---covering it.
+---@param area Rect
+function Window.set_ime_cursor_area(area) end
+
 return Window
\ No newline at end of file
@@ -0,0 +1,102 @@
+use crate::audio::mixer::{BusState, MASTER_BUS, Mixer};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "lua")]
+pub type BusObj = fey_lua::UserDataOf<Bus>;
+#[cfg(feature = "lua")]
+pub type BusRef = mlua::UserDataRef<Bus>;
+
+/// Handle to a named mixer bus.
+///
+/// Every voice plays through a bus, and every bus feeds into the master bus. Changing the volume
+/// of a bus, or muting and pausing it, affects all voices playing through it.
+#[derive(Clone)]
+pub struct Bus {
+    pub(crate) mixer: Arc<Mutex<Mixer>>,
+    pub(crate) index: usize,
+}
+
+impl Debug for Bus {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Bus").field(&self.name()).finish()
+    }
+}
+
+impl PartialEq for Bus {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && Arc::ptr_eq(&self.mixer, &other.mixer)
+    }
+}
+
+impl Bus {
+    #[inline]
+    fn get<T>(&self, f: impl FnOnce(&BusState) -> T) -> T {
+        f(&self.mixer.lock().unwrap().buses[self.index])
+    }
+
+    #[inline]
+    fn set(&self, f: impl FnOnce(&mut BusState)) {
+        f(&mut self.mixer.lock().unwrap().buses[self.index]);
+    }
+
+    /// Name of the bus.
+    #[inline]
+    pub fn name(&self) -> String {
+        self.get(|b| b.name.clone())
+    }
+
+    /// If this is the master bus.
+    #[inline]
+    pub fn is_master(&self) -> bool {
+        self.index == MASTER_BUS
+    }
+
+    /// Volume multiplier of the bus.
+    #[inline]
+    pub fn volume(&self) -> f32 {
+        self.get(|b| b.volume)
+    }
+
+    /// Set the bus' volume multiplier.
+    #[inline]
+    pub fn set_volume(&self, volume: f32) {
+        self.set(|b| b.volume = volume.max(0.0));
+    }
+
+    /// If the bus is muted. Voices on muted buses continue to play silently.
+    #[inline]
+    pub fn muted(&self) -> bool {
+        self.get(|b| b.muted)
+    }
+
+    /// Set whether the bus is muted.
+    #[inline]
+    pub fn set_muted(&self, muted: bool) {
+        self.set(|b| b.muted = muted);
+    }
+
+    /// If the bus is paused. Voices on paused buses do not advance.
+    #[inline]
+    pub fn paused(&self) -> bool {
+        self.get(|b| b.paused)
+    }
+
+    /// Set whether the bus is paused.
+    #[inline]
+    pub fn set_paused(&self, paused: bool) {
+        self.set(|b| b.paused = paused);
+    }
+
+    /// Stop all voices playing through this bus (or all voices, if this is the master bus).
+    pub fn stop_all(&self) {
+        let mut mixer = self.mixer.lock().unwrap();
+        for voice in &mut mixer.voices {
+            if self.is_master() || voice.bus == self.index {
+                voice.finished = true;
+            }
+        }
+    }
+}
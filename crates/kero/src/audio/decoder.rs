@@ -0,0 +1,108 @@
+use crate::audio::AudioError;
+use hound::{SampleFormat, WavReader};
+use lewton::inside_ogg::OggStreamReader;
+use std::io::Cursor;
+use std::sync::Arc;
+
+/// How many frames a WAV decoder will produce per chunk.
+const WAV_CHUNK_FRAMES: usize = 4096;
+
+type Reader = Cursor<Arc<[u8]>>;
+
+/// Decodes encoded audio bytes into stereo frames, one chunk at a time.
+pub(crate) enum Decoder {
+    Wav(WavReader<Reader>),
+    Ogg(Box<OggStreamReader<Reader>>),
+}
+
+impl Decoder {
+    /// Create a decoder for the bytes, detecting the format from its header.
+    pub fn new(bytes: Arc<[u8]>) -> Result<Self, AudioError> {
+        if bytes.starts_with(b"RIFF") {
+            Ok(Self::Wav(WavReader::new(Cursor::new(bytes))?))
+        } else if bytes.starts_with(b"OggS") {
+            Ok(Self::Ogg(Box::new(OggStreamReader::new(Cursor::new(
+                bytes,
+            ))?)))
+        } else {
+            Err(AudioError::UnsupportedFormat)
+        }
+    }
+
+    /// Sample rate of the decoded audio.
+    pub fn sample_rate(&self) -> u32 {
+        match self {
+            Self::Wav(reader) => reader.spec().sample_rate,
+            Self::Ogg(reader) => reader.ident_hdr.audio_sample_rate,
+        }
+    }
+
+    /// Decode the next chunk of frames into `out`, returning `false` once the stream is exhausted.
+    pub fn decode(&mut self, out: &mut impl Extend<[f32; 2]>) -> Result<bool, AudioError> {
+        match self {
+            Self::Wav(reader) => {
+                let spec = reader.spec();
+                let channels = spec.channels as usize;
+                let count = match spec.sample_format {
+                    SampleFormat::Float => decode_wav::<f32>(reader, channels, out, |s| s)?,
+                    SampleFormat::Int => {
+                        let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+                        decode_wav::<i32>(reader, channels, out, |s| s as f32 * scale)?
+                    }
+                };
+                Ok(count == WAV_CHUNK_FRAMES)
+            }
+            Self::Ogg(reader) => {
+                let channels = reader.ident_hdr.audio_channels as usize;
+                let Some(packet) = reader.read_dec_packet_itl()? else {
+                    return Ok(false);
+                };
+                out.extend(
+                    packet
+                        .chunks_exact(channels)
+                        .map(|frame| to_stereo(frame, |s| s as f32 / 32768.0)),
+                );
+                Ok(true)
+            }
+        }
+    }
+
+    /// Decode the rest of the stream.
+    pub fn decode_all(&mut self) -> Result<Vec<[f32; 2]>, AudioError> {
+        let mut frames = Vec::new();
+        while self.decode(&mut frames)? {}
+        Ok(frames)
+    }
+}
+
+fn decode_wav<S: hound::Sample + Copy>(
+    reader: &mut WavReader<Reader>,
+    channels: usize,
+    out: &mut impl Extend<[f32; 2]>,
+    convert: impl Fn(S) -> f32,
+) -> Result<usize, AudioError> {
+    let mut samples = reader.samples::<S>();
+    let mut frame: Vec<S> = Vec::with_capacity(channels);
+    let mut count = 0;
+    while count < WAV_CHUNK_FRAMES {
+        frame.clear();
+        for sample in samples.by_ref().take(channels) {
+            frame.push(sample?);
+        }
+        if frame.len() < channels {
+            break;
+        }
+        out.extend(Some(to_stereo(&frame, &convert)));
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Convert a frame with any number of channels into a stereo frame.
+fn to_stereo<S: Copy>(frame: &[S], convert: impl Fn(S) -> f32) -> [f32; 2] {
+    match frame {
+        [] => [0.0; 2],
+        [mono] => [convert(*mono); 2],
+        [l, r, ..] => [convert(*l), convert(*r)],
+    }
+}
@@ -0,0 +1,265 @@
+use crate::audio::decoder::Decoder;
+use crate::audio::{Music, Sound};
+use std::collections::VecDeque;
+
+/// Index of the master bus, which every other bus feeds into.
+pub(crate) const MASTER_BUS: usize = 0;
+
+/// Mixes all playing voices into a stereo output stream.
+///
+/// The mixer is shared between the game thread and the output device's audio thread.
+pub(crate) struct Mixer {
+    pub sample_rate: u32,
+    pub buses: Vec<BusState>,
+    pub voices: Vec<VoiceState>,
+    pub next_id: u64,
+}
+
+pub(crate) struct BusState {
+    pub name: String,
+    pub volume: f32,
+    pub muted: bool,
+    pub paused: bool,
+}
+
+pub(crate) struct VoiceState {
+    pub id: u64,
+    pub source: Source,
+    pub bus: usize,
+    pub volume: f32,
+    pub pan: f32,
+    pub pitch: f32,
+    pub looping: bool,
+    pub paused: bool,
+    pub finished: bool,
+
+    /// Playback position in source frames, relative to the start of the source.
+    pub cursor: f64,
+}
+
+pub(crate) enum Source {
+    Sound(Sound),
+    Music(Box<MusicStream>),
+}
+
+pub(crate) struct MusicStream {
+    pub music: Music,
+    pub decoder: Decoder,
+    pub sample_rate: u32,
+
+    /// Decoded frames that haven't been played yet. The front frame is at `offset`.
+    pub buffer: VecDeque<[f32; 2]>,
+    pub offset: f64,
+
+    /// Length of the track in frames, known once it has been decoded to the end.
+    pub length: Option<u64>,
+    pub decoded: u64,
+    pub exhausted: bool,
+}
+
+impl Mixer {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            buses: vec![BusState::new("master")],
+            voices: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Find a bus by name, creating it if it doesn't exist.
+    pub fn bus_index(&mut self, name: &str) -> usize {
+        match self.buses.iter().position(|bus| bus.name == name) {
+            Some(i) => i,
+            None => {
+                self.buses.push(BusState::new(name));
+                self.buses.len() - 1
+            }
+        }
+    }
+
+    pub fn add_voice(&mut self, source: Source, bus: usize) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.voices.push(VoiceState {
+            id,
+            source,
+            bus,
+            volume: 1.0,
+            pan: 0.0,
+            pitch: 1.0,
+            looping: false,
+            paused: false,
+            finished: false,
+            cursor: 0.0,
+        });
+        id
+    }
+
+    #[inline]
+    pub fn voice(&self, id: u64) -> Option<&VoiceState> {
+        self.voices.iter().find(|v| v.id == id)
+    }
+
+    #[inline]
+    pub fn voice_mut(&mut self, id: u64) -> Option<&mut VoiceState> {
+        self.voices.iter_mut().find(|v| v.id == id)
+    }
+
+    /// Render interleaved stereo samples into `out`, replacing its contents.
+    pub fn render(&mut self, out: &mut [f32]) {
+        out.fill(0.0);
+
+        let master = &self.buses[MASTER_BUS];
+        if master.paused {
+            return;
+        }
+        let master_gain = if master.muted { 0.0 } else { master.volume };
+
+        let out_rate = self.sample_rate as f64;
+        for voice in &mut self.voices {
+            let bus = &self.buses[voice.bus];
+            if voice.paused || voice.finished || bus.paused {
+                continue;
+            }
+            let gain = if bus.muted {
+                0.0
+            } else {
+                master_gain * bus.volume * voice.volume
+            };
+            let pan = voice.pan.clamp(-1.0, 1.0);
+            let gains = [gain * (1.0 - pan).min(1.0), gain * (1.0 + pan).min(1.0)];
+            voice.render(out, out_rate, gains);
+        }
+
+        self.voices.retain(|v| !v.finished);
+    }
+}
+
+impl BusState {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            volume: 1.0,
+            muted: false,
+            paused: false,
+        }
+    }
+}
+
+impl VoiceState {
+    /// Position of the voice in seconds.
+    pub fn position(&self) -> f32 {
+        let rate = match &self.source {
+            Source::Sound(sound) => sound.sample_rate(),
+            Source::Music(stream) => stream.sample_rate,
+        };
+        (self.cursor / rate as f64) as f32
+    }
+
+    fn render(&mut self, out: &mut [f32], out_rate: f64, gains: [f32; 2]) {
+        let pitch = self.pitch.max(0.0) as f64;
+        match &mut self.source {
+            Source::Sound(sound) => {
+                let frames = sound.frames();
+                let len = frames.len() as f64;
+                let step = pitch * sound.sample_rate() as f64 / out_rate;
+                for out in out.chunks_exact_mut(2) {
+                    if self.cursor >= len {
+                        if self.looping && len > 0.0 {
+                            self.cursor %= len;
+                        } else {
+                            self.finished = true;
+                            return;
+                        }
+                    }
+                    let i = self.cursor as usize;
+                    let t = self.cursor.fract() as f32;
+                    let a = frames[i];
+                    let b = match frames.get(i + 1) {
+                        Some(b) => *b,
+                        None if self.looping => frames[0],
+                        None => a,
+                    };
+                    mix_frame(out, a, b, t, gains);
+                    self.cursor += step;
+                }
+            }
+            Source::Music(stream) => {
+                let step = pitch * stream.sample_rate as f64 / out_rate;
+                for out in out.chunks_exact_mut(2) {
+                    if !stream.fill(2 + stream.offset as usize, self.looping) {
+                        self.finished = true;
+                        return;
+                    }
+                    let i = stream.offset as usize;
+                    let t = stream.offset.fract() as f32;
+                    let a = stream.buffer[i];
+                    let b = stream.buffer.get(i + 1).copied().unwrap_or(a);
+                    mix_frame(out, a, b, t, gains);
+
+                    stream.offset += step;
+                    self.cursor += step;
+                    let consumed = (stream.offset as usize).min(stream.buffer.len());
+                    stream.buffer.drain(..consumed);
+                    stream.offset -= consumed as f64;
+
+                    if let Some(length) = stream.length
+                        && length > 0
+                        && self.cursor >= length as f64
+                    {
+                        self.cursor %= length as f64;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl MusicStream {
+    pub fn new(music: Music) -> Result<Self, crate::audio::AudioError> {
+        let decoder = music.decoder()?;
+        Ok(Self {
+            sample_rate: music.sample_rate(),
+            music,
+            decoder,
+            buffer: VecDeque::new(),
+            offset: 0.0,
+            length: None,
+            decoded: 0,
+            exhausted: false,
+        })
+    }
+
+    /// Decode until at least `count` frames are buffered. Returns `false` if the stream ended
+    /// and there is nothing left to play.
+    fn fill(&mut self, count: usize, looping: bool) -> bool {
+        while self.buffer.len() < count && !self.exhausted {
+            let before = self.buffer.len();
+            let more = match self.decoder.decode(&mut self.buffer) {
+                Ok(more) => more,
+                Err(err) => {
+                    log::warn!("music stream failed to decode: {err}");
+                    false
+                }
+            };
+            if self.length.is_none() {
+                self.decoded += (self.buffer.len() - before) as u64;
+            }
+            if !more {
+                let length = *self.length.get_or_insert(self.decoded);
+                match looping.then(|| self.music.decoder()) {
+                    Some(Ok(decoder)) if length > 0 => self.decoder = decoder,
+                    _ => self.exhausted = true,
+                }
+            }
+        }
+        (self.offset as usize) < self.buffer.len()
+    }
+}
+
+#[inline]
+fn mix_frame(out: &mut [f32], a: [f32; 2], b: [f32; 2], t: f32, gains: [f32; 2]) {
+    out[0] += (a[0] + (b[0] - a[0]) * t) * gains[0];
+    out[1] += (a[1] + (b[1] - a[1]) * t) * gains[1];
+}
@@ -0,0 +1,15 @@
+//! Sound effects, streamed music, and audio mixing.
+
+mod bus;
+mod decoder;
+mod mixer;
+mod music;
+mod sound;
+mod system;
+mod voice;
+
+pub use bus::*;
+pub use music::*;
+pub use sound::*;
+pub use system::*;
+pub use voice::*;
@@ -0,0 +1,63 @@
+use crate::audio::AudioError;
+use crate::audio::decoder::Decoder;
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+#[cfg(feature = "lua")]
+pub type MusicObj = fey_lua::UserDataOf<Music>;
+#[cfg(feature = "lua")]
+pub type MusicRef = mlua::UserDataRef<Music>;
+
+/// A music track, which is kept encoded in memory and decoded while it plays.
+///
+/// This handle can be cloned and passed around freely to give objects access to the music.
+#[derive(Clone)]
+pub struct Music(pub(crate) Arc<MusicInner>);
+
+pub(crate) struct MusicInner {
+    pub bytes: Arc<[u8]>,
+    pub sample_rate: u32,
+}
+
+impl Debug for Music {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Music").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Music {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Music {
+    /// Create a music track from WAV or OGG Vorbis bytes.
+    ///
+    /// The header is validated immediately, but the audio is only decoded during playback.
+    pub fn from_memory(bytes: impl Into<Arc<[u8]>>) -> Result<Self, AudioError> {
+        let bytes = bytes.into();
+        let sample_rate = Decoder::new(bytes.clone())?.sample_rate();
+        Ok(Self(Arc::new(MusicInner { bytes, sample_rate })))
+    }
+
+    /// Load a WAV or OGG Vorbis music file.
+    #[inline]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AudioError> {
+        Self::from_memory(std::fs::read(path)?)
+    }
+
+    /// Sample rate of the music.
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.0.sample_rate
+    }
+
+    #[inline]
+    pub(crate) fn decoder(&self) -> Result<Decoder, AudioError> {
+        Decoder::new(self.0.bytes.clone())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::audio::AudioError;
+use crate::audio::decoder::Decoder;
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+#[cfg(feature = "lua")]
+pub type SoundObj = fey_lua::UserDataOf<Sound>;
+#[cfg(feature = "lua")]
+pub type SoundRef = mlua::UserDataRef<Sound>;
+
+/// A fully decoded sound, kept in memory for fast and repeated playback.
+///
+/// Sounds are best suited for short effects. For long tracks, use [`Music`](crate::audio::Music),
+/// which is streamed and decoded during playback instead.
+///
+/// This handle can be cloned and passed around freely to give objects access to the sound.
+#[derive(Clone)]
+pub struct Sound(pub(crate) Arc<SoundInner>);
+
+pub(crate) struct SoundInner {
+    pub sample_rate: u32,
+    pub frames: Vec<[f32; 2]>,
+}
+
+impl Debug for Sound {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Sound").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Sound {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Sound {
+    /// Create a sound from stereo frames played at the provided sample rate.
+    #[inline]
+    pub fn from_frames(sample_rate: u32, frames: Vec<[f32; 2]>) -> Self {
+        assert!(sample_rate > 0, "sample rate must be greater than zero");
+        Self(Arc::new(SoundInner {
+            sample_rate,
+            frames,
+        }))
+    }
+
+    /// Decode a sound from WAV or OGG Vorbis bytes.
+    pub fn from_memory(bytes: impl Into<Arc<[u8]>>) -> Result<Self, AudioError> {
+        let mut decoder = Decoder::new(bytes.into())?;
+        let sample_rate = decoder.sample_rate();
+        let frames = decoder.decode_all()?;
+        Ok(Self::from_frames(sample_rate, frames))
+    }
+
+    /// Load and decode a WAV or OGG Vorbis file.
+    #[inline]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AudioError> {
+        Self::from_memory(std::fs::read(path)?)
+    }
+
+    /// Sample rate of the sound.
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.0.sample_rate
+    }
+
+    /// How many stereo frames the sound contains.
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.0.frames.len()
+    }
+
+    /// Duration of the sound in seconds.
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.0.frames.len() as f32 / self.0.sample_rate as f32
+    }
+
+    /// The sound's stereo frames.
+    #[inline]
+    pub fn frames(&self) -> &[[f32; 2]] {
+        &self.0.frames
+    }
+}
@@ -0,0 +1,352 @@
+use crate::audio::mixer::{MASTER_BUS, Mixer, MusicStream, Source};
+use crate::audio::{Bus, Music, Sound, Voice, VoiceParams};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample, Stream, StreamConfig};
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// Sample rate used by the null output when none is specified.
+pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+/// Where the audio mixer sends its output.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum AudioOutput {
+    /// Play through the system's default output device. If no device is available, audio will
+    /// fall back to [`AudioOutput::Null`].
+    #[default]
+    Device,
+
+    /// Don't play audio on any device. The mixer is advanced by the game loop instead, and its
+    /// output can be captured with [`Audio::set_capture`] or rendered with [`Audio::render`].
+    /// This is useful for running tests without a sound device.
+    Null { sample_rate: u32 },
+}
+
+/// Handle to the audio system.
+///
+/// This handle can be cloned and passed around freely to give objects access to the audio system.
+#[derive(Clone)]
+pub struct Audio(Rc<AudioInner>);
+
+struct AudioInner {
+    mixer: Arc<Mutex<Mixer>>,
+    output: AudioOutput,
+    _stream: Option<Stream>,
+    pending_frames: Cell<f64>,
+    scratch: RefCell<Vec<f32>>,
+    captured: RefCell<Option<Vec<f32>>>,
+}
+
+impl Debug for Audio {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Audio").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Audio {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Audio {
+    /// Create a new audio system with the provided output.
+    ///
+    /// If the output is [`AudioOutput::Device`] but the device could not be opened, a warning is
+    /// logged and the null output is used instead.
+    pub fn new(output: AudioOutput) -> Self {
+        let (mixer, output, stream) = match output {
+            AudioOutput::Device => match open_device() {
+                Ok((mixer, stream)) => (mixer, output, Some(stream)),
+                Err(err) => {
+                    log::warn!("failed to open audio device, falling back to null output: {err}");
+                    let sample_rate = DEFAULT_SAMPLE_RATE;
+                    let mixer = Arc::new(Mutex::new(Mixer::new(sample_rate)));
+                    (mixer, AudioOutput::Null { sample_rate }, None)
+                }
+            },
+            AudioOutput::Null { sample_rate } => {
+                assert!(sample_rate > 0, "sample rate must be greater than zero");
+                let mixer = Arc::new(Mutex::new(Mixer::new(sample_rate)));
+                (mixer, output, None)
+            }
+        };
+        Self(Rc::new(AudioInner {
+            mixer,
+            output,
+            _stream: stream,
+            pending_frames: Cell::new(0.0),
+            scratch: RefCell::new(Vec::new()),
+            captured: RefCell::new(None),
+        }))
+    }
+
+    /// The output the audio is being sent to.
+    #[inline]
+    pub fn output(&self) -> AudioOutput {
+        self.0.output
+    }
+
+    /// If the audio is using the null output.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self.0.output, AudioOutput::Null { .. })
+    }
+
+    /// Output sample rate of the mixer.
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.0.mixer.lock().unwrap().sample_rate
+    }
+
+    /// Load a sound from a WAV or OGG Vorbis file.
+    #[inline]
+    pub fn load_sound(&self, path: impl AsRef<Path>) -> Result<Sound, AudioError> {
+        Sound::from_file(path)
+    }
+
+    /// Load a music track from a WAV or OGG Vorbis file.
+    #[inline]
+    pub fn load_music(&self, path: impl AsRef<Path>) -> Result<Music, AudioError> {
+        Music::from_file(path)
+    }
+
+    /// The master bus, which all other buses feed into.
+    #[inline]
+    pub fn master(&self) -> Bus {
+        Bus {
+            mixer: self.0.mixer.clone(),
+            index: MASTER_BUS,
+        }
+    }
+
+    /// Get the bus with the provided name, creating it if it doesn't exist.
+    ///
+    /// The bus named `"master"` is the master bus.
+    #[inline]
+    pub fn bus(&self, name: &str) -> Bus {
+        let index = self.0.mixer.lock().unwrap().bus_index(name);
+        Bus {
+            mixer: self.0.mixer.clone(),
+            index,
+        }
+    }
+
+    /// Volume multiplier of the master bus.
+    #[inline]
+    pub fn master_volume(&self) -> f32 {
+        self.master().volume()
+    }
+
+    /// Set the volume multiplier of the master bus.
+    #[inline]
+    pub fn set_master_volume(&self, volume: f32) {
+        self.master().set_volume(volume);
+    }
+
+    /// Play a sound with default parameters.
+    #[inline]
+    pub fn play_sound(&self, sound: &Sound) -> Voice {
+        self.play_sound_ext(sound, VoiceParams::default())
+    }
+
+    /// Play a sound.
+    #[inline]
+    pub fn play_sound_ext(&self, sound: &Sound, params: VoiceParams) -> Voice {
+        self.play(Source::Sound(sound.clone()), params)
+    }
+
+    /// Play a music track with default parameters.
+    #[inline]
+    pub fn play_music(&self, music: &Music) -> Result<Voice, AudioError> {
+        self.play_music_ext(music, VoiceParams::default())
+    }
+
+    /// Play a music track.
+    #[inline]
+    pub fn play_music_ext(&self, music: &Music, params: VoiceParams) -> Result<Voice, AudioError> {
+        let stream = MusicStream::new(music.clone())?;
+        Ok(self.play(Source::Music(Box::new(stream)), params))
+    }
+
+    fn play(&self, source: Source, params: VoiceParams) -> Voice {
+        let bus = match params.bus {
+            Some(bus) => {
+                assert!(
+                    Arc::ptr_eq(&bus.mixer, &self.0.mixer),
+                    "bus belongs to a different audio system"
+                );
+                bus.index
+            }
+            None => MASTER_BUS,
+        };
+        let mut mixer = self.0.mixer.lock().unwrap();
+        let id = mixer.add_voice(source, bus);
+        let voice = mixer.voice_mut(id).unwrap();
+        voice.volume = params.volume.max(0.0);
+        voice.pan = params.pan.clamp(-1.0, 1.0);
+        voice.pitch = params.pitch.max(0.0);
+        voice.looping = params.looping;
+        voice.paused = params.paused;
+        Voice {
+            mixer: self.0.mixer.clone(),
+            id,
+        }
+    }
+
+    /// Stop all playing voices.
+    #[inline]
+    pub fn stop_all(&self) {
+        self.master().stop_all();
+    }
+
+    /// How many voices are currently playing (or paused).
+    #[inline]
+    pub fn voice_count(&self) -> usize {
+        self.0.mixer.lock().unwrap().voices.len()
+    }
+
+    /// Render interleaved stereo samples from the mixer into `out`, advancing all voices.
+    ///
+    /// Panics if the audio is not using the null output, since the device renders on its own.
+    pub fn render(&self, out: &mut [f32]) {
+        assert!(
+            self.is_null(),
+            "can only render audio manually with the null output"
+        );
+        self.0.mixer.lock().unwrap().render(out);
+    }
+
+    /// If enabled, all samples rendered by the null output as the game runs will be captured,
+    /// and can be retrieved with [`Audio::take_captured`]. Disabling discards captured samples.
+    pub fn set_capture(&self, capture: bool) {
+        let mut captured = self.0.captured.borrow_mut();
+        match (capture, captured.is_some()) {
+            (true, false) => *captured = Some(Vec::new()),
+            (false, true) => *captured = None,
+            _ => {}
+        }
+    }
+
+    /// If the null output is capturing samples.
+    #[inline]
+    pub fn capturing(&self) -> bool {
+        self.0.captured.borrow().is_some()
+    }
+
+    /// Take all interleaved stereo samples captured so far, leaving capture enabled.
+    #[inline]
+    pub fn take_captured(&self) -> Vec<f32> {
+        self.0
+            .captured
+            .borrow_mut()
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Advance the null output by `dt` seconds.
+    pub(crate) fn update(&self, dt: f32) {
+        let AudioOutput::Null { sample_rate } = self.0.output else {
+            return;
+        };
+
+        let frames = self.0.pending_frames.get() + dt.max(0.0) as f64 * sample_rate as f64;
+        let count = frames as usize;
+        self.0.pending_frames.set(frames - count as f64);
+        if count == 0 {
+            return;
+        }
+
+        let mut scratch = self.0.scratch.borrow_mut();
+        scratch.resize(count * 2, 0.0);
+        self.0.mixer.lock().unwrap().render(&mut scratch);
+        if let Some(captured) = self.0.captured.borrow_mut().as_mut() {
+            captured.extend_from_slice(&scratch);
+        }
+    }
+}
+
+fn open_device() -> Result<(Arc<Mutex<Mixer>>, Stream), AudioError> {
+    let host = cpal::default_host();
+    let device = host.default_output_device().ok_or(AudioError::NoDevice)?;
+    let supported = device.default_output_config()?;
+    let config = supported.config();
+    let mixer = Arc::new(Mutex::new(Mixer::new(config.sample_rate.0)));
+    let stream = match supported.sample_format() {
+        SampleFormat::F32 => build_stream::<f32>(&device, &config, &mixer),
+        SampleFormat::I16 => build_stream::<i16>(&device, &config, &mixer),
+        SampleFormat::U16 => build_stream::<u16>(&device, &config, &mixer),
+        SampleFormat::I32 => build_stream::<i32>(&device, &config, &mixer),
+        format => return Err(AudioError::UnsupportedSampleFormat(format)),
+    }?;
+    stream.play()?;
+    Ok((mixer, stream))
+}
+
+fn build_stream<T: SizedSample + FromSample<f32>>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    mixer: &Arc<Mutex<Mixer>>,
+) -> Result<Stream, AudioError> {
+    let mixer = mixer.clone();
+    let channels = config.channels as usize;
+    let mut scratch = Vec::new();
+    let stream = device.build_output_stream(
+        config,
+        move |data: &mut [T], _| {
+            scratch.resize(data.len() / channels * 2, 0.0);
+            mixer.lock().unwrap().render(&mut scratch);
+            for (out, &[l, r]) in data.chunks_exact_mut(channels).zip(scratch.as_chunks().0) {
+                match out {
+                    [mono] => *mono = T::from_sample((l + r) * 0.5),
+                    [out_l, out_r, rest @ ..] => {
+                        *out_l = T::from_sample(l);
+                        *out_r = T::from_sample(r);
+                        rest.fill(T::EQUILIBRIUM);
+                    }
+                    [] => {}
+                }
+            }
+        },
+        |err| log::error!("audio stream error: {err}"),
+        None,
+    )?;
+    Ok(stream)
+}
+
+/// An audio error.
+#[derive(Debug, thiserror::Error)]
+pub enum AudioError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Wav(#[from] hound::Error),
+
+    #[error("{0}")]
+    Ogg(#[from] lewton::VorbisError),
+
+    #[error("unsupported audio format, expected WAV or OGG Vorbis")]
+    UnsupportedFormat,
+
+    #[error("no audio output device available")]
+    NoDevice,
+
+    #[error("unsupported output sample format: {0}")]
+    UnsupportedSampleFormat(SampleFormat),
+
+    #[error("{0}")]
+    DefaultStreamConfig(#[from] cpal::DefaultStreamConfigError),
+
+    #[error("{0}")]
+    BuildStream(#[from] cpal::BuildStreamError),
+
+    #[error("{0}")]
+    PlayStream(#[from] cpal::PlayStreamError),
+}
@@ -0,0 +1,178 @@
+use crate::audio::Bus;
+use crate::audio::mixer::{Mixer, VoiceState};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "lua")]
+pub type VoiceObj = fey_lua::UserDataOf<Voice>;
+#[cfg(feature = "lua")]
+pub type VoiceRef = mlua::UserDataRef<Voice>;
+
+/// Handle to a playing sound or music track.
+///
+/// Once a voice finishes playing or is stopped, the handle becomes inert: setters do nothing
+/// and getters return default values.
+#[derive(Clone)]
+pub struct Voice {
+    pub(crate) mixer: Arc<Mutex<Mixer>>,
+    pub(crate) id: u64,
+}
+
+/// Parameters for playing a voice.
+#[derive(Debug, Clone)]
+pub struct VoiceParams {
+    /// Volume multiplier, where `1.0` is full volume.
+    pub volume: f32,
+
+    /// Stereo pan, from `-1.0` (left) to `1.0` (right).
+    pub pan: f32,
+
+    /// Playback speed multiplier, which also shifts the pitch.
+    pub pitch: f32,
+
+    /// If the voice should start over when it reaches the end.
+    pub looping: bool,
+
+    /// If the voice should start paused.
+    pub paused: bool,
+
+    /// The bus to play through, or the master bus if not provided.
+    pub bus: Option<Bus>,
+}
+
+impl Default for VoiceParams {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            pan: 0.0,
+            pitch: 1.0,
+            looping: false,
+            paused: false,
+            bus: None,
+        }
+    }
+}
+
+impl Debug for Voice {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Voice").field(&self.id).finish()
+    }
+}
+
+impl PartialEq for Voice {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && Arc::ptr_eq(&self.mixer, &other.mixer)
+    }
+}
+
+impl Voice {
+    #[inline]
+    fn get<T: Default>(&self, f: impl FnOnce(&VoiceState) -> T) -> T {
+        self.mixer
+            .lock()
+            .unwrap()
+            .voice(self.id)
+            .map(f)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn set(&self, f: impl FnOnce(&mut VoiceState)) {
+        if let Some(voice) = self.mixer.lock().unwrap().voice_mut(self.id) {
+            f(voice);
+        }
+    }
+
+    /// If the voice is still playing (or paused) and hasn't finished or been stopped.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.get(|v| !v.finished)
+    }
+
+    /// Stop the voice. It cannot be resumed afterwards.
+    #[inline]
+    pub fn stop(&self) {
+        self.set(|v| v.finished = true);
+    }
+
+    /// If the voice is paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.get(|v| v.paused)
+    }
+
+    /// Pause the voice.
+    #[inline]
+    pub fn pause(&self) {
+        self.set_paused(true);
+    }
+
+    /// Resume the voice if it was paused.
+    #[inline]
+    pub fn resume(&self) {
+        self.set_paused(false);
+    }
+
+    /// Set whether the voice is paused.
+    #[inline]
+    pub fn set_paused(&self, paused: bool) {
+        self.set(|v| v.paused = paused);
+    }
+
+    /// Volume multiplier of the voice.
+    #[inline]
+    pub fn volume(&self) -> f32 {
+        self.get(|v| v.volume)
+    }
+
+    /// Set the voice's volume multiplier.
+    #[inline]
+    pub fn set_volume(&self, volume: f32) {
+        self.set(|v| v.volume = volume.max(0.0));
+    }
+
+    /// Stereo pan of the voice, from `-1.0` (left) to `1.0` (right).
+    #[inline]
+    pub fn pan(&self) -> f32 {
+        self.get(|v| v.pan)
+    }
+
+    /// Set the voice's stereo pan, from `-1.0` (left) to `1.0` (right).
+    #[inline]
+    pub fn set_pan(&self, pan: f32) {
+        self.set(|v| v.pan = pan.clamp(-1.0, 1.0));
+    }
+
+    /// Playback speed multiplier of the voice.
+    #[inline]
+    pub fn pitch(&self) -> f32 {
+        self.get(|v| v.pitch)
+    }
+
+    /// Set the voice's playback speed multiplier.
+    #[inline]
+    pub fn set_pitch(&self, pitch: f32) {
+        self.set(|v| v.pitch = pitch.max(0.0));
+    }
+
+    /// If the voice loops when it reaches the end.
+    #[inline]
+    pub fn looping(&self) -> bool {
+        self.get(|v| v.looping)
+    }
+
+    /// Set whether the voice loops when it reaches the end.
+    #[inline]
+    pub fn set_looping(&self, looping: bool) {
+        self.set(|v| v.looping = looping);
+    }
+
+    /// Playback position of the voice in seconds.
+    #[inline]
+    pub fn position(&self) -> f32 {
+        self.get(|v| v.position())
+    }
+}
@@ -1,16 +1,11 @@
 use super::Game;
+use crate::audio::Audio;
 use crate::core::frame_timer::FrameTimer;
-use crate::core::{Context, GameBuilder, Time, Window};
+use crate::core::{AppEvent, Context, GameBuilder, GameError, Window};
 use crate::gfx::{Draw, Graphics};
-use crate::input::{Gamepads, Keyboard, Mouse};
-use crate::prelude::ContextData;
-use directories::ProjectDirs;
 use dpi::LogicalSize;
-use std::cell::Cell;
-use std::rc::Rc;
-use std::sync::Arc;
 use winit::application::ApplicationHandler;
-use winit::event::WindowEvent;
+use winit::event::{DeviceEvent, DeviceId, WindowEvent};
 use winit::event_loop::ActiveEventLoop;
 use winit::window::{WindowAttributes, WindowId};
 
@@ -25,7 +20,6 @@ enum AppState<G: Game> {
         timer: FrameTimer,
         size: LogicalSize<f64>,
         game: G,
-        has_updated: bool,
 
         #[cfg(feature = "lua")]
         lua_app: crate::core::LuaApp,
@@ -34,6 +28,7 @@ enum AppState<G: Game> {
 
 pub(crate) struct AppHandler<G: Game> {
     state: AppState<G>,
+    error: Option<GameError>,
 }
 
 impl<G: Game> AppHandler<G> {
@@ -43,12 +38,32 @@ impl<G: Game> AppHandler<G> {
                 opts,
                 cfg: Some(cfg),
             },
+            error: None,
         }
     }
+
+    /// Take the error that stopped the app, if there was one.
+    pub(crate) fn take_error(&mut self) -> Option<GameError> {
+        self.error.take()
+    }
+
+    /// Stop the app because of an error, letting the game know about it first.
+    fn fail(&mut self, event_loop: &ActiveEventLoop, err: GameError) {
+        if let AppState::Running { ctx, game, .. } = &mut self.state {
+            game.on_error(ctx, &err);
+        }
+        self.error = Some(err);
+        event_loop.exit();
+    }
 }
 
 impl<G: Game> ApplicationHandler for AppHandler<G> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        // don't try to start again if starting up failed
+        if self.error.is_some() {
+            return;
+        }
+
         let AppState::Startup { opts, cfg } = &mut self.state else {
             return;
         };
@@ -58,11 +73,11 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
         let attrs = WindowAttributes::default()
             .with_title(&opts.title)
             .with_inner_size(size);
-        let window = Window(Arc::new(
+        let window = Window::new(
             event_loop
                 .create_window(attrs)
                 .expect("failed to create window"),
-        ));
+        );
 
         // initialize the graphics
         let graphics = Graphics::new(window.clone(), opts);
@@ -72,47 +87,35 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             graphics.device().clone(),
             graphics.queue().clone(),
             graphics.default_shader().clone(),
+            graphics.mask_shader().clone(),
             graphics.default_texture().clone(),
         );
 
-        // load the project directories
-        let app_name = if opts.app_name.is_empty() {
-            opts.title.as_str()
-        } else {
-            opts.app_name.as_str()
-        };
-        let dirs = ProjectDirs::from("", &opts.app_organization, app_name)
-            .expect("failed to locate system directories");
-
         // create the game context
-        let ctx = Context(Rc::new(ContextData {
-            window,
-            time: Time::new(),
-            mouse: Mouse::new(),
-            keyboard: Keyboard::new(),
-            gamepads: Gamepads::new(),
-            graphics,
-
-            #[cfg(feature = "lua")]
-            lua: opts.lua.weak(),
-
-            #[cfg(feature = "lua")]
-            reload_lua: Cell::new(false),
-
-            quit_requested: Cell::new(false),
-
-            dirs,
-        }));
+        let ctx = Context::new(window, graphics, Audio::new(opts.audio_output), opts);
 
         // create the frame timer
         let timer = FrameTimer::new(ctx.time.0.clone());
 
         #[cfg(feature = "lua")]
-        let lua_app = crate::core::LuaApp::new(opts.lua.clone(), &ctx);
+        let lua_app = match crate::core::LuaApp::new(opts.lua.clone(), &ctx, opts.lua_hot_reload) {
+            Ok(lua_app) => lua_app,
+            Err(err) => {
+                self.error = Some(err);
+                event_loop.exit();
+                return;
+            }
+        };
 
         // create the game
-        // TODO: propagate error
-        let game = G::new(&ctx, cfg.take().unwrap()).unwrap();
+        let game = match G::new(&ctx, cfg.take().unwrap()) {
+            Ok(game) => game,
+            Err(err) => {
+                self.error = Some(err);
+                event_loop.exit();
+                return;
+            }
+        };
 
         // start running the app loop
         self.state = AppState::Running {
@@ -121,7 +124,6 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             timer,
             size,
             game,
-            has_updated: false,
 
             #[cfg(feature = "lua")]
             lua_app,
@@ -134,13 +136,17 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
         window_id: WindowId,
         event: WindowEvent,
     ) {
+        // ignore any events that arrive after an error stopped the app
+        if self.error.is_some() {
+            return;
+        }
+
         let AppState::Running {
             ctx,
             draw,
             timer,
             size,
             game,
-            has_updated,
 
             #[cfg(feature = "lua")]
             lua_app,
@@ -150,7 +156,10 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
         };
 
         // likely redundant, winit probably only calls this for windows created by this process
-        if ctx.window.0.id() != window_id {
+        let Some(window) = ctx.window.winit().cloned() else {
+            return;
+        };
+        if window.id() != window_id {
             return;
         };
 
@@ -158,41 +167,85 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             WindowEvent::ActivationTokenDone { .. } => {}
             WindowEvent::Resized(new_size) => {
                 ctx.graphics.resized(new_size);
-                *size = new_size.to_logical::<f64>(ctx.window.0.scale_factor());
+                *size = new_size.to_logical::<f64>(window.scale_factor());
             }
             WindowEvent::Moved(_) => {}
             WindowEvent::CloseRequested => {
                 event_loop.exit();
             }
             WindowEvent::Destroyed => {}
-            WindowEvent::DroppedFile(_) => {}
-            WindowEvent::HoveredFile(_) => {}
-            WindowEvent::HoveredFileCancelled => {}
-            WindowEvent::Focused(_) => {}
+            WindowEvent::DroppedFile(path) => {
+                ctx.events.push(AppEvent::FileDropped(path));
+            }
+            WindowEvent::HoveredFile(path) => {
+                ctx.events.push(AppEvent::FileHovered(path));
+            }
+            WindowEvent::HoveredFileCancelled => {
+                ctx.events.push(AppEvent::FileHoverCancelled);
+            }
+            WindowEvent::Focused(focused) => {
+                ctx.events.push(AppEvent::Focused(focused));
+            }
             WindowEvent::KeyboardInput { event, .. } => {
-                ctx.keyboard.handle_event(event);
+                if !ctx.input_recorder.is_replaying() {
+                    ctx.keyboard.handle_event(event);
+                }
             }
             WindowEvent::ModifiersChanged(_) => {}
-            WindowEvent::Ime(_) => {}
+            WindowEvent::Ime(ime) => {
+                if !ctx.input_recorder.is_replaying() {
+                    ctx.keyboard.handle_ime(ime);
+                }
+            }
             WindowEvent::CursorMoved { position, .. } => {
-                let position = position.to_logical::<f32>(ctx.window.0.scale_factor());
-                ctx.mouse.handle_move(position);
+                if !ctx.input_recorder.is_replaying() {
+                    let position = position.to_logical::<f32>(window.scale_factor());
+                    ctx.mouse.handle_move(position);
+                }
             }
             WindowEvent::CursorEntered { .. } => {}
             WindowEvent::CursorLeft { .. } => {}
             WindowEvent::MouseWheel { delta, .. } => {
-                ctx.mouse.handle_scroll(delta);
+                if !ctx.input_recorder.is_replaying() {
+                    ctx.mouse.handle_scroll(delta);
+                }
             }
             WindowEvent::MouseInput { state, button, .. } => {
-                ctx.mouse.handle_input(button, state);
+                if !ctx.input_recorder.is_replaying() {
+                    ctx.mouse.handle_input(button, state);
+                }
+            }
+            WindowEvent::PinchGesture { delta, .. } => {
+                if !ctx.input_recorder.is_replaying() {
+                    ctx.touches.handle_pinch(delta);
+                }
+            }
+            WindowEvent::PanGesture { delta, .. } => {
+                if !ctx.input_recorder.is_replaying() {
+                    let delta = delta.to_logical::<f32>(window.scale_factor());
+                    ctx.touches.handle_pan(delta);
+                }
+            }
+            WindowEvent::DoubleTapGesture { .. } => {
+                if !ctx.input_recorder.is_replaying() {
+                    ctx.touches.handle_double_tap();
+                }
+            }
+            WindowEvent::RotationGesture { delta, .. } => {
+                if !ctx.input_recorder.is_replaying() {
+                    ctx.touches.handle_rotation(delta);
+                }
             }
-            WindowEvent::PinchGesture { .. } => {}
-            WindowEvent::PanGesture { .. } => {}
-            WindowEvent::DoubleTapGesture { .. } => {}
-            WindowEvent::RotationGesture { .. } => {}
             WindowEvent::TouchpadPressure { .. } => {}
             WindowEvent::AxisMotion { .. } => {}
-            WindowEvent::Touch(_) => {}
+            WindowEvent::Touch(touch) => {
+                if !ctx.input_recorder.is_replaying() {
+                    let position = touch.location.to_logical::<f32>(window.scale_factor());
+                    let pressure = touch.force.map(|force| force.normalized() as f32);
+                    ctx.touches
+                        .handle_touch(touch.id, touch.phase, position, pressure);
+                }
+            }
             WindowEvent::ScaleFactorChanged {
                 scale_factor,
                 mut inner_size_writer,
@@ -203,68 +256,176 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
                     .request_inner_size(size.to_physical(scale_factor))
                     .expect("failed to update window size");
             }
-            WindowEvent::ThemeChanged(_) => {}
-            WindowEvent::Occluded(_) => {}
+            WindowEvent::ThemeChanged(theme) => {
+                ctx.events.push(AppEvent::ThemeChanged(theme.into()));
+            }
+            WindowEvent::Occluded(occluded) => {
+                ctx.events.push(AppEvent::Occluded(occluded));
+            }
             WindowEvent::RedrawRequested => {
-                let monitor = ctx.window.monitor();
+                // reload any shaders that changed on disk
+                ctx.graphics.update_shaders();
 
-                timer.tick(monitor, || {
-                    *has_updated = true;
+                let monitor = ctx.window.monitor();
 
-                    // update gamepad input
-                    ctx.gamepads.update(ctx);
+                // update gamepad input
+                ctx.gamepads.update(ctx);
 
-                    // update the lua app
+                let mut result = Ok(());
+                timer.tick(monitor, || {
+                    // stop updating once an update fails
+                    if result.is_err() {
+                        return;
+                    }
+
+                    // fixed updates see input that happened since the last fixed update
+                    ctx.mouse.set_fixed_update_phase();
+                    ctx.keyboard.set_fixed_update_phase();
+                    ctx.gamepads.set_fixed_update_phase();
+                    ctx.touches.set_fixed_update_phase();
+                    ctx.events.set_fixed_update_phase();
+
+                    // replay and record input
+                    ctx.input_recorder.before_fixed_update(ctx);
+
+                    // perform a fixed update of the lua app
                     #[cfg(feature = "lua")]
-                    lua_app.update(ctx);
+                    {
+                        result = lua_app.fixed_update(ctx);
+                    }
 
-                    // update the game
-                    // TODO: propagate this error somewhere
-                    game.update(ctx).unwrap();
+                    // perform a fixed update of the game
+                    if result.is_ok() {
+                        result = game.fixed_update(ctx);
+                    }
 
-                    // clear input on-frame events (eg. pressed, released)
+                    // this fixed update consumed the on-frame events (eg. pressed, released)
                     ctx.mouse.clear_phase();
                     ctx.keyboard.clear_phase();
                     ctx.gamepads.clear_phase();
+                    ctx.touches.clear_phase();
+                    ctx.events.clear_phase();
                 });
 
+                // back to the per-frame input for the update
+                ctx.mouse.set_update_phase();
+                ctx.keyboard.set_update_phase();
+                ctx.gamepads.set_update_phase();
+                ctx.touches.set_update_phase();
+                ctx.events.set_update_phase();
+
+                // update the lua app
+                #[cfg(feature = "lua")]
+                if result.is_ok() {
+                    result = lua_app.update(ctx);
+                }
+
+                // update the game
+                if result.is_ok() {
+                    result = game.update(ctx);
+                }
+
+                // advance the audio if it isn't being driven by a device
+                ctx.audio.update(ctx.time.delta());
+
+                // clear input on-frame events (eg. pressed, released)
+                ctx.mouse.clear_phase();
+                ctx.keyboard.clear_phase();
+                ctx.gamepads.clear_phase();
+                ctx.touches.clear_phase();
+                ctx.events.clear_phase();
+
                 // switch to the render phase for input
                 ctx.mouse.set_render_phase();
                 ctx.keyboard.set_render_phase();
                 ctx.gamepads.set_render_phase();
+                ctx.touches.set_render_phase();
+                ctx.events.set_render_phase();
 
                 // begin rendering a frame
                 draw.begin_frame(ctx.window.size());
 
-                // only do render callbacks after we've started updating
-                if *has_updated {
+                // only do render callbacks if updating succeeded
+                if result.is_ok() {
                     // render the lua app
                     #[cfg(feature = "lua")]
-                    lua_app.render(ctx, draw);
+                    {
+                        result = lua_app.render(ctx, draw);
+                    }
 
                     // render the game
-                    // TODO: propagate this error somewhere
-                    game.render(ctx, draw).unwrap();
+                    if result.is_ok() {
+                        result = game.render(ctx, draw);
+                    }
                 }
 
                 // finish rendering a frame
-                draw.end_frame(timer.time.frame.get(), ctx.graphics.surface(), &ctx.window);
+                draw.end_frame(timer.time.frame.get(), &ctx.graphics, &ctx.window);
 
                 // clear input on-frame events (eg. pressed, released)
                 ctx.mouse.clear_phase();
                 ctx.keyboard.clear_phase();
                 ctx.gamepads.clear_phase();
+                ctx.touches.clear_phase();
+                ctx.events.clear_phase();
 
                 // switch back to the update phase for input
                 ctx.mouse.set_update_phase();
                 ctx.keyboard.set_update_phase();
                 ctx.gamepads.set_update_phase();
+                ctx.touches.set_update_phase();
+                ctx.events.set_update_phase();
+
+                // create and show any custom cursor the game set
+                ctx.window.apply_custom_cursor(event_loop);
 
-                // quit if the user requested it
-                if ctx.quit_requested() {
+                // stop if the game failed, or quit if the user requested it
+                if let Err(err) = result {
+                    self.fail(event_loop, err);
+                } else if ctx.quit_requested() {
                     event_loop.exit();
                 }
             }
         }
     }
+
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        // ignore any events that arrive after an error stopped the app
+        if self.error.is_some() {
+            return;
+        }
+
+        let AppState::Running { ctx, .. } = &self.state else {
+            return;
+        };
+
+        // device events arrive even when the window isn't focused
+        if let DeviceEvent::MouseMotion { delta } = event
+            && ctx.window.has_focus()
+            && !ctx.input_recorder.is_replaying()
+        {
+            ctx.mouse.handle_motion(delta);
+        }
+    }
+
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        let AppState::Running { ctx, .. } = &self.state else {
+            return;
+        };
+
+        // save any recording that's still in progress
+        if let Err(err) = ctx.graphics.stop_recording() {
+            log::error!("failed to save recording: {err}");
+        }
+
+        // save the input recording
+        if let Err(err) = ctx.input_recorder.finish() {
+            log::error!("failed to save input recording: {err}");
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// Handle to the clipboard, for copying and pasting text.
+///
+/// This handle can be cloned and passed around freely to give objects access to the clipboard.
+///
+/// Text is shared with other applications through the system clipboard. If the system clipboard
+/// can't be opened (eg. when running headless), the game keeps its own clipboard instead, so
+/// text copied in the game can still be pasted in the game.
+#[derive(Clone)]
+pub struct Clipboard(Rc<RefCell<Backend>>);
+
+enum Backend {
+    System(arboard::Clipboard),
+    Memory(Option<String>),
+}
+
+impl Debug for Clipboard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Clipboard").finish_non_exhaustive()
+    }
+}
+
+impl Clipboard {
+    pub(crate) fn new() -> Self {
+        let backend = match arboard::Clipboard::new() {
+            Ok(clipboard) => Backend::System(clipboard),
+            Err(err) => {
+                log::warn!("failed to open system clipboard, falling back to in-game one: {err}");
+                Backend::Memory(None)
+            }
+        };
+        Self(Rc::new(RefCell::new(backend)))
+    }
+
+    /// If the clipboard is shared with other applications, or false if the game is keeping its
+    /// own because the system clipboard couldn't be opened.
+    #[inline]
+    pub fn is_system(&self) -> bool {
+        matches!(&*self.0.borrow(), Backend::System(_))
+    }
+
+    /// The text on the clipboard, or none if it is empty or holds something other than text.
+    pub fn text(&self) -> Result<Option<String>, ClipboardError> {
+        match &mut *self.0.borrow_mut() {
+            Backend::System(clipboard) => match clipboard.get_text() {
+                Ok(text) => Ok(Some(text)),
+                Err(arboard::Error::ContentNotAvailable) => Ok(None),
+                Err(err) => Err(err.into()),
+            },
+            Backend::Memory(text) => Ok(text.clone()),
+        }
+    }
+
+    /// Put the text on the clipboard.
+    pub fn set_text(&self, text: impl Into<String>) -> Result<(), ClipboardError> {
+        match &mut *self.0.borrow_mut() {
+            Backend::System(clipboard) => clipboard.set_text(text.into())?,
+            Backend::Memory(contents) => *contents = Some(text.into()),
+        }
+        Ok(())
+    }
+
+    /// Empty the clipboard.
+    pub fn clear(&self) -> Result<(), ClipboardError> {
+        match &mut *self.0.borrow_mut() {
+            Backend::System(clipboard) => clipboard.clear()?,
+            Backend::Memory(contents) => *contents = None,
+        }
+        Ok(())
+    }
+}
+
+/// A clipboard error.
+#[derive(Debug, thiserror::Error)]
+pub enum ClipboardError {
+    #[error("{0}")]
+    System(#[from] arboard::Error),
+}
@@ -1,7 +1,8 @@
 use super::Time;
-use crate::core::Window;
+use crate::audio::Audio;
+use crate::core::{Clipboard, Events, GameBuilder, Window};
 use crate::gfx::Graphics;
-use crate::input::{Gamepads, Keyboard, Mouse};
+use crate::input::{Gamepads, InputContexts, InputRecorder, Keyboard, Mouse, Touches};
 use directories::ProjectDirs;
 use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
@@ -23,7 +24,13 @@ pub struct ContextData {
     pub mouse: Mouse,
     pub keyboard: Keyboard,
     pub gamepads: Gamepads,
+    pub touches: Touches,
+    pub input_recorder: InputRecorder,
+    pub input_contexts: InputContexts,
+    pub events: Events,
+    pub clipboard: Clipboard,
     pub graphics: Graphics,
+    pub audio: Audio,
 
     #[cfg(feature = "lua")]
     pub lua: mlua::WeakLua,
@@ -52,6 +59,63 @@ impl Debug for Context {
 }
 
 impl Context {
+    pub(crate) fn new(
+        window: Window,
+        graphics: Graphics,
+        audio: Audio,
+        opts: &GameBuilder,
+    ) -> Self {
+        // load the project directories
+        let app_name = if opts.app_name.is_empty() {
+            opts.title.as_str()
+        } else {
+            opts.app_name.as_str()
+        };
+        let dirs = ProjectDirs::from("", &opts.app_organization, app_name)
+            .expect("failed to locate system directories");
+
+        // set up input recording and replaying
+        let mouse = Mouse::new();
+        let keyboard = Keyboard::new();
+        let gamepads = Gamepads::new();
+        let touches = Touches::new(mouse.clone());
+        let input_recorder = InputRecorder::new(keyboard.clone(), mouse.clone(), gamepads.clone());
+        if let Some(recording) = &opts.input_replay {
+            input_recorder.start_replay(recording.clone());
+        }
+        if let Some(path) = &opts.input_recording {
+            input_recorder.start_recording_to(path.clone());
+        }
+
+        let time = Time::new();
+        let input_contexts = InputContexts::new(time.clone());
+
+        Self(Rc::new(ContextData {
+            window,
+            time,
+            mouse,
+            keyboard,
+            gamepads,
+            touches,
+            input_recorder,
+            input_contexts,
+            events: Events::new(),
+            clipboard: Clipboard::new(),
+            graphics,
+            audio,
+
+            #[cfg(feature = "lua")]
+            lua: opts.lua.weak(),
+
+            #[cfg(feature = "lua")]
+            reload_lua: Cell::new(false),
+
+            quit_requested: Cell::new(false),
+
+            dirs,
+        }))
+    }
+
     #[cfg(feature = "lua")]
     pub fn from_lua(lua: &mlua::Lua) -> mlua::AppDataRef<'_, Self> {
         lua.app_data_ref::<Self>().unwrap()
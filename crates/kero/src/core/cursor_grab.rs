@@ -0,0 +1,56 @@
+use winit::window::CursorGrabMode;
+
+/// How the cursor is held by the window.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CursorGrab {
+    /// The cursor moves freely.
+    #[default]
+    None,
+
+    /// The cursor can't leave the window. Not supported on macOS.
+    Confined,
+
+    /// The cursor is locked in place. Use [`Mouse::motion`](crate::input::Mouse::motion) to read
+    /// how the mouse moves. Not supported on Windows or X11.
+    Locked,
+}
+
+impl From<CursorGrab> for CursorGrabMode {
+    #[inline]
+    fn from(value: CursorGrab) -> Self {
+        match value {
+            CursorGrab::None => Self::None,
+            CursorGrab::Confined => Self::Confined,
+            CursorGrab::Locked => Self::Locked,
+        }
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::FromLua for CursorGrab {
+    fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
+        let s = mlua::BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "none" => Self::None,
+            "confined" => Self::Confined,
+            "locked" => Self::Locked,
+            s => {
+                return Err(mlua::prelude::LuaError::runtime(format!(
+                    "invalid cursor grab {s:?}"
+                )));
+            }
+        })
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::IntoLua for CursorGrab {
+    fn into_lua(self, lua: &mlua::Lua) -> mlua::Result<mlua::Value> {
+        match self {
+            Self::None => "none",
+            Self::Confined => "confined",
+            Self::Locked => "locked",
+        }
+        .into_lua(lua)
+    }
+}
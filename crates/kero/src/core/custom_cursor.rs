@@ -0,0 +1,95 @@
+use crate::grid::Grid;
+use crate::img::ImageRgba8;
+use crate::math::Vec2U;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, OnceLock};
+use thiserror::Error;
+use winit::event_loop::ActiveEventLoop;
+use winit::window::{CustomCursor as WinitCursor, CustomCursorSource, MAX_CURSOR_SIZE};
+
+#[cfg(feature = "lua")]
+pub type CustomCursorRef = mlua::UserDataRef<CustomCursor>;
+
+/// Error creating a [`CustomCursor`].
+#[derive(Debug, Error)]
+pub enum CustomCursorError {
+    #[error(
+        "cursor image is {size}, larger than the maximum of {MAX_CURSOR_SIZE}x{MAX_CURSOR_SIZE}"
+    )]
+    TooLarge { size: Vec2U },
+
+    #[error("cursor hotspot {hotspot} is outside of the {size} image")]
+    HotspotOutOfBounds { hotspot: Vec2U, size: Vec2U },
+}
+
+/// A cursor made from an image, which can be shown with
+/// [`Window::set_custom_cursor`](super::Window::set_custom_cursor).
+///
+/// This handle can be cloned and passed around freely.
+#[derive(Clone)]
+pub struct CustomCursor(Arc<Inner>);
+
+struct Inner {
+    source: Mutex<Option<CustomCursorSource>>,
+    cursor: OnceLock<WinitCursor>,
+}
+
+impl Debug for CustomCursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomCursor").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for CustomCursor {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl CustomCursor {
+    /// Create a cursor from the image. The hotspot is the pixel of the image that points at the
+    /// mouse position, such as the tip of an arrow. Images larger than 2048x2048 are not allowed,
+    /// and some platforms limit cursors to much smaller sizes, so keep them small.
+    pub fn new(image: &ImageRgba8, hotspot: impl Into<Vec2U>) -> Result<Self, CustomCursorError> {
+        let size = image.size();
+        let hotspot = hotspot.into();
+        let max = MAX_CURSOR_SIZE as u32;
+        if size.x > max || size.y > max {
+            return Err(CustomCursorError::TooLarge { size });
+        }
+        if hotspot.x >= size.x || hotspot.y >= size.y {
+            return Err(CustomCursorError::HotspotOutOfBounds { hotspot, size });
+        }
+
+        // the image is already checked, so this can only fail if the image's store is invalid
+        let source = WinitCursor::from_rgba(
+            image.bytes(),
+            size.x as u16,
+            size.y as u16,
+            hotspot.x as u16,
+            hotspot.y as u16,
+        )
+        .expect("invalid cursor image");
+
+        Ok(Self(Arc::new(Inner {
+            source: Mutex::new(Some(source)),
+            cursor: OnceLock::new(),
+        })))
+    }
+
+    /// The platform cursor, if it was already created.
+    #[inline]
+    pub(crate) fn get(&self) -> Option<&WinitCursor> {
+        self.0.cursor.get()
+    }
+
+    /// The platform cursor, creating it if needed.
+    #[inline]
+    pub(crate) fn get_or_create(&self, event_loop: &ActiveEventLoop) -> &WinitCursor {
+        self.0.cursor.get_or_init(|| {
+            let source = self.0.source.lock().unwrap().take();
+            event_loop.create_custom_cursor(source.expect("cursor was already created"))
+        })
+    }
+}
@@ -0,0 +1,171 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// The system's color theme.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+impl From<winit::window::Theme> for Theme {
+    #[inline]
+    fn from(value: winit::window::Theme) -> Self {
+        match value {
+            winit::window::Theme::Light => Self::Light,
+            winit::window::Theme::Dark => Self::Dark,
+        }
+    }
+}
+
+/// Something that happened to the game's window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppEvent {
+    /// A file is being dragged over the window. This happens once for each file being dragged.
+    FileHovered(PathBuf),
+
+    /// The files being dragged over the window were dragged away or the drag was cancelled.
+    FileHoverCancelled,
+
+    /// A file was dropped onto the window. This happens once for each file dropped.
+    FileDropped(PathBuf),
+
+    /// The window gained (`true`) or lost (`false`) focus.
+    Focused(bool),
+
+    /// The window became fully hidden from view (`true`), for example by being minimized or
+    /// covered by another window, or became visible again (`false`). Not all platforms report
+    /// this.
+    Occluded(bool),
+
+    /// The system's color theme changed.
+    ThemeChanged(Theme),
+}
+
+/// Handle to the window events that happened this frame.
+///
+/// This handle can be cloned and passed around freely to give objects access to the events.
+///
+/// ```ignore
+/// // pause the game when the player switches to another window
+/// if ctx.events.focus_lost() {
+///     self.paused = true;
+/// }
+///
+/// // import any levels dropped onto the editor
+/// for path in ctx.events.dropped_files() {
+///     self.import(&path)?;
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Events(Rc<State>);
+
+impl Debug for Events {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Events").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    phases: [RefCell<Vec<AppEvent>>; 3],
+    phase: Cell<usize>,
+    hovered_files: RefCell<Vec<PathBuf>>,
+    occluded: Cell<bool>,
+}
+
+impl Events {
+    pub(crate) fn new() -> Self {
+        Self(Rc::new(State {
+            phases: std::array::from_fn(|_| RefCell::new(Vec::new())),
+            phase: Cell::new(0),
+            hovered_files: RefCell::new(Vec::new()),
+            occluded: Cell::new(false),
+        }))
+    }
+
+    #[inline]
+    fn phase(&self) -> &RefCell<Vec<AppEvent>> {
+        &self.0.phases[self.0.phase.get()]
+    }
+
+    /// All events that happened this frame, in the order they happened.
+    #[inline]
+    pub fn all(&self) -> impl Iterator<Item = AppEvent> {
+        self.phase().borrow().clone().into_iter()
+    }
+
+    /// Files that were dropped onto the window this frame.
+    #[inline]
+    pub fn dropped_files(&self) -> impl Iterator<Item = PathBuf> {
+        self.all().filter_map(|e| match e {
+            AppEvent::FileDropped(path) => Some(path),
+            _ => None,
+        })
+    }
+
+    /// Files that are currently being dragged over the window.
+    #[inline]
+    pub fn hovered_files(&self) -> Vec<PathBuf> {
+        self.0.hovered_files.borrow().clone()
+    }
+
+    /// If the window gained focus this frame.
+    #[inline]
+    pub fn focus_gained(&self) -> bool {
+        self.phase().borrow().contains(&AppEvent::Focused(true))
+    }
+
+    /// If the window lost focus this frame.
+    #[inline]
+    pub fn focus_lost(&self) -> bool {
+        self.phase().borrow().contains(&AppEvent::Focused(false))
+    }
+
+    /// If the window is fully hidden from view. This is always false on platforms that don't
+    /// report it.
+    #[inline]
+    pub fn occluded(&self) -> bool {
+        self.0.occluded.get()
+    }
+
+    #[inline]
+    pub(crate) fn set_update_phase(&self) {
+        self.0.phase.set(0);
+    }
+
+    #[inline]
+    pub(crate) fn set_render_phase(&self) {
+        self.0.phase.set(1);
+    }
+
+    #[inline]
+    pub(crate) fn set_fixed_update_phase(&self) {
+        self.0.phase.set(2);
+    }
+
+    pub(crate) fn push(&self, event: AppEvent) {
+        match &event {
+            AppEvent::FileHovered(path) => {
+                self.0.hovered_files.borrow_mut().push(path.clone());
+            }
+            AppEvent::FileHoverCancelled | AppEvent::FileDropped(_) => {
+                self.0.hovered_files.borrow_mut().clear();
+            }
+            AppEvent::Occluded(occluded) => {
+                self.0.occluded.set(*occluded);
+            }
+            AppEvent::Focused(_) | AppEvent::ThemeChanged(_) => {}
+        }
+        for phase in &self.0.phases {
+            phase.borrow_mut().push(event.clone());
+        }
+    }
+
+    #[inline]
+    pub(crate) fn clear_phase(&self) {
+        self.phase().borrow_mut().clear();
+    }
+}
@@ -1,5 +1,4 @@
 use crate::core::{Monitor, TimeState};
-use std::mem::replace;
 use std::rc::Rc;
 use std::time::{Duration, Instant};
 
@@ -14,7 +13,6 @@ pub(crate) struct FrameTimer {
     pub accum: Duration,
     pub prev_frame: Option<Instant>,
     pub snapshots: Vec<f64>,
-    pub last_unfixed: Option<Instant>,
 }
 
 impl FrameTimer {
@@ -25,11 +23,12 @@ impl FrameTimer {
             accum: Duration::from_secs(0),
             prev_frame: None,
             snapshots: Vec::new(),
-            last_unfixed: None,
         }
     }
 
-    pub fn tick<F: FnMut()>(&mut self, display: Option<Monitor>, mut update_fn: F) {
+    /// Advance the timer by one frame, calling `fixed_update_fn` for every fixed step that
+    /// has accumulated. Afterwards, the delta is set to the frame's variable delta.
+    pub fn tick<F: FnMut()>(&mut self, display: Option<Monitor>, mut fixed_update_fn: F) {
         let refresh_rate = display
             .and_then(|monitor| monitor.refresh_rate_mhz())
             .unwrap_or(60000);
@@ -41,9 +40,9 @@ impl FrameTimer {
             .get()
             .unwrap_or((refresh_rate as f64) / 1000.0);
 
-        // get our frame duration based on our FPS, which doubles as the "delta" property
+        // get our fixed step duration based on our FPS
         let frame_duration = Duration::from_secs_f64(1.0 / target_fps);
-        self.time.delta.set(frame_duration.as_secs_f32());
+        self.time.fixed_delta.set(frame_duration.as_secs_f32());
 
         // check how much time has passed since the last render
         let curr_time = Instant::now();
@@ -57,38 +56,39 @@ impl FrameTimer {
                 .unwrap_or(delta)
         }
 
-        // accumulate time so we know when to trigger a frame
-        self.accum = self.accum + Duration::from_secs_f64(delta); //.min(max_duration);
+        // don't let a long stall accumulate more time than we're willing to catch up on, or
+        // each frame could take longer than the last trying to catch up
+        let max_accum = Duration::from_secs_f32(self.time.max_accumulated_time.get().max(0.0))
+            .max(frame_duration);
+        let delta = delta.min(max_accum.as_secs_f64());
 
-        // when accumulator exceeds our target frame time, perform a frame
+        // accumulate time so we know when to trigger a fixed update
+        self.accum = (self.accum + Duration::from_secs_f64(delta)).min(max_accum);
+
+        // when accumulator exceeds our fixed step, perform a fixed update
         let mut max_frames = self.time.max_frame_skip.get() + 1;
-        if self.accum >= frame_duration {
-            while self.accum >= frame_duration {
-                self.accum -= frame_duration;
-
-                if max_frames > 0 {
-                    max_frames -= 1;
-
-                    // how long since our last update?
-                    let now = Instant::now();
-                    if let Some(last_unfixed) = replace(&mut self.last_unfixed, Some(now)) {
-                        self.time
-                            .unfixed_delta
-                            .set((now - last_unfixed).as_secs_f32());
-                    }
-
-                    // update the timer
-                    self.time
-                        .since_startup
-                        .update(|t| t + self.time.delta.get());
-                    self.time.frame.update(|f| f + 1);
-
-                    // notify that an update happened
-                    update_fn();
-                }
+        while self.accum >= frame_duration {
+            self.accum -= frame_duration;
+
+            if max_frames > 0 {
+                max_frames -= 1;
+
+                // notify that a fixed update happened
+                self.time.delta.set(frame_duration.as_secs_f32());
+                fixed_update_fn();
             }
         }
 
+        // how far we are between the last fixed update and the next one
+        self.time
+            .alpha
+            .set((self.accum.as_secs_f64() / frame_duration.as_secs_f64()) as f32);
+
+        // the frame's update and render use the variable delta
+        self.time.delta.set(delta as f32);
+        self.time.since_startup.update(|t| t + delta as f32);
+        self.time.frame.update(|f| f + 1);
+
         // track the FPS over the last 60 frames
         let curr_frame = Instant::now();
         let frame_diff = (curr_frame - self.prev_frame.unwrap_or(curr_frame)).as_secs_f64();
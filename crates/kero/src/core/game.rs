@@ -11,9 +11,35 @@ pub trait Game: 'static {
     where
         Self: Sized;
 
-    /// Called every update in order to perform game logic.
+    /// Called at a fixed rate, set by [`Time::set_target_fps`](crate::core::Time::set_target_fps),
+    /// in order to perform game logic that needs a constant time step, such as physics. It may be
+    /// called several times in one frame, or not at all.
+    ///
+    /// Input events such as presses and releases are held until the next fixed update sees them,
+    /// and each one is seen by exactly one fixed update, so input that drives fixed-step logic
+    /// should be read here rather than in [`update`](Game::update).
+    #[allow(unused_variables)]
+    fn fixed_update(&mut self, ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called once every frame, after any fixed updates, in order to perform game logic.
+    ///
+    /// Input events such as presses and releases are seen here once per frame, on the frame they
+    /// happened, independently of whether any fixed update has seen them.
     fn update(&mut self, ctx: &Context) -> Result<(), GameError>;
 
     /// Called every frame refresh in order to perform game rendering.
     fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError>;
+
+    /// Called when [fixed_update()](Game::fixed_update), [update()](Game::update), or
+    /// [render()](Game::render) returns an error, right
+    /// before the game shuts down and the error is returned from
+    /// [`GameBuilder::run`](crate::core::GameBuilder::run). Useful for crash reporting or saving
+    /// the player's progress.
+    ///
+    /// Lua errors while hot-reloading don't shut the game down, so they are logged with
+    /// [`log::error!`] instead of being passed here.
+    #[allow(unused_variables)]
+    fn on_error(&mut self, ctx: &Context, error: &GameError) {}
 }
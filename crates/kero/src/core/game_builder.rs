@@ -1,6 +1,10 @@
+use crate::audio::AudioOutput;
 use crate::core::app_handler::AppHandler;
-use crate::core::{Game, GameError};
+use crate::core::{Context, Game, GameError};
+use crate::img::ImageRgba8;
+use crate::input::InputRecording;
 use crate::math::Vec2U;
+use std::path::PathBuf;
 use winit::event_loop::EventLoop;
 
 /// A builder for a game.
@@ -11,8 +15,16 @@ pub struct GameBuilder {
     pub app_organization: String,
     pub app_name: String,
 
+    pub audio_output: AudioOutput,
+
+    pub input_recording: Option<PathBuf>,
+    pub input_replay: Option<InputRecording>,
+
     #[cfg(feature = "lua")]
     pub lua: mlua::Lua,
+
+    #[cfg(feature = "lua")]
+    pub lua_hot_reload: bool,
 }
 
 impl GameBuilder {
@@ -24,12 +36,20 @@ impl GameBuilder {
             app_organization: String::new(),
             app_name: String::new(),
 
+            audio_output: AudioOutput::Device,
+
+            input_recording: None,
+            input_replay: None,
+
             #[cfg(feature = "lua")]
             lua: {
                 let lua = mlua::Lua::new();
                 crate::lua::TempTypes::init(&lua)?;
                 lua
             },
+
+            #[cfg(feature = "lua")]
+            lua_hot_reload: false,
         };
 
         #[cfg(feature = "lua")]
@@ -42,25 +62,40 @@ impl GameBuilder {
                 .with_module::<fey_lua::InstantModule>()?
                 .with_modules::<fey_math::MathModules>()?
                 .with_module::<fey_rand::RandModule>()?
+                .with_module::<ActionMapModule>()?
                 .with_module::<AppModule>()?
+                .with_module::<AudioModule>()?
+                .with_module::<BufferedInputModule>()?
+                .with_module::<CaptureModule>()?
+                .with_module::<ClipboardModule>()?
                 .with_module::<ColorModeModule>()?
+                .with_module::<CustomCursorModule>()?
                 .with_module::<DrawModule>()?
+                .with_module::<EventsModule>()?
                 .with_module::<FontModule>()?
                 .with_module::<IndexBufferModule>()?
+                .with_module::<InputContextsModule>()?
+                .with_module::<InstanceBufferModule>()?
+                .with_module::<InstanceModule>()?
                 .with_module::<GamepadModule>()?
                 .with_module::<GamepadButtonModule>()?
                 .with_module::<GamepadAxisModule>()?
+                .with_module::<GamepadStickModule>()?
                 .with_module::<KeyModule>()?
                 .with_module::<KeyboardModule>()?
                 .with_module::<MonitorModule>()?
                 .with_module::<MouseModule>()?
+                .with_module::<MusicModule>()?
                 .with_module::<SamplerModule>()?
                 .with_module::<ScreenModule>()?
                 .with_module::<ShaderModule>()?
+                .with_module::<SoundModule>()?
                 .with_module::<SubTextureModule>()?
                 .with_module::<SurfaceModule>()?
+                .with_module::<TextEditModule>()?
                 .with_module::<TextureModule>()?
                 .with_module::<TimeModule>()?
+                .with_module::<TouchesModule>()?
                 .with_module::<VertexBufferModule>()?
                 .with_module::<VertexModule>()?
                 .with_module::<VideoModeModule>()?
@@ -103,6 +138,48 @@ impl GameBuilder {
         }
     }
 
+    /// Set where the game's audio is output. Use [`AudioOutput::Null`] to run without a sound
+    /// device, such as when testing.
+    pub fn with_audio_output(self, audio_output: AudioOutput) -> Self {
+        Self {
+            audio_output,
+            ..self
+        }
+    }
+
+    /// Record the player's input from the start of the game, and save the recording to `path`
+    /// when the game exits. See [`InputRecorder`](crate::input::InputRecorder).
+    pub fn with_input_recording(self, path: impl Into<PathBuf>) -> Self {
+        Self {
+            input_recording: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Replay the recording from the start of the game in place of the real input devices.
+    /// Combined with [`run_headless`](Self::run_headless), this can be used to play back bug
+    /// reports or run automated playtests.
+    pub fn with_input_replay(self, recording: InputRecording) -> Self {
+        Self {
+            input_replay: Some(recording),
+            ..self
+        }
+    }
+
+    /// Reload the Lua scripts whenever a file in the `lua` folder is added, removed, or changed.
+    ///
+    /// Before reloading, `Main:save_state()` is called if it exists, and the value it returns is
+    /// passed to `Main:load_state(state)` after reloading. If `load_state` exists, it is called
+    /// instead of `Main:init()`, so the game can pick up where it left off. `App.restart()` still
+    /// resets the game from scratch.
+    #[cfg(feature = "lua")]
+    pub fn with_lua_hot_reload(self, lua_hot_reload: bool) -> Self {
+        Self {
+            lua_hot_reload,
+            ..self
+        }
+    }
+
     #[cfg(feature = "lua")]
     pub fn with_module<M: crate::lua::LuaModule>(self) -> Result<Self, GameError> {
         let module = M::load(&self.lua)?;
@@ -123,16 +200,55 @@ impl GameBuilder {
     }
 
     /// Run your game.
+    ///
+    /// If creating, updating, or rendering the game returns an error, the game is stopped and the
+    /// error is returned. [`Game::on_error`] is called with the error first, if the game was
+    /// already created.
     pub fn run<G: Game>(self, cfg: G::Config) -> Result<(), GameError> {
         let event_loop = EventLoop::new()?;
-        event_loop.run_app(&mut AppHandler::<G>::new(self, cfg))?;
-        Ok(())
+        let mut app = AppHandler::<G>::new(self, cfg);
+        event_loop.run_app(&mut app)?;
+        match app.take_error() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Run your game headless for the provided number of frames, without creating a window, and
+    /// return every rendered frame.
+    ///
+    /// Frames are rendered to an offscreen surface the size of the window, using a software
+    /// graphics adapter if one is available, so this works on machines with no display or GPU.
+    /// Each frame performs exactly one fixed update and one update with the same time step.
+    /// Audio is not played on any device, the null output is used instead.
+    pub fn run_headless<G: Game>(
+        self,
+        cfg: G::Config,
+        frames: u64,
+    ) -> Result<Vec<ImageRgba8>, GameError> {
+        let mut images = Vec::new();
+        self.run_headless_with::<G>(cfg, frames, |_, img| {
+            images.push(img);
+            Ok(())
+        })?;
+        Ok(images)
+    }
+
+    /// Run your game headless for the provided number of frames, passing each rendered frame to
+    /// `on_frame` as it is produced. See [`run_headless`](Self::run_headless) for details.
+    pub fn run_headless_with<G: Game>(
+        self,
+        cfg: G::Config,
+        frames: u64,
+        on_frame: impl FnMut(&Context, ImageRgba8) -> Result<(), GameError>,
+    ) -> Result<(), GameError> {
+        crate::core::headless::run_headless::<G>(self, cfg, frames, on_frame)
     }
 
     #[cfg(feature = "lua")]
     pub fn run_lua(self) -> Result<(), GameError> {
-        use crate::gfx::Draw;
         use crate::core::Context;
+        use crate::gfx::Draw;
 
         pub struct LuaApp;
 
@@ -1,6 +1,10 @@
-use crate::gfx::{DrawError, IndexBufferUploadError, VertexBufferUploadError};
+use crate::audio::AudioError;
+use crate::gfx::{
+    DrawError, IndexBufferUploadError, ShaderError, TextureUploadError, VertexBufferUploadError,
+};
 use crate::guid::GuidParseError;
 use crate::img::ImageError;
+use crate::input::{BindingSetError, InputRecordingError};
 use std::error::Error;
 use winit::error::EventLoopError;
 
@@ -37,12 +41,24 @@ pub enum GameError {
     #[error("{0}")]
     Font(#[from] fey_font::FontError),
 
+    #[error("{0}")]
+    Audio(#[from] AudioError),
+
     #[cfg(feature = "lua")]
     #[error("{0}")]
     Lua(#[from] mlua::prelude::LuaError),
-    //
-    // #[error("{0}")]
-    // TextureUpload(#[from] TextureUploadError),
+
+    #[error("{0}")]
+    TextureUpload(#[from] TextureUploadError),
+
+    #[error("{0}")]
+    InputRecording(#[from] InputRecordingError),
+
+    #[error("{0}")]
+    BindingSet(#[from] BindingSetError),
+
+    #[error("{0}")]
+    Shader(#[from] ShaderError),
 }
 
 impl GameError {
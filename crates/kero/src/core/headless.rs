@@ -0,0 +1,165 @@
+use crate::audio::{Audio, AudioOutput, DEFAULT_SAMPLE_RATE};
+use crate::core::{Context, Game, GameBuilder, GameError, Window};
+use crate::gfx::{Draw, Graphics};
+use crate::img::ImageRgba8;
+
+/// Run a game without a window for a fixed number of frames, rendering each frame to an
+/// offscreen surface and passing the result to `on_frame`.
+///
+/// Every frame performs exactly one fixed update and one update with the same delta, so runs are
+/// deterministic.
+pub(crate) fn run_headless<G: Game>(
+    opts: GameBuilder,
+    cfg: G::Config,
+    frames: u64,
+    mut on_frame: impl FnMut(&Context, ImageRgba8) -> Result<(), GameError>,
+) -> Result<(), GameError> {
+    // create a stand-in for the window
+    let window = Window::headless(&opts.title, opts.size);
+
+    // initialize the graphics
+    let graphics = Graphics::new(window.clone(), &opts);
+
+    // create the drawing context
+    let mut draw = Draw::new(
+        graphics.device().clone(),
+        graphics.queue().clone(),
+        graphics.default_shader().clone(),
+        graphics.mask_shader().clone(),
+        graphics.default_texture().clone(),
+    );
+
+    // headless games never play audio on a device
+    let audio = Audio::new(match opts.audio_output {
+        AudioOutput::Device => AudioOutput::Null {
+            sample_rate: DEFAULT_SAMPLE_RATE,
+        },
+        output => output,
+    });
+
+    // create the game context
+    let ctx = Context::new(window, graphics, audio, &opts);
+
+    // the offscreen surface that stands in for the window
+    let target = ctx.graphics.create_rgba8_surface(opts.size);
+
+    #[cfg(feature = "lua")]
+    let mut lua_app = crate::core::LuaApp::new(opts.lua.clone(), &ctx, opts.lua_hot_reload)?;
+
+    // create the game
+    let mut game = G::new(&ctx, cfg)?;
+
+    // run the frames, stopping at the first error
+    let mut run_frames = || -> Result<(), GameError> {
+        for _ in 0..frames {
+            // reload any shaders that changed on disk
+            ctx.graphics.update_shaders();
+
+            // advance the timer by exactly one frame
+            let time = &ctx.time.0;
+            let fps = time.target_fps.get().unwrap_or(60.0);
+            let delta = (1.0 / fps) as f32;
+            time.delta.set(delta);
+            time.fixed_delta.set(delta);
+            time.unfixed_delta.set(delta);
+            time.fps.set(fps.round() as u32);
+            time.since_startup.update(|t| t + delta);
+            time.frame.update(|f| f + 1);
+
+            // perform exactly one fixed update, leaving no time between it and the next one
+            ctx.mouse.set_fixed_update_phase();
+            ctx.keyboard.set_fixed_update_phase();
+            ctx.gamepads.set_fixed_update_phase();
+            ctx.touches.set_fixed_update_phase();
+            ctx.events.set_fixed_update_phase();
+            ctx.input_recorder.before_fixed_update(&ctx);
+            #[cfg(feature = "lua")]
+            lua_app.fixed_update(&ctx)?;
+            game.fixed_update(&ctx)?;
+            time.alpha.set(0.0);
+
+            // the fixed update consumed the on-frame events (eg. pressed, released)
+            ctx.mouse.clear_phase();
+            ctx.keyboard.clear_phase();
+            ctx.gamepads.clear_phase();
+            ctx.touches.clear_phase();
+            ctx.events.clear_phase();
+            ctx.mouse.set_update_phase();
+            ctx.keyboard.set_update_phase();
+            ctx.gamepads.set_update_phase();
+            ctx.touches.set_update_phase();
+            ctx.events.set_update_phase();
+
+            // update the lua app
+            #[cfg(feature = "lua")]
+            lua_app.update(&ctx)?;
+
+            // update the game
+            game.update(&ctx)?;
+
+            // advance the audio
+            ctx.audio.update(delta);
+
+            // clear input on-frame events (eg. pressed, released)
+            ctx.mouse.clear_phase();
+            ctx.keyboard.clear_phase();
+            ctx.gamepads.clear_phase();
+            ctx.touches.clear_phase();
+            ctx.events.clear_phase();
+
+            // switch to the render phase for input
+            ctx.mouse.set_render_phase();
+            ctx.keyboard.set_render_phase();
+            ctx.gamepads.set_render_phase();
+            ctx.touches.set_render_phase();
+            ctx.events.set_render_phase();
+
+            // render the frame to the offscreen surface
+            draw.begin_frame(ctx.window.size());
+
+            #[cfg(feature = "lua")]
+            lua_app.render(&ctx, &mut draw)?;
+
+            game.render(&ctx, &mut draw)?;
+            draw.end_frame_offscreen(time.frame.get(), &ctx.graphics, &target);
+
+            // clear input on-frame events (eg. pressed, released)
+            ctx.mouse.clear_phase();
+            ctx.keyboard.clear_phase();
+            ctx.gamepads.clear_phase();
+            ctx.touches.clear_phase();
+            ctx.events.clear_phase();
+
+            // switch back to the update phase for input
+            ctx.mouse.set_update_phase();
+            ctx.keyboard.set_update_phase();
+            ctx.gamepads.set_update_phase();
+            ctx.touches.set_update_phase();
+            ctx.events.set_update_phase();
+
+            // read the frame back from the GPU
+            on_frame(&ctx, target.texture().read_pixels())?;
+
+            // quit if the user requested it
+            if ctx.quit_requested() {
+                break;
+            }
+        }
+        Ok(())
+    };
+    let result = run_frames();
+
+    // let the game know it failed
+    if let Err(err) = &result {
+        game.on_error(&ctx, err);
+    }
+
+    // save any recordings that are still in progress
+    let recorded = ctx.graphics.stop_recording();
+    let recorded_input = ctx.input_recorder.finish();
+    result?;
+    recorded?;
+    recorded_input?;
+
+    Ok(())
+}
@@ -1,11 +1,37 @@
 use super::{Context, GameError};
 use crate::gfx::Draw;
 use fey_lua::TempTypes;
-use mlua::prelude::LuaResult;
+use mlua::prelude::{LuaError, LuaResult};
 use mlua::{Function, Lua, Table, Value};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Callbacks that run right before Lua scripts are reloaded, so systems that store data defined
+/// by scripts can clear it out before the scripts run again.
+#[derive(Default)]
+pub struct LuaReloadHooks(Vec<Box<dyn Fn(&Lua) -> LuaResult<()>>>);
+
+impl LuaReloadHooks {
+    /// Add a callback to run before Lua scripts are reloaded.
+    pub fn add(lua: &Lua, hook: impl Fn(&Lua) -> LuaResult<()> + 'static) {
+        if lua.app_data_ref::<Self>().is_none() {
+            lua.set_app_data(Self::default());
+        }
+        lua.app_data_mut::<Self>().unwrap().0.push(Box::new(hook));
+    }
+
+    fn run(lua: &Lua) -> LuaResult<()> {
+        // take the hooks out so they can access the app data themselves
+        let Some(hooks) = lua.remove_app_data::<Self>() else {
+            return Ok(());
+        };
+        let result = hooks.0.iter().try_for_each(|hook| hook(lua));
+        lua.set_app_data(hooks);
+        result
+    }
+}
 
 pub struct LuaApp {
     pub lua: Lua,
@@ -13,10 +39,11 @@ pub struct LuaApp {
     pub default_modules: HashSet<String>,
     pub main: LuaResult<LuaMain>,
     pub call_lua_init: bool,
+    pub watcher: Option<LuaWatcher>,
 }
 
 impl LuaApp {
-    pub fn new(lua: Lua, ctx: &Context) -> Self {
+    pub fn new(lua: Lua, ctx: &Context, hot_reload: bool) -> Result<Self, GameError> {
         // add context to lua
         assert!(
             lua.set_app_data(ctx.clone()).is_none(),
@@ -31,33 +58,7 @@ impl LuaApp {
         );
 
         // preload all scripts in the root lua folder
-        fn read_dir(lua: &Lua, dir: PathBuf, prefix: String) -> Result<(), GameError> {
-            for entry in std::fs::read_dir(&dir)?.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    if let Some(name) = path.file_name().and_then(OsStr::to_str) {
-                        read_dir(lua, dir.join(name), format!("{prefix}{name}."))?;
-                    }
-                } else if path.is_file() && path.extension().is_some_and(|ext| ext == "lua") {
-                    if let Some(name) = path.file_stem().and_then(OsStr::to_str) {
-                        let path = path.clone();
-                        let file_name = path.to_string_lossy().to_string();
-                        let mod_name = format!("{prefix}{name}");
-                        lua.preload_module(
-                            &mod_name,
-                            lua.create_function(move |lua, _: ()| {
-                                let code = std::fs::read_to_string(&path)?;
-                                lua.load(code)
-                                    .set_name(format!("@{file_name}"))
-                                    .eval::<Value>()
-                            })?,
-                        )?;
-                    }
-                }
-            }
-            Ok(())
-        }
-        read_dir(&lua, "lua".into(), String::new()).unwrap();
+        preload_scripts(&lua)?;
 
         // get a list of all the default globals
         let default_globals = lua
@@ -79,36 +80,84 @@ impl LuaApp {
 
         // load up the entry point
         let main = LuaMain::load(&lua, &default_globals, &default_modules);
-        let call_lua_init = if let Err(err) = &main {
-            println!("{err}");
-            false
-        } else {
-            true
-        };
+        let call_lua_init = main.is_ok();
 
-        Self {
+        let mut app = Self {
             lua,
             default_globals,
             default_modules,
             main,
             call_lua_init,
+            watcher: hot_reload.then(LuaWatcher::new),
+        };
+        if let Err(err) = &app.main {
+            app.fail(err.clone())?;
         }
+        Ok(app)
     }
 
-    pub fn reload(&mut self) {
-        self.main = LuaMain::load(&self.lua, &self.default_globals, &self.default_modules);
-        self.call_lua_init = if let Err(err) = &self.main {
-            println!("{err}");
-            false
+    /// Stop running the scripts because of an error. When hot-reloading, the error is logged and
+    /// the scripts wait until they are fixed, otherwise the error is returned.
+    fn fail(&mut self, err: LuaError) -> Result<(), GameError> {
+        self.main = Err(err.clone());
+        self.call_lua_init = false;
+        self.report(err.into())
+    }
+
+    /// Log the error if hot-reloading, otherwise return it.
+    fn report(&self, err: GameError) -> Result<(), GameError> {
+        if self.watcher.is_some() {
+            log::error!("{err}");
+            Ok(())
         } else {
-            true
+            Err(err)
+        }
+    }
+
+    /// Reload the scripts. If `keep_state` is true, the game can save its state before the
+    /// reload and restore it afterwards.
+    pub fn reload(&mut self, keep_state: bool) -> Result<(), GameError> {
+        // let the game save its state before the scripts are unloaded
+        let state = match &self.main {
+            Ok(main) if keep_state => main.save_state().unwrap_or_else(|err| {
+                log::error!("{err}");
+                None
+            }),
+            _ => None,
+        };
+
+        // pick up any scripts that were added
+        if let Err(err) = preload_scripts(&self.lua) {
+            self.report(err)?;
+        }
+
+        // clear out any data that was defined by the old scripts
+        if let Err(err) = LuaReloadHooks::run(&self.lua) {
+            self.report(err.into())?;
+        }
+
+        self.main = LuaMain::load(&self.lua, &self.default_globals, &self.default_modules);
+        let main = match &self.main {
+            Ok(main) => main,
+            Err(err) => return self.fail(err.clone()),
         };
+
+        // if the game restores its state, it doesn't need to be initialized again
+        let restored = match state.map(|state| main.load_state(state)) {
+            Some(Ok(restored)) => restored,
+            Some(Err(err)) => return self.fail(err),
+            None => false,
+        };
+        self.call_lua_init = !restored;
+        Ok(())
     }
 
-    pub fn update(&mut self, ctx: &Context) {
-        // reload the lua if requested
+    pub fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+        // restart the lua if requested, or reload it if any scripts changed
         if ctx.reload_lua.take() {
-            self.reload();
+            self.reload(false)?;
+        } else if self.watcher.as_mut().is_some_and(LuaWatcher::changed) {
+            self.reload(true)?;
         }
 
         // call Main:init() when requested
@@ -116,35 +165,134 @@ impl LuaApp {
             self.call_lua_init = false;
 
             if let Ok(Err(err)) = self.main.as_ref().map(|main| main.init()) {
-                println!("{err}");
-                self.main = Err(err);
+                self.fail(err)?;
             }
         }
 
         // call Main:update()
         if let Ok(Err(err)) = self.main.as_ref().map(|main| main.update()) {
-            println!("{err}");
-            self.main = Err(err);
+            self.fail(err)?;
         }
+        Ok(())
     }
 
-    pub fn render(&mut self, _ctx: &Context, draw: &mut Draw) {
-        // call Main:render()
-        if let Ok(Err(err)) = self.main.as_ref().map(|main| main.render(&self.lua, draw)) {
-            println!("{err}");
-            self.main = Err(err);
+    pub fn fixed_update(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        // wait until Main:init() has been called
+        if self.call_lua_init {
+            return Ok(());
+        }
+
+        // call Main:fixed_update()
+        if let Ok(Err(err)) = self.main.as_ref().map(|main| main.fixed_update()) {
+            self.fail(err)?;
         }
+        Ok(())
+    }
+
+    pub fn render(&mut self, _ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+        // call Main:render()
+        let result = match self.main.as_ref().map(|main| main.render(&self.lua, draw)) {
+            Ok(Err(err)) => self.fail(err),
+            _ => Ok(()),
+        };
 
         // clear all single-frame temp types
         self.lua.app_data_mut::<TempTypes>().unwrap().clear_frame();
+        result
+    }
+}
+
+/// Preload all the scripts in the root lua folder, so they can be required by module name.
+fn preload_scripts(lua: &Lua) -> Result<(), GameError> {
+    fn read_dir(lua: &Lua, dir: PathBuf, prefix: String) -> Result<(), GameError> {
+        for entry in std::fs::read_dir(&dir)?.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if let Some(name) = path.file_name().and_then(OsStr::to_str) {
+                    read_dir(lua, dir.join(name), format!("{prefix}{name}."))?;
+                }
+            } else if path.is_file() && path.extension().is_some_and(|ext| ext == "lua") {
+                if let Some(name) = path.file_stem().and_then(OsStr::to_str) {
+                    let path = path.clone();
+                    let file_name = path.to_string_lossy().to_string();
+                    let mod_name = format!("{prefix}{name}");
+                    lua.preload_module(
+                        &mod_name,
+                        lua.create_function(move |lua, _: ()| {
+                            let code = std::fs::read_to_string(&path)?;
+                            lua.load(code)
+                                .set_name(format!("@{file_name}"))
+                                .eval::<Value>()
+                        })?,
+                    )?;
+                }
+            }
+        }
+        Ok(())
     }
+    read_dir(lua, "lua".into(), String::new())
+}
+
+/// Watches the root lua folder for scripts being added, removed, or changed.
+pub struct LuaWatcher {
+    modified: HashMap<PathBuf, SystemTime>,
+    last_check: Instant,
+}
+
+impl LuaWatcher {
+    /// How often the lua folder is checked for changes.
+    const INTERVAL: Duration = Duration::from_millis(250);
+
+    fn new() -> Self {
+        Self {
+            modified: scan_scripts(),
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Check if any scripts changed since the last check.
+    fn changed(&mut self) -> bool {
+        if self.last_check.elapsed() < Self::INTERVAL {
+            return false;
+        }
+        self.last_check = Instant::now();
+        let modified = scan_scripts();
+        let changed = modified != self.modified;
+        self.modified = modified;
+        changed
+    }
+}
+
+/// Get the modified time of every script in the root lua folder.
+fn scan_scripts() -> HashMap<PathBuf, SystemTime> {
+    fn scan(dir: &Path, modified: &mut HashMap<PathBuf, SystemTime>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                scan(&path, modified);
+            } else if path.extension().is_some_and(|ext| ext == "lua")
+                && let Ok(time) = entry.metadata().and_then(|m| m.modified())
+            {
+                modified.insert(path, time);
+            }
+        }
+    }
+    let mut modified = HashMap::new();
+    scan(Path::new("lua"), &mut modified);
+    modified
 }
 
 pub struct LuaMain {
     module: Table,
     init_fn: Function,
+    fixed_update_fn: Option<Function>,
     update_fn: Function,
     render_fn: Function,
+    save_state_fn: Option<Function>,
+    load_state_fn: Option<Function>,
 }
 
 impl LuaMain {
@@ -182,8 +330,11 @@ impl LuaMain {
             .unwrap()
             .call::<Table>("Main")?;
         let init_fn = module.get("init")?;
+        let fixed_update_fn = module.get("fixed_update")?;
         let update_fn = module.get("update")?;
         let render_fn = module.get("render")?;
+        let save_state_fn = module.get("save_state")?;
+        let load_state_fn = module.get("load_state")?;
 
         // run the garbage collector
         lua.gc_collect()?;
@@ -192,8 +343,11 @@ impl LuaMain {
         Ok(Self {
             module,
             init_fn,
+            fixed_update_fn,
             update_fn,
             render_fn,
+            save_state_fn,
+            load_state_fn,
         })
     }
 
@@ -202,6 +356,31 @@ impl LuaMain {
         self.init_fn.call(self.module.clone())
     }
 
+    /// Call `Main:save_state()` if it exists, returning the state to restore after reloading.
+    #[inline]
+    fn save_state(&self) -> LuaResult<Option<Value>> {
+        match &self.save_state_fn {
+            Some(f) => f.call(self.module.clone()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Call `Main:load_state(state)` if it exists, returning if it was called.
+    #[inline]
+    fn load_state(&self, state: Value) -> LuaResult<bool> {
+        match &self.load_state_fn {
+            Some(f) => f.call((self.module.clone(), state)).map(|()| true),
+            None => Ok(false),
+        }
+    }
+
+    fn fixed_update(&self) -> LuaResult<()> {
+        match &self.fixed_update_fn {
+            Some(f) => f.call(self.module.clone()),
+            None => Ok(()),
+        }
+    }
+
     #[inline]
     fn update(&self) -> LuaResult<()> {
         self.update_fn.call(self.module.clone())
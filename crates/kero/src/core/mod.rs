@@ -1,13 +1,18 @@
 //! The framework's core systems.
 
 mod app_handler;
+mod clipboard;
 mod context;
+mod cursor_grab;
 mod cursor_icon;
+mod custom_cursor;
 mod display_mode;
+mod events;
 mod frame_timer;
 mod game;
 mod game_builder;
 mod game_error;
+mod headless;
 mod monitor;
 mod time;
 mod video_mode;
@@ -16,9 +21,13 @@ mod window;
 #[cfg(feature = "lua")]
 mod lua_app;
 
+pub use clipboard::*;
 pub use context::*;
+pub use cursor_grab::*;
 pub use cursor_icon::*;
+pub use custom_cursor::*;
 pub use display_mode::*;
+pub use events::*;
 pub use game::*;
 pub use game_builder::*;
 pub use game_error::*;
@@ -29,3 +38,6 @@ pub use window::*;
 
 #[cfg(feature = "lua")]
 pub(crate) use lua_app::*;
+
+#[cfg(feature = "lua")]
+pub use lua_app::LuaReloadHooks;
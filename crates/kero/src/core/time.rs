@@ -23,7 +23,10 @@ pub(crate) struct TimeState {
     pub target_fps: Cell<Option<f64>>,
     pub max_frame_skip: Cell<u32>,
     pub fps: Cell<u32>,
+    pub max_accumulated_time: Cell<f32>,
     pub delta: Cell<f32>,
+    pub fixed_delta: Cell<f32>,
+    pub alpha: Cell<f32>,
     pub unfixed_delta: Cell<f32>,
     pub since_startup: Cell<f32>,
     pub frame: Cell<u64>,
@@ -36,7 +39,10 @@ impl Default for TimeState {
             target_fps: Cell::new(Some(60.0)),
             max_frame_skip: Cell::new(0),
             fps: Cell::new(60),
+            max_accumulated_time: Cell::new(0.25),
             delta: Cell::new(1.0 / 60.0),
+            fixed_delta: Cell::new(1.0 / 60.0),
+            alpha: Cell::new(0.0),
             unfixed_delta: Cell::new(0.0),
             since_startup: Cell::new(0.0),
             frame: Cell::new(0),
@@ -49,6 +55,8 @@ impl Time {
         Self(Rc::new(TimeState::default()))
     }
 
+    /// How many times per second [`Game::fixed_update`](super::Game::fixed_update) is called.
+    /// If `None`, the monitor's refresh rate is used.
     #[inline]
     pub fn target_fps(&self) -> Option<f64> {
         self.0.target_fps.get()
@@ -69,18 +77,46 @@ impl Time {
         self.0.max_frame_skip.set(max);
     }
 
+    /// The most time, in seconds, that can build up for fixed updates to catch up on. Time past
+    /// this is dropped, so that after a long stall the game slows down instead of trying to catch
+    /// up with more and more fixed updates every frame.
+    #[inline]
+    pub fn max_accumulated_time(&self) -> f32 {
+        self.0.max_accumulated_time.get()
+    }
+
+    #[inline]
+    pub fn set_max_accumulated_time(&self, seconds: f32) {
+        self.0.max_accumulated_time.set(seconds);
+    }
+
     /// FPS the app is running at.
     #[inline]
     pub fn fps(&self) -> u32 {
         self.0.fps.get()
     }
 
-    /// Duration since the last frame, in seconds.
+    /// Duration since the last frame, in seconds. During a fixed update, this is the fixed
+    /// step instead.
     #[inline]
     pub fn delta(&self) -> f32 {
         self.0.delta.get()
     }
 
+    /// Duration of each fixed update, in seconds.
+    #[inline]
+    pub fn fixed_delta(&self) -> f32 {
+        self.0.fixed_delta.get()
+    }
+
+    /// How far, from `0.0` to `1.0`, the current frame is between the last fixed update and the
+    /// next one. Use this to interpolate between the previous and current state of anything that
+    /// moves in [`Game::fixed_update`](super::Game::fixed_update) when rendering.
+    #[inline]
+    pub fn alpha(&self) -> f32 {
+        self.0.alpha.get()
+    }
+
     /// Unfixed duration since last frame, in seconds.
     #[inline]
     pub fn unfixed_delta(&self) -> f32 {
@@ -1,11 +1,12 @@
 use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use std::fmt::{Debug, Formatter};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use winit::event_loop::ActiveEventLoop;
 use winit::window::{Cursor, Fullscreen, Window as WinitWindow};
 
-use crate::math::{Vec2I, Vec2U};
+use crate::math::{RectF, Vec2I, Vec2U};
 
-use super::{CursorIcon, DisplayMode, Monitor, VideoMode};
+use super::{CursorGrab, CursorIcon, CustomCursor, DisplayMode, Monitor, Theme, VideoMode};
 
 /// Handle to the window.
 ///
@@ -13,31 +14,86 @@ use super::{CursorIcon, DisplayMode, Monitor, VideoMode};
 ///
 /// Obtained from [`Context`](super::Context).
 #[derive(Clone)]
-pub struct Window(pub(crate) Arc<WinitWindow>);
+pub struct Window {
+    kind: WindowKind,
+    /// Custom cursor that was set before it was created, waiting to be created and displayed.
+    pending_cursor: Arc<Mutex<Option<CustomCursor>>>,
+}
+
+#[derive(Clone)]
+pub(crate) enum WindowKind {
+    Winit(Arc<WinitWindow>),
+    Headless(Arc<HeadlessWindow>),
+}
+
+/// Stand-in for a window when the game is running headless. It has a fixed size, is never on
+/// any monitor, and ignores any requests to change it.
+pub(crate) struct HeadlessWindow {
+    title: Mutex<String>,
+    size: Vec2U,
+}
 
 impl Debug for Window {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("Window").finish_non_exhaustive()
+        f.debug_struct("Window").finish_non_exhaustive()
     }
 }
 
 impl Window {
+    pub(crate) fn new(window: WinitWindow) -> Self {
+        Self {
+            kind: WindowKind::Winit(Arc::new(window)),
+            pending_cursor: Arc::default(),
+        }
+    }
+
+    pub(crate) fn headless(title: &str, size: Vec2U) -> Self {
+        Self {
+            kind: WindowKind::Headless(Arc::new(HeadlessWindow {
+                title: Mutex::new(title.to_string()),
+                size,
+            })),
+            pending_cursor: Arc::default(),
+        }
+    }
+
+    /// The underlying winit window, or `None` if the game is running headless.
+    #[inline]
+    pub(crate) fn winit(&self) -> Option<&Arc<WinitWindow>> {
+        match &self.kind {
+            WindowKind::Winit(window) => Some(window),
+            WindowKind::Headless(_) => None,
+        }
+    }
+
+    /// If the game is running headless, without an actual window.
+    #[inline]
+    pub fn is_headless(&self) -> bool {
+        matches!(self.kind, WindowKind::Headless(_))
+    }
+
     /// The window title.
     #[inline]
     pub fn title(&self) -> String {
-        self.0.title()
+        match &self.kind {
+            WindowKind::Winit(window) => window.title(),
+            WindowKind::Headless(window) => window.title.lock().unwrap().clone(),
+        }
     }
 
     /// Set the window title.
     #[inline]
     pub fn set_title(&self, title: &str) {
-        self.0.set_title(title);
+        match &self.kind {
+            WindowKind::Winit(window) => window.set_title(title),
+            WindowKind::Headless(window) => *window.title.lock().unwrap() = title.to_string(),
+        }
     }
 
     /// The window scale factor.
     #[inline]
     pub fn scale_factor(&self) -> f32 {
-        self.0.scale_factor() as f32
+        self.winit().map_or(1.0, |w| w.scale_factor() as f32)
     }
 
     /// The inverse window scale factor.
@@ -49,28 +105,34 @@ impl Window {
     /// The monitor the window is on.
     #[inline]
     pub fn monitor(&self) -> Option<Monitor> {
-        self.0.current_monitor().map(Monitor)
+        self.winit()?.current_monitor().map(Monitor)
     }
 
     /// The primary monitor.
     #[inline]
     pub fn primary_monitor(&self) -> Option<Monitor> {
-        self.0.primary_monitor().map(Monitor)
+        self.winit()?.primary_monitor().map(Monitor)
     }
 
     /// All monitors.
     #[inline]
     pub fn monitors(&self) -> impl Iterator<Item = Monitor> {
-        self.0.available_monitors().map(Monitor)
+        self.winit()
+            .into_iter()
+            .flat_map(|w| w.available_monitors())
+            .map(Monitor)
     }
 
     /// Center the window on the selected monitor.
     #[inline]
     pub fn center_on(&self, monitor: &Monitor) {
+        let Some(window) = self.winit() else {
+            return;
+        };
         let mon_pos = monitor.0.position();
         let mon_size = monitor.0.size();
-        let win_size = self.0.outer_size();
-        self.0.set_outer_position(PhysicalPosition::new(
+        let win_size = window.outer_size();
+        window.set_outer_position(PhysicalPosition::new(
             mon_pos.x + ((mon_size.width - win_size.width) / 2) as i32,
             mon_pos.y + ((mon_size.height - win_size.height) / 2) as i32,
         ));
@@ -79,7 +141,7 @@ impl Window {
     /// The window display mode.
     #[inline]
     pub fn display_mode(&self) -> DisplayMode {
-        match self.0.fullscreen() {
+        match self.winit().and_then(|w| w.fullscreen()) {
             Some(Fullscreen::Exclusive(mode)) => DisplayMode::FullscreenExclusive(VideoMode(mode)),
             Some(Fullscreen::Borderless(monitor)) => {
                 DisplayMode::FullscreenBorderless(monitor.map(Monitor))
@@ -91,16 +153,18 @@ impl Window {
     /// Set the window display mode.
     #[inline]
     pub fn set_display_mode(&self, display_mode: DisplayMode) {
+        let Some(window) = self.winit() else {
+            return;
+        };
         match display_mode {
             DisplayMode::FullscreenExclusive(mode) => {
-                self.0.set_fullscreen(Some(Fullscreen::Exclusive(mode.0)));
+                window.set_fullscreen(Some(Fullscreen::Exclusive(mode.0)));
             }
             DisplayMode::FullscreenBorderless(monitor) => {
-                self.0
-                    .set_fullscreen(Some(Fullscreen::Borderless(monitor.map(|m| m.0))));
+                window.set_fullscreen(Some(Fullscreen::Borderless(monitor.map(|m| m.0))));
             }
             DisplayMode::Windowed(monitor) => {
-                self.0.set_fullscreen(None);
+                window.set_fullscreen(None);
                 if let Some(monitor) = monitor {
                     self.center_on(&monitor);
                 }
@@ -111,7 +175,7 @@ impl Window {
     /// If the window is in a fullscreen mode.
     #[inline]
     pub fn is_fullscreen(&self) -> bool {
-        self.0.fullscreen().is_some()
+        self.winit().is_some_and(|w| w.fullscreen().is_some())
     }
 
     /// Set the window to borderless fullscreen mode.
@@ -132,26 +196,33 @@ impl Window {
         self.set_display_mode(DisplayMode::Windowed(monitor));
     }
 
-    /// If the window has focus.
+    /// If the window has focus. A headless window always has focus.
     #[inline]
     pub fn has_focus(&self) -> bool {
-        self.0.has_focus()
+        self.winit().is_none_or(|w| w.has_focus())
+    }
+
+    /// The system's color theme, or none if it's unknown or the game is running headless.
+    #[inline]
+    pub fn theme(&self) -> Option<Theme> {
+        self.winit()?.theme().map(Theme::from)
     }
 
     /// The window position in pixels.
     #[inline]
     pub fn pixel_pos(&self) -> Option<Vec2I> {
-        self.0.inner_position().ok().map(Vec2I::from)
+        self.winit()?.inner_position().ok().map(Vec2I::from)
     }
 
     /// The DPI-independent window position.
     #[inline]
     pub fn pos(&self) -> Option<Vec2I> {
+        let window = self.winit()?;
         Some(
-            self.0
+            window
                 .inner_position()
                 .ok()?
-                .to_logical(self.0.scale_factor())
+                .to_logical(window.scale_factor())
                 .into(),
         )
     }
@@ -159,17 +230,18 @@ impl Window {
     /// The window outer position in pixels.
     #[inline]
     pub fn outer_pixel_pos(&self) -> Option<Vec2I> {
-        self.0.outer_position().ok().map(Vec2I::from)
+        self.winit()?.outer_position().ok().map(Vec2I::from)
     }
 
     /// The DPI-independent window outer position.
     #[inline]
     pub fn outer_pos(&self) -> Option<Vec2I> {
+        let window = self.winit()?;
         Some(
-            self.0
+            window
                 .outer_position()
                 .ok()?
-                .to_logical(self.0.scale_factor())
+                .to_logical(window.scale_factor())
                 .into(),
         )
     }
@@ -178,58 +250,78 @@ impl Window {
     #[inline]
     pub fn set_outer_pos(&self, pos: impl Into<Vec2I>) {
         let pos = pos.into();
-        self.0
-            .set_outer_position(LogicalPosition::new(pos.x, pos.y));
+        if let Some(window) = self.winit() {
+            window.set_outer_position(LogicalPosition::new(pos.x, pos.y));
+        }
     }
 
     /// Set the window outer pixel position.
     #[inline]
     pub fn set_outer_pixel_pos(&self, pos: impl Into<Vec2I>) {
         let pos = pos.into();
-        self.0
-            .set_outer_position(PhysicalPosition::new(pos.x, pos.y));
+        if let Some(window) = self.winit() {
+            window.set_outer_position(PhysicalPosition::new(pos.x, pos.y));
+        }
     }
 
     /// The window pixel size.
     #[inline]
     pub fn pixel_size(&self) -> Vec2U {
-        self.0.inner_size().into()
+        match &self.kind {
+            WindowKind::Winit(window) => window.inner_size().into(),
+            WindowKind::Headless(window) => window.size,
+        }
     }
 
     /// The window DPI-independent size.
     #[inline]
     pub fn size(&self) -> Vec2U {
-        self.0.inner_size().to_logical(self.0.scale_factor()).into()
+        match &self.kind {
+            WindowKind::Winit(window) => {
+                window.inner_size().to_logical(window.scale_factor()).into()
+            }
+            WindowKind::Headless(window) => window.size,
+        }
     }
 
     /// Request a DPI-independent size for the window.
     #[inline]
     pub fn request_size(&self, size: impl Into<Vec2U>) -> bool {
         let size = size.into();
-        self.0
-            .request_inner_size(LogicalSize::new(size.x, size.y))
-            .is_none()
+        self.winit().is_some_and(|w| {
+            w.request_inner_size(LogicalSize::new(size.x, size.y))
+                .is_none()
+        })
     }
 
     /// Request a pixel size for the window.
     #[inline]
     pub fn request_pixel_size(&self, size: impl Into<Vec2U>) -> bool {
         let size = size.into();
-        self.0
-            .request_inner_size(PhysicalSize::new(size.x, size.y))
-            .is_none()
+        self.winit().is_some_and(|w| {
+            w.request_inner_size(PhysicalSize::new(size.x, size.y))
+                .is_none()
+        })
     }
 
     /// Outer pixel size of the window.
     #[inline]
     pub fn outer_pixel_size(&self) -> Vec2U {
-        self.0.outer_size().into()
+        match &self.kind {
+            WindowKind::Winit(window) => window.outer_size().into(),
+            WindowKind::Headless(window) => window.size,
+        }
     }
 
     /// Outer DPI-independent size of the window.
     #[inline]
     pub fn outer_size(&self) -> Vec2U {
-        self.0.outer_size().to_logical(self.0.scale_factor()).into()
+        match &self.kind {
+            WindowKind::Winit(window) => {
+                window.outer_size().to_logical(window.scale_factor()).into()
+            }
+            WindowKind::Headless(window) => window.size,
+        }
     }
 
     /// DPI-independent center of the window.
@@ -247,70 +339,156 @@ impl Window {
     /// If the window can be resized by the user.
     #[inline]
     pub fn resizable(&self) -> bool {
-        self.0.is_resizable()
+        self.winit().is_some_and(|w| w.is_resizable())
     }
 
     /// Set if the window can be resized by the user.
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
-        self.0.set_resizable(resizable);
+        if let Some(window) = self.winit() {
+            window.set_resizable(resizable);
+        }
     }
 
     /// If the window is maximized.
     #[inline]
     pub fn maximized(&self) -> bool {
-        self.0.is_maximized()
+        self.winit().is_some_and(|w| w.is_maximized())
     }
 
     /// Set if the window is maximized.
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
-        self.0.set_maximized(maximized);
+        if let Some(window) = self.winit() {
+            window.set_maximized(maximized);
+        }
     }
 
     /// If the window is minimized.
     #[inline]
     pub fn minimized(&self) -> Option<bool> {
-        self.0.is_minimized()
+        match &self.kind {
+            WindowKind::Winit(window) => window.is_minimized(),
+            WindowKind::Headless(_) => Some(false),
+        }
     }
 
     /// Set if the window is minimized.
     #[inline]
     pub fn set_minimized(&self, minimized: bool) {
-        self.0.set_minimized(minimized);
+        if let Some(window) = self.winit() {
+            window.set_minimized(minimized);
+        }
     }
 
     /// Set the window's minimum inner DPI-independent size.
     #[inline]
     pub fn set_min_inner_size(&self, size: impl Into<Option<Vec2U>>) {
-        self.0
-            .set_min_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        if let Some(window) = self.winit() {
+            window.set_min_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        }
     }
 
     /// Set the window's minimum inner pixel size.
     #[inline]
     pub fn set_min_inner_pixel_size(&self, size: impl Into<Option<Vec2U>>) {
-        self.0
-            .set_min_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        if let Some(window) = self.winit() {
+            window.set_min_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        }
     }
 
     /// Set the window's maximum inner DPI-independent size.
     #[inline]
     pub fn set_max_inner_size(&self, size: impl Into<Option<Vec2U>>) {
-        self.0
-            .set_max_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        if let Some(window) = self.winit() {
+            window.set_max_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        }
     }
 
     /// Set the window's maximum inner pixel size.
     #[inline]
     pub fn set_max_inner_pixel_size(&self, size: impl Into<Option<Vec2U>>) {
-        self.0
-            .set_max_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        if let Some(window) = self.winit() {
+            window.set_max_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        }
     }
 
     /// Set the cursor to display when the mouse is over the window.
     #[inline]
     pub fn set_cursor(&self, icon: CursorIcon) {
-        self.0.set_cursor(Cursor::Icon(icon.into()));
+        self.pending_cursor.lock().unwrap().take();
+        if let Some(window) = self.winit() {
+            window.set_cursor(Cursor::Icon(icon.into()));
+        }
+    }
+
+    /// Set a custom cursor image to display when the mouse is over the window. The first time a
+    /// cursor is used, it may take until the end of the frame to appear.
+    pub fn set_custom_cursor(&self, cursor: &CustomCursor) {
+        let Some(window) = self.winit() else {
+            return;
+        };
+        match cursor.get() {
+            Some(created) => {
+                self.pending_cursor.lock().unwrap().take();
+                window.set_cursor(Cursor::Custom(created.clone()));
+            }
+            None => {
+                *self.pending_cursor.lock().unwrap() = Some(cursor.clone());
+            }
+        }
+    }
+
+    /// Create and display a custom cursor that was set since the last call.
+    pub(crate) fn apply_custom_cursor(&self, event_loop: &ActiveEventLoop) {
+        let Some(window) = self.winit() else {
+            return;
+        };
+        if let Some(cursor) = self.pending_cursor.lock().unwrap().take() {
+            let created = cursor.get_or_create(event_loop);
+            window.set_cursor(Cursor::Custom(created.clone()));
+        }
+    }
+
+    /// Set whether the cursor is visible while it's over the window.
+    #[inline]
+    pub fn set_cursor_visible(&self, visible: bool) {
+        if let Some(window) = self.winit() {
+            window.set_cursor_visible(visible);
+        }
+    }
+
+    /// Set how the cursor is held by the window, returning false if the platform doesn't support
+    /// the grab mode. A common fallback is to use [`CursorGrab::Confined`] when
+    /// [`CursorGrab::Locked`] isn't supported, and vice versa.
+    #[inline]
+    pub fn set_cursor_grab(&self, grab: CursorGrab) -> bool {
+        self.winit()
+            .is_none_or(|window| window.set_cursor_grab(grab.into()).is_ok())
+    }
+
+    /// Set whether the input method editor is allowed, letting players type text in languages such
+    /// as Japanese or Chinese by composing it. While allowed, some keys may be consumed by the
+    /// editor instead of reaching the game, so only allow it while a text field is focused.
+    ///
+    /// Composed text is reported by [`Keyboard::preedit`](crate::input::Keyboard::preedit) and
+    /// [`Keyboard::text_input`](crate::input::Keyboard::text_input).
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        if let Some(window) = self.winit() {
+            window.set_ime_allowed(allowed);
+        }
+    }
+
+    /// Set the area of the window the text being composed is in, in window coordinates. The input
+    /// method editor will try to place its candidate box so that it doesn't cover this area.
+    #[inline]
+    pub fn set_ime_cursor_area(&self, area: RectF) {
+        if let Some(window) = self.winit() {
+            window.set_ime_cursor_area(
+                LogicalPosition::new(area.x, area.y),
+                LogicalSize::new(area.w, area.h),
+            );
+        }
     }
 }
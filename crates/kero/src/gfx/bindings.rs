@@ -38,8 +38,14 @@ impl Bindings {
 
     #[inline]
     pub fn set(&mut self, shader: &Shader, name: &str, value: BindingValue) {
-        let Some((idx, def)) = shader
-            .param_defs()
+        // params removed by reloading the shader are ignored
+        let value_ty = value.param_ty();
+        if shader.was_param_removed(name, value_ty) {
+            return;
+        }
+
+        let param_defs = shader.param_defs();
+        let Some((idx, def)) = param_defs
             .defs
             .iter()
             .enumerate()
@@ -48,7 +54,6 @@ impl Bindings {
             panic!("param {name:?} not found");
         };
 
-        let value_ty = value.param_ty();
         if def.ty != value_ty {
             panic!(
                 "cannot set param {name:?} of type {:?} to a value of type {value_ty:?}",
@@ -0,0 +1,296 @@
+use crate::color::Rgba8;
+use crate::gfx::{Surface, Texture, TextureFormat, TextureReadback};
+use crate::grid::Grid;
+use crate::img::{ApngEncoder, ImageError, ImageRgba8};
+use crate::math::RectU;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use wgpu::{Device, Queue, TextureUsages};
+
+/// The file format a [`Recorder`] writes captured frames to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// Each captured frame is saved as its own numbered PNG file in a directory.
+    PngSequence,
+
+    /// All captured frames are saved into a single animated PNG, played back at `fps`.
+    Apng { fps: u16 },
+}
+
+/// Settings for recording presented frames, started with
+/// [`Graphics::start_recording`](super::Graphics::start_recording).
+///
+/// By default every frame of the window is captured. For pixel art games, use
+/// [`with_source`](Self::with_source) to record the [`Screen`](super::Screen) surface instead,
+/// which captures the game at its native resolution rather than the window's upscaled one.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    path: PathBuf,
+    format: RecordFormat,
+    every: u32,
+    source: Option<Texture>,
+}
+
+impl Recorder {
+    /// Record frames as a sequence of PNG files in the directory `dir`, which will be created if
+    /// it does not exist. Files are named `frame_00000.png`, `frame_00001.png`, and so on.
+    #[inline]
+    pub fn png_sequence(dir: impl Into<PathBuf>) -> Self {
+        Self::new(dir.into(), RecordFormat::PngSequence)
+    }
+
+    /// Record frames into a single animated PNG file that plays back at `fps`.
+    #[inline]
+    pub fn apng(path: impl Into<PathBuf>, fps: u16) -> Self {
+        Self::new(path.into(), RecordFormat::Apng { fps })
+    }
+
+    #[inline]
+    fn new(path: PathBuf, format: RecordFormat) -> Self {
+        Self {
+            path,
+            format,
+            every: 1,
+            source: None,
+        }
+    }
+
+    /// Only capture every `n`th presented frame. Must be greater than zero.
+    #[inline]
+    pub fn with_every(mut self, n: u32) -> Self {
+        self.every = n;
+        self
+    }
+
+    /// Capture the surface instead of the window. The surface must have an
+    /// [`Rgba8`](TextureFormat::Rgba8) format.
+    #[inline]
+    pub fn with_source(mut self, surface: &Surface) -> Self {
+        self.source = Some(surface.texture().clone());
+        self
+    }
+
+    /// Where the frames are being written.
+    #[inline]
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// The format frames are written in.
+    #[inline]
+    pub fn format(&self) -> RecordFormat {
+        self.format
+    }
+
+    /// Check the settings before recording starts, so bad ones are reported up front instead of
+    /// when the first frame is captured.
+    pub(crate) fn validate(&self) -> Result<(), CaptureError> {
+        if self.every == 0 {
+            return Err(CaptureError::ZeroEvery);
+        }
+        if self.format == (RecordFormat::Apng { fps: 0 }) {
+            return Err(CaptureError::ZeroFps);
+        }
+        if let Some(source) = &self.source {
+            check_source_format(source.format())?;
+        }
+        Ok(())
+    }
+}
+
+/// Surfaces can only be captured if they have an [`Rgba8`](TextureFormat::Rgba8) format.
+pub(crate) fn check_source_format(format: TextureFormat) -> Result<(), CaptureError> {
+    match format {
+        TextureFormat::Rgba8 => Ok(()),
+        format => Err(CaptureError::InvalidFormat(format)),
+    }
+}
+
+/// An error capturing a screenshot or recording.
+#[derive(Debug, thiserror::Error)]
+pub enum CaptureError {
+    #[error("{0}")]
+    Image(#[from] ImageError),
+
+    #[error("cannot capture a surface with format {0:?}, it must be Rgba8")]
+    InvalidFormat(TextureFormat),
+
+    #[error("cannot capture every 0th frame, `every` must be greater than zero")]
+    ZeroEvery,
+
+    #[error("cannot record an animated PNG at 0 fps")]
+    ZeroFps,
+}
+
+/// Screenshots and recordings waiting on the GPU.
+#[derive(Default)]
+pub(crate) struct Captures {
+    screenshots: Vec<(Option<Texture>, PathBuf)>,
+    recording: Option<Recording>,
+    pending: VecDeque<PendingCapture>,
+}
+
+struct Recording {
+    recorder: Recorder,
+    presented: u64,
+    captured: usize,
+    apng: Option<ApngEncoder<Rgba8>>,
+}
+
+struct PendingCapture {
+    readback: TextureReadback<Rgba8>,
+    swizzle: bool,
+    dest: CaptureDest,
+}
+
+enum CaptureDest {
+    Screenshot(PathBuf),
+    Frame(usize),
+}
+
+impl Captures {
+    pub fn request_screenshot(&mut self, source: Option<Texture>, path: PathBuf) {
+        self.screenshots.push((source, path));
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn start_recording(&mut self, recorder: Recorder) -> Result<(), CaptureError> {
+        if recorder.format == RecordFormat::PngSequence {
+            std::fs::create_dir_all(&recorder.path).map_err(ImageError::from)?;
+        }
+        self.recording = Some(Recording {
+            recorder,
+            presented: 0,
+            captured: 0,
+            apng: None,
+        });
+        Ok(())
+    }
+
+    /// Wait on all outstanding captures, then save the recording. Returns how many frames it
+    /// captured.
+    pub fn stop_recording(&mut self) -> Result<usize, ImageError> {
+        let result = self.flush(true);
+        let Some(rec) = self.recording.take() else {
+            return result.map(|_| 0);
+        };
+        result?;
+        if let Some(apng) = &rec.apng {
+            apng.save_to_file(&rec.recorder.path)?;
+        }
+        Ok(rec.captured)
+    }
+
+    /// Called after a frame is rendered to `target`, before it is presented. Starts reading back
+    /// any requested captures and saves the ones that have arrived.
+    pub fn frame_rendered(&mut self, device: &Device, queue: &Queue, target: &wgpu::Texture) {
+        // take the requested screenshots
+        for (source, path) in std::mem::take(&mut self.screenshots) {
+            if let Some(capture) = read(device, queue, target, source.as_ref()) {
+                self.pending.push_back(PendingCapture {
+                    readback: capture.0,
+                    swizzle: capture.1,
+                    dest: CaptureDest::Screenshot(path),
+                });
+            }
+        }
+
+        // capture every nth frame of the recording
+        if let Some(rec) = &mut self.recording {
+            if rec.presented % rec.recorder.every as u64 == 0
+                && let Some(capture) = read(device, queue, target, rec.recorder.source.as_ref())
+            {
+                self.pending.push_back(PendingCapture {
+                    readback: capture.0,
+                    swizzle: capture.1,
+                    dest: CaptureDest::Frame(rec.captured),
+                });
+                rec.captured += 1;
+            }
+            rec.presented += 1;
+        }
+
+        if let Err(err) = self.flush(false) {
+            log::error!("failed to save frame capture: {err}");
+        }
+    }
+
+    /// Save the captures that have arrived from the GPU, in the order they were requested. If
+    /// `wait` is true, blocks until all of them have arrived.
+    fn flush(&mut self, wait: bool) -> Result<(), ImageError> {
+        while let Some(pending) = self.pending.pop_front() {
+            let mut img = if wait {
+                pending.readback.wait()
+            } else {
+                match pending.readback.try_take() {
+                    Ok(img) => img,
+                    Err(readback) => {
+                        self.pending.push_front(PendingCapture {
+                            readback,
+                            ..pending
+                        });
+                        break;
+                    }
+                }
+            };
+            if pending.swizzle {
+                for px in img.pixels_mut() {
+                    std::mem::swap(&mut px.r, &mut px.b);
+                }
+            }
+            match pending.dest {
+                CaptureDest::Screenshot(path) => img.save_png_to_file(path)?,
+                CaptureDest::Frame(index) => self.save_frame(index, img)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn save_frame(&mut self, index: usize, img: ImageRgba8) -> Result<(), ImageError> {
+        let Some(rec) = &mut self.recording else {
+            return Ok(());
+        };
+        match rec.recorder.format {
+            RecordFormat::PngSequence => {
+                img.save_png_to_file(rec.recorder.path.join(format!("frame_{index:05}.png")))
+            }
+            RecordFormat::Apng { fps } => rec
+                .apng
+                .get_or_insert_with(|| ApngEncoder::new(img.size(), fps))
+                .push_frame(img),
+        }
+    }
+}
+
+/// Start reading back the source surface, or the render target if there is none. Also returns
+/// whether the red and blue channels need to be swapped.
+fn read(
+    device: &Device,
+    queue: &Queue,
+    target: &wgpu::Texture,
+    source: Option<&Texture>,
+) -> Option<(TextureReadback<Rgba8>, bool)> {
+    if let Some(texture) = source {
+        return Some((
+            texture.read_region_async(RectU::sized(texture.size())),
+            false,
+        ));
+    }
+    let swizzle = match target.format() {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+        format => {
+            log::warn!("cannot capture frames of format {format:?}");
+            return None;
+        }
+    };
+    if !target.usage().contains(TextureUsages::COPY_SRC) {
+        log::warn!("cannot capture frames, the window surface does not support copying");
+        return None;
+    }
+    let rect = RectU::new(0, 0, target.width(), target.height());
+    Some((TextureReadback::new(device, queue, target, rect), swizzle))
+}
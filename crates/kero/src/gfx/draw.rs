@@ -2,20 +2,22 @@ use crate::color::{Rgba8, Rgba64F, ToRgba};
 use crate::core::Window;
 use crate::gfx::buffer_cache::BufferCache;
 use crate::gfx::{
-    BindingValue, BlendMode, ColorMode, DrawCall, FilterMode, Font, IndexBuffer, RenderData,
-    RenderLayer, RenderPass, Sampler, Shader, SubTexture, Surface, Texture, Topology, UniformValue,
-    Vertex, VertexBuffer,
+    BindingValue, BlendMode, ColorMode, DrawCall, FilterMode, Font, Graphics, IndexBuffer,
+    Instance, InstanceBuffer, MaskMode, RenderData, RenderLayer, RenderPass, Sampler, Shader,
+    StencilMode, Stroke, SubTexture, Surface, Texture, Topology, UniformValue, Vertex,
+    VertexBuffer, create_stencil_texture,
 };
 use crate::math::{
-    Affine2F, Angle, CircleF, LineF, Mat2F, Mat3F, Mat4F, Numeric, PolygonF, QuadF, RadiansF,
-    RectF, RectU, TriangleF, Vec2, Vec2F, Vec2U, Vec3F, Vec4F, vec2,
+    Affine2F, Angle, CircleF, LineF, Mat2F, Mat3F, Mat4F, Numeric, PathF, PolygonF, QuadF,
+    RadiansF, RectF, RectU, Shape, TriangleF, Vec2, Vec2F, Vec2U, Vec3F, Vec4F, vec2,
 };
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::mem::{replace, swap};
 use wgpu::{
-    Color, CommandEncoderDescriptor, Device, IndexFormat, LoadOp, Operations, Queue,
-    RenderPassColorAttachment, RenderPassDescriptor, StoreOp, TextureViewDescriptor,
+    Color, CommandEncoderDescriptor, Device, Extent3d, IndexFormat, LoadOp, Operations, Queue,
+    RenderPassColorAttachment, RenderPassDepthStencilAttachment, RenderPassDescriptor, StoreOp,
+    TextureViewDescriptor,
 };
 
 /// Rendering API.
@@ -41,14 +43,22 @@ impl Draw {
         device: Device,
         queue: Queue,
         default_shader: Shader,
+        mask_shader: Shader,
         default_texture: Texture,
     ) -> Self {
+        // drawing without instances draws a single untransformed one
+        let default_instances = InstanceBuffer::new(&device, queue.clone(), 1);
+        default_instances.upload(&[Instance::default()]).unwrap();
+
         Self {
             cache: DrawCache {
                 device,
                 queue,
                 default_shader,
+                mask_shader,
                 default_texture,
+                default_instances,
+                window_stencil: None,
                 samplers: HashMap::new(),
                 buffer_cache: BufferCache::default(),
                 render_layer_vecs: Vec::new(),
@@ -103,23 +113,43 @@ impl Draw {
         self.clip_rect = None;
     }
 
-    pub(crate) fn end_frame(
+    pub(crate) fn end_frame(&mut self, frame: u64, graphics: &Graphics, window: &Window) {
+        // get the window surface
+        let window_surface = graphics
+            .surface()
+            .expect("window surface requires a window")
+            .get_current_texture()
+            .expect("failed to acquire surface texture");
+
+        self.submit_frame(frame, &window_surface.texture);
+
+        // capture the frame before it is presented
+        graphics.frame_rendered(&window_surface.texture);
+
+        let window = window.winit().expect("window surface requires a window");
+        window.pre_present_notify();
+        window_surface.present();
+        window.request_redraw();
+    }
+
+    /// Finish the frame by rendering it to an offscreen surface instead of the window.
+    pub(crate) fn end_frame_offscreen(
         &mut self,
         frame: u64,
-        surface: &wgpu::Surface<'static>,
-        window: &Window,
+        graphics: &Graphics,
+        target: &Surface,
     ) {
+        self.submit_frame(frame, &target.texture().0.texture);
+        graphics.frame_rendered(&target.texture().0.texture);
+    }
+
+    fn submit_frame(&mut self, frame: u64, window_texture: &wgpu::Texture) {
         // if the current render pass has anything in it, finish and submit it
         let mut pass = replace(&mut self.pass, RenderPass::new(None, None, Vec::new()));
         if pass.finish(&mut self.cache) {
             self.data.passes.push(pass);
         }
 
-        // get the window surface
-        let window_surface = surface
-            .get_current_texture()
-            .expect("failed to acquire surface texture");
-
         // create the command encoder
         let mut encoder = self
             .cache
@@ -131,9 +161,7 @@ impl Draw {
             _ = encoder.begin_render_pass(&RenderPassDescriptor {
                 label: None,
                 color_attachments: &[Some(RenderPassColorAttachment {
-                    view: &window_surface
-                        .texture
-                        .create_view(&TextureViewDescriptor::default()),
+                    view: &window_texture.create_view(&TextureViewDescriptor::default()),
                     depth_slice: None,
                     resolve_target: None,
                     ops: Operations {
@@ -148,11 +176,12 @@ impl Draw {
         }
 
         // perform the rest of our render passes
+        let mut window_stencil_cleared = false;
         for pass in &self.data.passes {
             let surface_tex = if let Some(surface) = pass.surface.as_ref() {
                 surface.texture().0.texture.clone()
             } else {
-                window_surface.texture.clone()
+                window_texture.clone()
             };
             let surface_format = surface_tex.format();
             let load = if let Some(clear_color) = pass.clear_color {
@@ -161,6 +190,27 @@ impl Draw {
             } else {
                 LoadOp::Load
             };
+
+            // the window only gets a stencil buffer once a pass drawing to it uses masks, and it
+            // starts each frame with an empty mask
+            let stencil_tex = match pass.surface.as_ref() {
+                Some(surface) => surface.stencil().cloned(),
+                None if pass.uses_stencil => Some(self.cache.window_stencil(surface_tex.size())),
+                None => None,
+            };
+            let stencil_load = if pass.clear_color.is_some()
+                || (pass.surface.is_none() && !window_stencil_cleared)
+            {
+                LoadOp::Clear(0)
+            } else {
+                LoadOp::Load
+            };
+            if pass.surface.is_none() && stencil_tex.is_some() {
+                window_stencil_cleared = true;
+            }
+            let stencil_view = stencil_tex
+                .as_ref()
+                .map(|tex| tex.create_view(&TextureViewDescriptor::default()));
             let mut wgpu_pass = encoder.begin_render_pass(&RenderPassDescriptor {
                 label: None,
                 color_attachments: &[Some(RenderPassColorAttachment {
@@ -172,7 +222,16 @@ impl Draw {
                         store: StoreOp::Store,
                     },
                 })],
-                depth_stencil_attachment: None,
+                depth_stencil_attachment: stencil_view.as_ref().map(|view| {
+                    RenderPassDepthStencilAttachment {
+                        view,
+                        depth_ops: None,
+                        stencil_ops: Some(Operations {
+                            load: stencil_load,
+                            store: StoreOp::Store,
+                        }),
+                    }
+                }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
@@ -181,13 +240,35 @@ impl Draw {
             for layer in pass.layers.iter() {
                 // perform all the draw calls
                 for call in layer.calls.iter() {
+                    // instanced calls with no instances have nothing to draw
+                    let instances = call
+                        .instances
+                        .as_ref()
+                        .unwrap_or(&self.cache.default_instances);
+                    if instances.count() == 0 {
+                        continue;
+                    }
+
+                    // masks are ignored on surfaces without a stencil buffer, and calls that only
+                    // draw into the mask aren't drawn at all, or they would paint the surface
+                    if stencil_view.is_none()
+                        && matches!(call.stencil, StencilMode::Write | StencilMode::Clear)
+                    {
+                        continue;
+                    }
+                    let stencil = stencil_view.is_some().then_some(call.stencil);
+
                     // set the render pipeline
                     wgpu_pass.set_pipeline(&call.shader.request_pipeline(
                         &self.cache.device,
                         call.topology,
                         surface_format,
                         call.blend_mode,
+                        stencil,
                     ));
+                    if let Some(stencil) = stencil {
+                        wgpu_pass.set_stencil_reference(stencil.reference());
+                    }
 
                     if let Some(RectU { x, y, w, h }) = call.clip_rect {
                         wgpu_pass.set_scissor_rect(x, y, w, h);
@@ -222,17 +303,24 @@ impl Draw {
                             .slice(..call.indices.size_in_bytes().to_u64()),
                         IndexFormat::Uint32,
                     );
+                    wgpu_pass.set_vertex_buffer(
+                        1,
+                        instances
+                            .buffer()
+                            .slice(..instances.size_in_bytes().to_u64()),
+                    );
 
                     // perform the draw call
-                    wgpu_pass.draw_indexed(0..call.indices.count().to_u32(), 0, 0..1);
+                    wgpu_pass.draw_indexed(
+                        0..call.indices.count().to_u32(),
+                        0,
+                        0..instances.count().to_u32(),
+                    );
                 }
             }
         }
 
         self.cache.queue.submit([encoder.finish()]);
-        window.0.pre_present_notify();
-        window_surface.present();
-        window.0.request_redraw();
     }
 
     /// Set the target surface and optionally clear it with a single color. If `None` is passed
@@ -447,6 +535,73 @@ impl Draw {
             .set_scissor_rect(self.clip_rect, &mut self.cache);
     }
 
+    /// Start drawing a mask. Until [`end_mask`](Self::end_mask) is called, anything drawn is
+    /// added to the mask instead of appearing on the surface.
+    ///
+    /// With the default shader, fully transparent pixels are left out of the mask, so sprites
+    /// mask by their shape. Custom shaders can `discard` pixels to leave them out.
+    ///
+    /// Masks require a stencil buffer. The window always has one, but surfaces must be created
+    /// with [`Graphics::create_surface_with_stencil`]. Masks drawn to other surfaces are ignored.
+    ///
+    /// ```ignore
+    /// // draw the minimap inside a circle
+    /// draw.clear_mask();
+    /// draw.begin_mask();
+    /// draw.circle(minimap_circle, Rgba8::WHITE, None);
+    /// draw.end_mask(MaskMode::Inside);
+    /// draw.texture_at(&minimap, minimap_pos);
+    /// draw.set_mask_mode(None);
+    /// ```
+    #[inline]
+    pub fn begin_mask(&mut self) {
+        self.pass.uses_stencil = true;
+        self.pass
+            .layer(self.layer)
+            .set_stencil(StencilMode::Write, &mut self.cache);
+    }
+
+    /// Stop drawing the mask, so that anything drawn afterward is only drawn where the mode
+    /// allows.
+    #[inline]
+    pub fn end_mask(&mut self, mode: MaskMode) {
+        self.set_mask_mode(mode);
+    }
+
+    /// The current mask mode, or `None` if drawing isn't masked.
+    #[inline]
+    pub fn mask_mode(&mut self) -> Option<MaskMode> {
+        match self.pass.layer(self.layer).stencil {
+            StencilMode::Test(mode) => Some(mode),
+            _ => None,
+        }
+    }
+
+    /// Set where drawing is allowed by the mask, or pass `None` to stop masking. Like the blend
+    /// mode, this is reset whenever the [surface](Self::set_surface) changes, but the surface
+    /// keeps its mask unless it is cleared.
+    #[inline]
+    pub fn set_mask_mode(&mut self, mode: impl Into<Option<MaskMode>>) {
+        let stencil = match mode.into() {
+            Some(mode) => {
+                self.pass.uses_stencil = true;
+                StencilMode::Test(mode)
+            }
+            None => StencilMode::Ignore,
+        };
+        self.pass
+            .layer(self.layer)
+            .set_stencil(stencil, &mut self.cache);
+    }
+
+    /// Erase the whole mask, ignoring the clipping rectangle. Clearing the surface with
+    /// [`set_surface`](Self::set_surface) also clears its mask.
+    #[inline]
+    pub fn clear_mask(&mut self) {
+        self.pass.uses_stencil = true;
+        self.pass.layer(self.layer).clear_stencil(&mut self.cache);
+    }
+
     /// Size of the transform stack.
     #[inline]
     pub fn transform_count(&self) -> usize {
@@ -741,6 +896,17 @@ impl Draw {
         self.lines(tri.into().0, color, true);
     }
 
+    /// Draw a triangle outline with the thickness.
+    #[inline]
+    pub fn triangle_outline_thick(
+        &mut self,
+        tri: impl Into<TriangleF>,
+        color: Rgba8,
+        thickness: f32,
+    ) {
+        self.stroke_lines(tri.into().0, &Stroke::new(thickness), color, true);
+    }
+
     /// Draw a filled quad.
     #[inline]
     pub fn quad(&mut self, quad: impl Into<QuadF>, color: Rgba8) {
@@ -761,6 +927,12 @@ impl Draw {
         self.lines(quad.into().0, color, true);
     }
 
+    /// Draw a quad outline with the thickness.
+    #[inline]
+    pub fn quad_outline_thick(&mut self, quad: impl Into<QuadF>, color: Rgba8, thickness: f32) {
+        self.stroke_lines(quad.into().0, &Stroke::new(thickness), color, true);
+    }
+
     /// Draw a filled rectangle.
     #[inline]
     pub fn rect(&mut self, rect: impl Into<RectF>, color: Rgba8) {
@@ -773,9 +945,20 @@ impl Draw {
         self.quad_outline(rect.into(), color);
     }
 
-    /// Draw a filled polygon.
+    /// Draw a rectangle outline with the thickness. The outline is centered on the rectangle's
+    /// edges, so half of it falls outside the rectangle.
+    #[inline]
+    pub fn rect_outline_thick(&mut self, rect: impl Into<RectF>, color: Rgba8, thickness: f32) {
+        self.quad_outline_thick(rect.into(), color, thickness);
+    }
+
+    /// Draw a filled polygon. If the polygon is concave, it is
+    /// [triangulated](crate::math::Polygon::triangulate) first.
     #[inline]
     pub fn polygon(&mut self, poly: &PolygonF, color: Rgba8) {
+        if !poly.is_convex() {
+            return self.polygon_with_holes(poly, &[], color);
+        }
         let (verts, inds, mat) = self.tri_mode();
         let start = verts.len() as u32;
         verts.extend(
@@ -789,12 +972,39 @@ impl Draw {
         }
     }
 
+    /// Draw a filled polygon with the holes cut out of it. The holes should be inside the
+    /// polygon and not overlap each other.
+    pub fn polygon_with_holes(&mut self, poly: &PolygonF, holes: &[PolygonF], color: Rgba8) {
+        let mut tris = Vec::new();
+        poly.triangulate_indices(holes, &mut tris);
+        let (verts, inds, mat) = self.tri_mode();
+        let start = verts.len() as u32;
+        verts.extend(
+            poly.points()
+                .iter()
+                .chain(holes.iter().flat_map(PolygonF::points))
+                .map(|p| Vertex::veto(mat.transform_pos2(*p), color)),
+        );
+        inds.extend(tris.into_iter().map(|i| start + i as u32));
+    }
+
     /// Draw a polygon outline.
     #[inline]
     pub fn polygon_outline(&mut self, poly: &PolygonF, color: Rgba8) {
         self.lines(poly.points().iter().copied(), color, true);
     }
 
+    /// Draw a polygon outline with the thickness.
+    #[inline]
+    pub fn polygon_outline_thick(&mut self, poly: &PolygonF, color: Rgba8, thickness: f32) {
+        self.stroke_lines(
+            poly.points().iter().copied(),
+            &Stroke::new(thickness),
+            color,
+            true,
+        );
+    }
+
     #[inline]
     fn fan(&mut self, points: impl IntoIterator<Item = Vec2F>, color: Rgba8, loops: bool) {
         let (verts, inds, mat) = self.tri_mode();
@@ -858,6 +1068,87 @@ impl Draw {
         );
     }
 
+    /// Draw a circle outline with the thickness, using the provided number of segments. If
+    /// `None`, then [`suggest_seg_count_f`](crate::math::Circle::suggest_seg_count) will be used.
+    #[inline]
+    pub fn circle_outline_thick(
+        &mut self,
+        circ: impl Into<CircleF>,
+        color: Rgba8,
+        thickness: f32,
+        seg_count: Option<u32>,
+    ) {
+        let circ = circ.into();
+        let seg_count = seg_count
+            .map(u32::to_f32)
+            .unwrap_or_else(|| circ.suggest_seg_count_f(|p| self.matrix.transform_pos2(p)));
+        self.stroke_lines(
+            circ.iter_hull_points_n(seg_count, RadiansF::ZERO),
+            &Stroke::new(thickness),
+            color,
+            true,
+        );
+    }
+
+    /// Draw a stroke along the series of points, optionally looping back to the start. Unlike
+    /// [`lines`](Self::lines), the stroke is made of triangles, so it can be any width and
+    /// have [joins](crate::gfx::LineJoin), [caps](crate::gfx::LineCap) and dashes.
+    #[inline]
+    pub fn stroke_lines(
+        &mut self,
+        points: impl IntoIterator<Item = Vec2F>,
+        stroke: &Stroke,
+        color: Rgba8,
+        loops: bool,
+    ) {
+        let (verts, inds, mat) = self.tri_mode();
+        stroke.tessellate(points, loops, verts, inds, mat, color);
+    }
+
+    /// Draw a stroke along each subpath of the path. Curves are flattened finely enough that
+    /// they stay smooth at the current transform. To stroke a series of points, use
+    /// [`stroke_lines`](Self::stroke_lines).
+    pub fn stroke_path(&mut self, path: &PathF, stroke: &Stroke, color: Rgba8) {
+        let Some(tolerance) = self.path_tolerance() else {
+            return;
+        };
+        let (verts, inds, mat) = self.tri_mode();
+        path.flatten_with(tolerance, |points, closed| {
+            stroke.tessellate(points.iter().copied(), closed, verts, inds, mat, color);
+        });
+    }
+
+    /// Fill the area inside the path. Subpaths inside of other subpaths cut holes out of them,
+    /// as described in [`triangulate_into`](crate::math::Path::triangulate_into).
+    pub fn fill_path(&mut self, path: &PathF, color: Rgba8) {
+        let Some(tolerance) = self.path_tolerance() else {
+            return;
+        };
+        let mut points = Vec::new();
+        let mut tris = Vec::new();
+        path.triangulate_into(tolerance, &mut points, &mut tris);
+        let (verts, inds, mat) = self.tri_mode();
+        let start = verts.len() as u32;
+        verts.extend(
+            points
+                .iter()
+                .map(|p| Vertex::veto(mat.transform_pos2(*p), color)),
+        );
+        inds.extend(tris.into_iter().map(|i| start + i as u32));
+    }
+
+    /// Tolerance to flatten paths with, so their curves stray from the true curve by at most a
+    /// quarter of a pixel, or none if the transform scales paths down to nothing.
+    #[inline]
+    fn path_tolerance(&self) -> Option<f32> {
+        let scale = f32::max(
+            self.matrix.transform_vec2(Vec2F::X_AXIS).len(),
+            self.matrix.transform_vec2(Vec2F::Y_AXIS).len(),
+        );
+        let tolerance = 0.25 / scale;
+        (tolerance.is_finite() && tolerance > 0.0).then_some(tolerance)
+    }
+
     /// Draw a subtexture.
     #[inline]
     pub fn subtextured_quad_flipped(
@@ -1034,6 +1325,52 @@ impl Draw {
             topology,
             vertices.clone(),
             indices.clone(),
+            None,
+            &mut self.cache,
+        );
+    }
+
+    /// Draw the provided vertex/index buffers once for every instance in the instance buffer.
+    /// Each instance transforms the vertices, maps their texture coordinates into its texture
+    /// rect and tints them, all on the GPU. This is much faster than drawing many copies of the
+    /// same geometry one at a time.
+    ///
+    /// Like [`buffers`](Self::buffers), the vertices are not transformed by the draw transform.
+    /// The instances are applied by `vert_default`, so custom shaders should call it to support
+    /// them.
+    ///
+    /// ```ignore
+    /// // a unit quad, stretched to the size of each sprite by its transform
+    /// let quad = gfx.create_vertex_buffer(&[
+    ///     Vertex::simple(vec2(0.0, 0.0), vec2(0.0, 0.0)),
+    ///     Vertex::simple(vec2(1.0, 0.0), vec2(1.0, 0.0)),
+    ///     Vertex::simple(vec2(1.0, 1.0), vec2(1.0, 1.0)),
+    ///     Vertex::simple(vec2(0.0, 1.0), vec2(0.0, 1.0)),
+    /// ]);
+    /// let inds = gfx.create_index_buffer(&[0, 1, 2, 0, 2, 3]);
+    /// let bullets = gfx.create_instance_buffer(&vec![Instance::default(); 50_000]);
+    ///
+    /// // every frame
+    /// bullets.upload(&self.bullet_instances)?;
+    /// draw.instanced(Some(texture), Topology::Triangles, &quad, &inds, &bullets);
+    /// ```
+    #[inline]
+    pub fn instanced(
+        &mut self,
+        texture: Option<Texture>,
+        topology: Topology,
+        vertices: &VertexBuffer,
+        indices: &IndexBuffer,
+        instances: &InstanceBuffer,
+    ) {
+        let layer = self.pass.layer(self.layer);
+        let texture = texture.unwrap_or_else(|| self.cache.default_texture.clone());
+        layer.submit_buffers(
+            texture,
+            topology,
+            vertices.clone(),
+            indices.clone(),
+            Some(instances.clone()),
             &mut self.cache,
         );
     }
@@ -1043,7 +1380,10 @@ pub(crate) struct DrawCache {
     pub device: Device,
     pub queue: Queue,
     pub default_shader: Shader,
+    pub mask_shader: Shader,
     pub default_texture: Texture,
+    pub default_instances: InstanceBuffer,
+    pub window_stencil: Option<wgpu::Texture>,
     pub samplers: HashMap<Sampler, wgpu::Sampler>,
     pub buffer_cache: BufferCache,
     pub render_layer_vecs: Vec<Vec<RenderLayer>>,
@@ -1053,6 +1393,20 @@ pub(crate) struct DrawCache {
     pub window_size: Vec2U,
 }
 
+impl DrawCache {
+    /// The window's stencil buffer, recreated if the window has been resized.
+    fn window_stencil(&mut self, size: Extent3d) -> wgpu::Texture {
+        if let Some(tex) = &self.window_stencil
+            && tex.size() == size
+        {
+            return tex.clone();
+        }
+        let tex = create_stencil_texture(&self.device, size.width, size.height);
+        self.window_stencil = Some(tex.clone());
+        tex
+    }
+}
+
 /// A drawing error.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum DrawError {
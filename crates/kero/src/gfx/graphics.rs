@@ -1,7 +1,9 @@
 use crate::color::{FromRgb, Rgba8, Rgba16, Rgba32F};
 use crate::core::{GameBuilder, Window};
 use crate::gfx::{
-    IndexBuffer, Shader, Surface, Texture, TextureFormat, TexturePixel, Vertex, VertexBuffer,
+    CaptureError, Captures, IndexBuffer, Instance, InstanceBuffer, Recorder, Shader, ShaderError,
+    ShaderWatcher, Surface, Texture, TextureFormat, TexturePixel, Vertex, VertexBuffer,
+    check_source_format,
 };
 use crate::grid::Grid;
 use crate::img::{DynImage, Image, ImageError, ImageRgba8};
@@ -9,11 +11,11 @@ use crate::math::Vec2U;
 use dpi::PhysicalSize;
 use pollster::FutureExt;
 use std::fmt::{Debug, Formatter};
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use wgpu::{
     Adapter, BackendOptions, Backends, Device, DeviceDescriptor, ExperimentalFeatures, Features,
-    Instance, InstanceDescriptor, InstanceFlags, Limits, MemoryBudgetThresholds, MemoryHints,
+    InstanceDescriptor, InstanceFlags, Limits, MemoryBudgetThresholds, MemoryHints,
     PowerPreference, PresentMode, Queue, RequestAdapterOptions, SurfaceCapabilities,
     SurfaceConfiguration, TextureUsages, Trace,
 };
@@ -33,15 +35,17 @@ impl Debug for Graphics {
 
 struct GraphicsInner {
     window: Window,
-    _instance: Instance,
-    surface_caps: SurfaceCapabilities,
-    pub(crate) surface: wgpu::Surface<'static>,
+    _instance: wgpu::Instance,
+    window_surface: Option<WindowSurface>,
     _adapter: Adapter,
     device: Device,
     queue: Queue,
     limits: Limits,
     default_texture: Texture,
     default_shader: Shader,
+    mask_shader: Shader,
+    captures: Mutex<Captures>,
+    shader_watcher: Mutex<ShaderWatcher>,
 
     #[cfg(feature = "lua")]
     default_texture_userdata: mlua::AnyUserData,
@@ -53,9 +57,15 @@ struct GraphicsInner {
     lua: mlua::WeakLua,
 }
 
+struct WindowSurface {
+    surface: wgpu::Surface<'static>,
+    caps: SurfaceCapabilities,
+}
+
 fn config(size: PhysicalSize<u32>, caps: &SurfaceCapabilities) -> SurfaceConfiguration {
     SurfaceConfiguration {
-        usage: TextureUsages::RENDER_ATTACHMENT,
+        // allow copying from the window so frames can be captured
+        usage: TextureUsages::RENDER_ATTACHMENT | (caps.usages & TextureUsages::COPY_SRC),
         format: wgpu::TextureFormat::Bgra8Unorm,
         width: size.width,
         height: size.height,
@@ -67,6 +77,8 @@ fn config(size: PhysicalSize<u32>, caps: &SurfaceCapabilities) -> SurfaceConfigu
 }
 
 impl Graphics {
+    /// Create the graphics state. If the window is headless, no window surface is created and
+    /// a software adapter is preferred, so rendering works on machines without a GPU.
     #[allow(unused_variables)]
     pub(crate) fn new(window: Window, opts: &GameBuilder) -> Self {
         // create the instance
@@ -78,7 +90,7 @@ impl Graphics {
             } else {
                 Backends::VULKAN
             };
-            Instance::new(&InstanceDescriptor {
+            wgpu::Instance::new(&InstanceDescriptor {
                 backends,
                 flags: InstanceFlags::DEBUG | InstanceFlags::VALIDATION,
                 memory_budget_thresholds: MemoryBudgetThresholds::default(),
@@ -89,19 +101,27 @@ impl Graphics {
         };
 
         // create the window surface
-        let surface = instance
-            .create_surface(window.0.clone())
-            .expect("failed to create window surface");
-
-        // request an adapter to a graphics device
-        let adapter = instance
-            .request_adapter(&RequestAdapterOptions {
-                power_preference: PowerPreference::HighPerformance,
-                force_fallback_adapter: false,
-                compatible_surface: Some(&surface),
-            })
-            .block_on()
-            .expect("failed to find a suitable graphics device");
+        let surface = window.winit().map(|window| {
+            instance
+                .create_surface(window.clone())
+                .expect("failed to create window surface")
+        });
+
+        // request an adapter to a graphics device, preferring a software one when headless
+        let request_adapter = |force_fallback_adapter: bool| {
+            instance
+                .request_adapter(&RequestAdapterOptions {
+                    power_preference: PowerPreference::HighPerformance,
+                    force_fallback_adapter,
+                    compatible_surface: surface.as_ref(),
+                })
+                .block_on()
+        };
+        let adapter = match surface {
+            Some(_) => request_adapter(false),
+            None => request_adapter(true).or_else(|_| request_adapter(false)),
+        }
+        .expect("failed to find a suitable graphics device");
 
         // request a graphics device and queue for it
         let (device, queue) = adapter
@@ -121,11 +141,18 @@ impl Graphics {
         let limits = device.limits();
 
         // create the surface configuration and configure the surface
-        let surface_caps = surface.get_capabilities(&adapter);
-        surface.configure(&device, &config(window.0.inner_size(), &surface_caps));
+        let window_surface = surface.map(|surface| {
+            let caps = surface.get_capabilities(&adapter);
+            let size = window.winit().unwrap().inner_size();
+            surface.configure(&device, &config(size, &caps));
+            WindowSurface { surface, caps }
+        });
 
         // create the default shader
-        let default_shader = Shader::new(&device, include_str!("shader_default.wgsl"));
+        let default_shader = Shader::new(&device, include_str!("shader_default.wgsl"))
+            .expect("failed to compile default shader");
+        let mask_shader = Shader::new(&device, include_str!("shader_mask.wgsl"))
+            .expect("failed to compile mask shader");
 
         // create the default texture
         let default_texture = Texture::new(
@@ -140,8 +167,7 @@ impl Graphics {
         Self(Arc::new(GraphicsInner {
             window,
             _instance: instance,
-            surface_caps,
-            surface,
+            window_surface,
             _adapter: adapter,
             device,
             queue,
@@ -157,7 +183,10 @@ impl Graphics {
             lua: opts.lua.weak(),
 
             default_shader,
+            mask_shader,
             default_texture,
+            captures: Mutex::default(),
+            shader_watcher: Mutex::default(),
         }))
     }
 
@@ -172,9 +201,10 @@ impl Graphics {
         &self.0.window
     }
 
+    /// The window's surface, or `None` if running headless.
     #[inline]
-    pub(crate) fn surface(&self) -> &wgpu::Surface<'static> {
-        &self.0.surface
+    pub(crate) fn surface(&self) -> Option<&wgpu::Surface<'static>> {
+        self.0.window_surface.as_ref().map(|ws| &ws.surface)
     }
 
     #[inline]
@@ -210,6 +240,13 @@ impl Graphics {
         &self.0.default_shader
     }
 
+    /// Shader that replaces the default shader while drawing [masks](super::Draw::begin_mask),
+    /// which leaves fully transparent pixels out of the mask.
+    #[inline]
+    pub(crate) fn mask_shader(&self) -> &Shader {
+        &self.0.mask_shader
+    }
+
     /// Texture that is used by default (a single `Rgba8::FUCHSIA` pixel).
     #[inline]
     pub fn default_texture(&self) -> &Texture {
@@ -228,32 +265,122 @@ impl Graphics {
         &self.0.default_texture_userdata
     }
 
+    /// Save a screenshot of the window to a PNG file once the current frame has been rendered.
+    ///
+    /// The file is written once the frame arrives from the GPU, which may be a few frames later.
+    #[inline]
+    pub fn capture_screenshot(&self, path: impl Into<PathBuf>) {
+        self.0
+            .captures
+            .lock()
+            .unwrap()
+            .request_screenshot(None, path.into());
+    }
+
+    /// Save a screenshot of the surface to a PNG file once the current frame has been rendered.
+    /// The surface must have an [`Rgba8`](TextureFormat::Rgba8) format.
+    ///
+    /// This is useful for capturing a [`Screen`](super::Screen) at its native resolution.
+    #[inline]
+    pub fn capture_surface_screenshot(
+        &self,
+        surface: &Surface,
+        path: impl Into<PathBuf>,
+    ) -> Result<(), CaptureError> {
+        check_source_format(surface.format())?;
+        self.0
+            .captures
+            .lock()
+            .unwrap()
+            .request_screenshot(Some(surface.texture().clone()), path.into());
+        Ok(())
+    }
+
+    /// Start recording presented frames. If a recording is already in progress, it is stopped
+    /// and saved first. Fails without starting if the recorder's settings are invalid.
+    pub fn start_recording(&self, recorder: Recorder) -> Result<(), CaptureError> {
+        recorder.validate()?;
+        let mut captures = self.0.captures.lock().unwrap();
+        captures.stop_recording()?;
+        captures.start_recording(recorder)
+    }
+
+    /// Stop recording and save the recording, returning how many frames were captured. Returns
+    /// zero if nothing was being recorded.
+    #[inline]
+    pub fn stop_recording(&self) -> Result<usize, ImageError> {
+        self.0.captures.lock().unwrap().stop_recording()
+    }
+
+    /// If frames are currently being recorded.
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        self.0.captures.lock().unwrap().is_recording()
+    }
+
+    /// Called once a frame has been rendered to `target`, to capture it if requested.
+    #[inline]
+    pub(crate) fn frame_rendered(&self, target: &wgpu::Texture) {
+        self.0
+            .captures
+            .lock()
+            .unwrap()
+            .frame_rendered(&self.0.device, &self.0.queue, target);
+    }
+
     /// Create a new shader from the provided [WGSL](https://www.w3.org/TR/WGSL/) source code.
     ///
     /// See [`default_shader`](Self::default_shader) for a starting point.
-    pub fn create_shader(&self, source: &str) -> Shader {
+    pub fn create_shader(&self, source: &str) -> Result<Shader, ShaderError> {
         Shader::new(&self.0.device, source)
     }
 
     /// Create a new shader from the provided [WGSL](https://www.w3.org/TR/WGSL/) source file.
     ///
     /// See [`default_shader`](Self::default_shader) for a starting point.
-    pub fn load_shader(&self, path: impl AsRef<Path>) -> Result<Shader, std::io::Error> {
+    pub fn load_shader(&self, path: impl AsRef<Path>) -> Result<Shader, ShaderError> {
         let source = std::fs::read_to_string(path)?;
-        Ok(self.create_shader(&source))
+        self.create_shader(&source)
+    }
+
+    /// Load a shader from the provided [WGSL](https://www.w3.org/TR/WGSL/) source file, and
+    /// reload it whenever the file changes on disk.
+    ///
+    /// If a reload fails to compile, the shader keeps using its last working version and the
+    /// error is logged and available from [`Shader::reload_error`]. Parameters that a reload
+    /// removes can still be set without panicking, they are just ignored.
+    pub fn watch_shader(&self, path: impl Into<PathBuf>) -> Result<Shader, ShaderError> {
+        let shader = Shader::new_watched(&self.0.device, path.into())?;
+        self.0.shader_watcher.lock().unwrap().add(&shader);
+        Ok(shader)
+    }
+
+    /// Reload any watched shaders whose files have changed.
+    #[inline]
+    pub(crate) fn update_shaders(&self) {
+        self.0.shader_watcher.lock().unwrap().update(&self.0.device);
     }
 
     /// Create a new surface that can be rendered to.
     pub fn create_surface(&self, size: impl Into<Vec2U>, format: TextureFormat) -> Surface {
+        self.create_surface_ext(size.into(), format, false)
+    }
+
+    /// Create a new surface that can be rendered to, with a stencil buffer so that
+    /// [masks](super::Draw::begin_mask) can be drawn to it.
+    pub fn create_surface_with_stencil(
+        &self,
+        size: impl Into<Vec2U>,
+        format: TextureFormat,
+    ) -> Surface {
+        self.create_surface_ext(size.into(), format, true)
+    }
+
+    fn create_surface_ext(&self, size: Vec2U, format: TextureFormat, stencil: bool) -> Surface {
         Surface::new(
             self,
-            Texture::new(
-                &self.0.device,
-                self.0.queue.clone(),
-                size.into(),
-                format,
-                true,
-            ),
+            Texture::new(&self.0.device, self.0.queue.clone(), size, format, true),
+            stencil,
         )
     }
 
@@ -363,11 +490,21 @@ impl Graphics {
         buffer
     }
 
+    /// Create a new instance buffer from the provided instances.
+    pub fn create_instance_buffer(&self, instances: &[Instance]) -> InstanceBuffer {
+        let buffer = InstanceBuffer::new(&self.0.device, self.0.queue.clone(), instances.len());
+        buffer.upload(instances).unwrap();
+        buffer
+    }
+
     pub(crate) fn resized(&self, new_size: PhysicalSize<u32>) {
         // only configure surface if the window has an actual size
-        if new_size.width > 0 && new_size.height > 0 {
-            let config = config(new_size, &self.0.surface_caps);
-            self.0.surface.configure(&self.0.device, &config);
+        if let Some(ws) = &self.0.window_surface
+            && new_size.width > 0
+            && new_size.height > 0
+        {
+            ws.surface
+                .configure(&self.0.device, &config(new_size, &ws.caps));
         }
     }
 }
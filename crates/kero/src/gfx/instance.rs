@@ -0,0 +1,86 @@
+use crate::color::Rgba8;
+use crate::math::{Affine2F, RectF, rect};
+use bytemuck::{Pod, Zeroable};
+use wgpu::{BufferAddress, VertexAttribute, VertexBufferLayout, VertexFormat, VertexStepMode};
+
+/// Per-instance data passed to the shader when drawing with
+/// [`Draw::instanced`](super::Draw::instanced).
+///
+/// Each instance draws the whole vertex buffer again, with the vertex positions transformed by
+/// the instance's transform, texture coordinates mapped into its texture rect, and colors
+/// multiplied by its color. This is applied by `vert_default` in the shader.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Zeroable, Pod)]
+pub struct Instance {
+    /// The transform applied to the vertex positions.
+    pub transform: Affine2F,
+
+    /// The area of the texture that the vertices' `0..1` texture coordinates map to.
+    pub tex: RectF,
+
+    /// The color multiplied with the vertex colors.
+    pub col: Rgba8,
+}
+
+impl Default for Instance {
+    #[inline]
+    fn default() -> Self {
+        Self::simple(Affine2F::IDENTITY)
+    }
+}
+
+impl Instance {
+    pub(crate) const LAYOUT: VertexBufferLayout<'static> = VertexBufferLayout {
+        array_stride: size_of::<Self>() as BufferAddress,
+        step_mode: VertexStepMode::Instance,
+        attributes: &[
+            VertexAttribute {
+                format: VertexFormat::Float32x2,
+                offset: 0,
+                shader_location: 4,
+            },
+            VertexAttribute {
+                format: VertexFormat::Float32x2,
+                offset: 8,
+                shader_location: 5,
+            },
+            VertexAttribute {
+                format: VertexFormat::Float32x2,
+                offset: 16,
+                shader_location: 6,
+            },
+            VertexAttribute {
+                format: VertexFormat::Float32x4,
+                offset: 24,
+                shader_location: 7,
+            },
+            VertexAttribute {
+                format: VertexFormat::Unorm8x4,
+                offset: 40,
+                shader_location: 8,
+            },
+        ],
+    };
+
+    /// Create a new instance.
+    #[inline]
+    pub const fn new(transform: Affine2F, tex: RectF, col: Rgba8) -> Self {
+        Self {
+            transform,
+            tex,
+            col,
+        }
+    }
+
+    /// Create an instance that uses the whole texture and leaves the vertex colors as they are.
+    #[inline]
+    pub const fn simple(transform: Affine2F) -> Self {
+        Self::new(transform, rect(0.0, 0.0, 1.0, 1.0), Rgba8::WHITE)
+    }
+
+    /// Create an instance that uses the whole texture and tints the vertex colors.
+    #[inline]
+    pub const fn tinted(transform: Affine2F, col: Rgba8) -> Self {
+        Self::new(transform, rect(0.0, 0.0, 1.0, 1.0), col)
+    }
+}
@@ -0,0 +1,115 @@
+use crate::gfx::Instance;
+use bytemuck::cast_slice;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use wgpu::{BufferAddress, BufferDescriptor, BufferUsages, Device, Queue};
+
+#[cfg(feature = "lua")]
+pub type InstanceBufferObj = fey_lua::UserDataOf<InstanceBuffer>;
+#[cfg(feature = "lua")]
+pub type InstanceBufferRef = mlua::UserDataRef<InstanceBuffer>;
+
+/// Handle to an instance buffer.
+///
+/// This handle can be cloned and passed around freely to give objects access to the buffer.
+///
+/// Instance buffers are created from [`Graphics`](super::Graphics).
+#[derive(Clone)]
+pub struct InstanceBuffer(Arc<Inner>);
+
+impl Debug for InstanceBuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("InstanceBuffer").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for InstanceBuffer {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for InstanceBuffer {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Arc::as_ptr(&self.0).partial_cmp(&Arc::as_ptr(&other.0))
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    buffer: wgpu::Buffer,
+    queue: Queue,
+    count: AtomicUsize,
+}
+
+impl InstanceBuffer {
+    pub(crate) fn new(device: &Device, queue: Queue, capacity: usize) -> Self {
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: (capacity * size_of::<Instance>()) as BufferAddress,
+            usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self(Arc::new(Inner {
+            buffer,
+            queue,
+            count: AtomicUsize::new(0),
+        }))
+    }
+
+    /// Replace the buffer's instances. Buffers will not grow in size so this must be less than or
+    /// equal to the buffer's [`capacity`](Self::capacity).
+    #[inline]
+    pub fn upload(&self, instances: &[Instance]) -> Result<(), InstanceBufferUploadError> {
+        if instances.len() > self.capacity() {
+            return Err(InstanceBufferUploadError::InsufficientSpace {
+                expected: self.capacity(),
+                got: instances.len(),
+            });
+        }
+        self.0.count.store(instances.len(), Ordering::Relaxed);
+        self.0
+            .queue
+            .write_buffer(&self.0.buffer, 0, cast_slice(instances));
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn buffer(&self) -> &wgpu::Buffer {
+        &self.0.buffer
+    }
+
+    /// Maximum amount of instances the buffer can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity_in_bytes() / size_of::<Instance>()
+    }
+
+    /// Maximum amount of bytes the buffer can hold.
+    #[inline]
+    pub fn capacity_in_bytes(&self) -> usize {
+        self.0.buffer.size() as usize
+    }
+
+    /// How many instances are in the buffer.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.0.count.load(Ordering::Relaxed)
+    }
+
+    /// How many bytes are in the buffer.
+    #[inline]
+    pub fn size_in_bytes(&self) -> usize {
+        self.0.count.load(Ordering::Relaxed) * size_of::<Instance>()
+    }
+}
+
+/// An error uploading instances to a buffer.
+#[derive(Debug, thiserror::Error)]
+pub enum InstanceBufferUploadError {
+    #[error("attempted to upload {got} instances to buffer with a capacity of {expected}")]
+    InsufficientSpace { expected: usize, got: usize },
+}
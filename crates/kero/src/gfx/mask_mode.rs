@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, FromRepr, VariantArray};
+use wgpu::{
+    ColorWrites, CompareFunction, Device, Extent3d, StencilFaceState, StencilOperation,
+    StencilState, TextureDescriptor, TextureDimension, TextureUsages,
+};
+
+/// Format of the stencil buffers that masks are drawn into.
+pub(crate) const STENCIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Stencil8;
+
+/// Where drawing is allowed once a mask has been drawn.
+#[derive(
+    Default,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumCount,
+    FromRepr,
+    VariantArray,
+)]
+pub enum MaskMode {
+    /// Only pixels inside the mask are drawn.
+    #[default]
+    Inside,
+
+    /// Only pixels outside the mask are drawn.
+    Outside,
+}
+
+/// How a draw call reads or writes the stencil buffer.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub(crate) enum StencilMode {
+    /// The stencil buffer is left alone.
+    #[default]
+    Ignore,
+
+    /// Pixels are drawn into the mask instead of the surface.
+    Write,
+
+    /// Pixels are erased from the mask instead of drawn to the surface.
+    Clear,
+
+    /// Pixels are only drawn where they pass the mask.
+    Test(MaskMode),
+}
+
+impl StencilMode {
+    /// Value written to or compared against the stencil buffer.
+    #[inline]
+    pub fn reference(self) -> u32 {
+        match self {
+            Self::Clear => 0,
+            _ => 1,
+        }
+    }
+
+    /// Color channels written to the surface.
+    #[inline]
+    pub fn color_writes(self) -> ColorWrites {
+        match self {
+            Self::Write | Self::Clear => ColorWrites::empty(),
+            _ => ColorWrites::ALL,
+        }
+    }
+
+    /// Stencil test and operations of the render pipeline.
+    pub fn state(self) -> StencilState {
+        let (compare, pass_op) = match self {
+            Self::Ignore => (CompareFunction::Always, StencilOperation::Keep),
+            Self::Write | Self::Clear => (CompareFunction::Always, StencilOperation::Replace),
+            Self::Test(MaskMode::Inside) => (CompareFunction::Equal, StencilOperation::Keep),
+            Self::Test(MaskMode::Outside) => (CompareFunction::NotEqual, StencilOperation::Keep),
+        };
+        let face = StencilFaceState {
+            compare,
+            fail_op: StencilOperation::Keep,
+            depth_fail_op: StencilOperation::Keep,
+            pass_op,
+        };
+        StencilState {
+            front: face,
+            back: face,
+            read_mask: 0xff,
+            write_mask: 0xff,
+        }
+    }
+}
+
+/// Create a stencil buffer for a surface of the size.
+pub(crate) fn create_stencil_texture(device: &Device, width: u32, height: u32) -> wgpu::Texture {
+    device.create_texture(&TextureDescriptor {
+        label: None,
+        size: Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: STENCIL_FORMAT,
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
@@ -3,44 +3,56 @@
 mod bindings;
 mod blend_mode;
 mod buffer_cache;
+mod capture;
 mod color_mode;
 mod draw;
 mod font;
 mod graphics;
 mod index_buffer;
+mod instance;
+mod instance_buffer;
+mod mask_mode;
 mod params;
 mod render_data;
 mod sampler;
 mod screen;
 mod shader;
+mod stroke;
 mod sub_texture;
 mod surface;
 mod texture;
 mod texture_format;
 mod texture_packer;
 mod texture_pixel;
+mod texture_readback;
 mod topology;
 mod vertex;
 mod vertex_buffer;
 
 pub use bindings::*;
 pub use blend_mode::*;
+pub use capture::*;
 pub use color_mode::*;
 pub use draw::*;
 pub use font::*;
 pub use graphics::*;
 pub use index_buffer::*;
+pub use instance::*;
+pub use instance_buffer::*;
+pub use mask_mode::*;
 pub use params::*;
 pub(crate) use render_data::*;
 pub use sampler::*;
 pub use screen::*;
 pub use shader::*;
+pub use stroke::*;
 pub use sub_texture::*;
 pub use surface::*;
 pub use texture::*;
 pub use texture_format::*;
 pub use texture_packer::*;
 pub use texture_pixel::*;
+pub use texture_readback::*;
 pub use topology::*;
 pub use vertex::*;
 pub use vertex_buffer::*;
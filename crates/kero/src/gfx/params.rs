@@ -1,4 +1,4 @@
-use crate::gfx::{BindingValue, Sampler, Texture, UniformValue};
+use crate::gfx::{BindingValue, Sampler, ShaderError, Texture, UniformValue};
 use crate::math::{Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
 use naga::{ImageClass, ImageDimension, Module, Scalar, ScalarKind, TypeInner, VectorSize};
 use serde::{Deserialize, Serialize};
@@ -11,28 +11,32 @@ pub struct ParamDefs {
 }
 
 impl ParamDefs {
-    pub(crate) fn new(module: &Module) -> Self {
+    pub(crate) fn new(module: &Module) -> Result<Self, ShaderError> {
         let mut defs = Vec::new();
         for (binding_idx, (_, global)) in module.global_variables.iter().enumerate() {
             // must have a name
             let Some(name) = global.name.clone() else {
-                panic!("global has no name");
+                return Err(ShaderError::Interface("global has no name".to_string()));
             };
 
             // must have resource binding
             let Some(binding) = global.binding.as_ref() else {
-                panic!("global variable {name:?} has no resource binding");
+                return Err(ShaderError::Interface(format!(
+                    "global variable {name:?} has no resource binding"
+                )));
             };
 
             // all user-written bindings are in group 0
             if binding.group != 0 {
-                panic!("global variable {name:?} must be in @group(0)");
+                return Err(ShaderError::Interface(format!(
+                    "global variable {name:?} must be in @group(0)"
+                )));
             }
             if binding.binding != binding_idx as u32 {
-                panic!(
+                return Err(ShaderError::Interface(format!(
                     "global variable {name:?} has @binding({}) but @binding({}) was expected next",
                     binding.binding, binding_idx
-                );
+                )));
             }
 
             let naga_ty = &module.types[global.ty];
@@ -118,7 +122,9 @@ impl ParamDefs {
 
                 _ => {
                     let naga_name = naga_ty.name.clone().unwrap_or_else(|| "???".to_string());
-                    panic!("global variable {name:?} has invalid type {naga_name:?}");
+                    return Err(ShaderError::Interface(format!(
+                        "global variable {name:?} has invalid type {naga_name:?}"
+                    )));
                 }
             };
 
@@ -126,7 +132,7 @@ impl ParamDefs {
             defs.push(ParamDef { name, ty });
         }
 
-        Self { defs }
+        Ok(Self { defs })
     }
 
     /// Find a parameter by name.
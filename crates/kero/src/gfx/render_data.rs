@@ -1,10 +1,10 @@
 use crate::color::Rgba8;
 use crate::gfx::draw::DrawCache;
 use crate::gfx::{
-    BindingValue, Bindings, BlendMode, IndexBuffer, Sampler, Shader, Surface, Texture, Topology,
-    UniformValue, Vertex, VertexBuffer,
+    BindingValue, Bindings, BlendMode, ColorMode, IndexBuffer, InstanceBuffer, Sampler, Shader,
+    StencilMode, Surface, Texture, Topology, UniformValue, Vertex, VertexBuffer,
 };
-use crate::math::{Mat4, Numeric, Rect, Vec2};
+use crate::math::{Mat4, Numeric, Rect, Vec2, vec2};
 
 #[derive(Debug)]
 pub struct RenderData {
@@ -26,6 +26,7 @@ pub struct RenderPass {
     pub surface: Option<Surface>,
     pub clear_color: Option<Rgba8>,
     pub layers: Vec<RenderLayer>,
+    pub uses_stencil: bool,
 }
 
 impl RenderPass {
@@ -38,6 +39,7 @@ impl RenderPass {
             surface,
             clear_color,
             layers: layers_vec,
+            uses_stencil: false,
         }
     }
 
@@ -73,6 +75,7 @@ pub struct RenderLayer {
     pub bindings: Bindings,
     pub blend_mode: BlendMode,
     pub scissor_rect: Option<Rect<u32>>,
+    pub stencil: StencilMode,
     pub topology: Topology,
     pub vertices: Vec<Vertex>,
     pub indices: Vec<u32>,
@@ -80,6 +83,7 @@ pub struct RenderLayer {
     pub main_texture: Texture,
     pub main_sampler: Sampler,
     pub ortho: Mat4<f32>,
+    pub size: Vec2<f32>,
 }
 
 impl RenderLayer {
@@ -90,6 +94,7 @@ impl RenderLayer {
             bindings: Bindings::new(&cache.default_shader, &cache.default_texture),
             blend_mode: BlendMode::Normal,
             scissor_rect: None,
+            stencil: StencilMode::Ignore,
             topology: Topology::Triangles,
             vertices: cache.vertices_vecs.pop().unwrap_or_default(),
             indices: cache.indices_vecs.pop().unwrap_or_default(),
@@ -97,6 +102,7 @@ impl RenderLayer {
             main_texture: cache.default_texture.clone(),
             main_sampler: Sampler::default(),
             ortho: Mat4::ortho(0.0, size.x, size.y, 0.0, 0.0, 1.0),
+            size,
         }
     }
 
@@ -113,7 +119,23 @@ impl RenderLayer {
         self.vertices.clear();
         self.indices.clear();
 
-        // update the binding values
+        // submit the draw call
+        self.update_bindings();
+        self.calls.push(DrawCall {
+            shader: self.call_shader(cache),
+            bindings: self.bindings.clone(),
+            blend_mode: self.blend_mode,
+            clip_rect: self.scissor_rect,
+            stencil: self.stencil,
+            vertices,
+            indices,
+            instances: None,
+            topology: self.topology,
+        });
+    }
+
+    /// Update the binding values the layer sets on every shader.
+    fn update_bindings(&mut self) {
         self.bindings.set(
             &self.shader,
             "view_matrix",
@@ -129,17 +151,16 @@ impl RenderLayer {
             "main_sampler",
             BindingValue::Sampler(self.main_sampler),
         );
+    }
 
-        // submit the draw call
-        self.calls.push(DrawCall {
-            shader: self.shader.clone(),
-            bindings: self.bindings.clone(),
-            blend_mode: self.blend_mode,
-            clip_rect: self.scissor_rect,
-            vertices,
-            indices,
-            topology: self.topology,
-        });
+    /// The shader to submit draw calls with. Masks drawn with the default shader use the mask
+    /// shader instead, which has the same parameters.
+    fn call_shader(&self, cache: &DrawCache) -> Shader {
+        if self.stencil == StencilMode::Write && self.shader == cache.default_shader {
+            cache.mask_shader.clone()
+        } else {
+            self.shader.clone()
+        }
     }
 
     pub fn set_shader(&mut self, shader: &Shader, cache: &mut DrawCache) {
@@ -191,6 +212,45 @@ impl RenderLayer {
         }
     }
 
+    pub fn set_stencil(&mut self, stencil: StencilMode, cache: &mut DrawCache) {
+        if self.stencil != stencil {
+            self.flush(cache);
+            self.stencil = stencil;
+        }
+    }
+
+    /// Erase the whole stencil buffer by covering it with a quad that ignores the clip rect.
+    pub fn clear_stencil(&mut self, cache: &mut DrawCache) {
+        self.flush(cache);
+
+        let Vec2 { x: w, y: h } = self.size;
+        let quad = [vec2(0.0, 0.0), vec2(w, 0.0), vec2(w, h), vec2(0.0, h)]
+            .map(|pos| Vertex::new(pos, Vec2::ZERO, Rgba8::WHITE, ColorMode::VETO));
+        let (vertices, indices) =
+            cache
+                .buffer_cache
+                .request(&cache.device, &cache.queue, &quad, &[0, 1, 2, 0, 2, 3]);
+
+        let shader = cache.mask_shader.clone();
+        let mut bindings = Bindings::new(&shader, &cache.default_texture);
+        bindings.set(
+            &shader,
+            "view_matrix",
+            BindingValue::Uniform(UniformValue::Mat4(self.ortho)),
+        );
+        self.calls.push(DrawCall {
+            shader,
+            bindings,
+            blend_mode: BlendMode::Normal,
+            clip_rect: None,
+            stencil: StencilMode::Clear,
+            vertices,
+            indices,
+            instances: None,
+            topology: Topology::Triangles,
+        });
+    }
+
     pub fn set_topology(&mut self, topology: Topology, cache: &mut DrawCache) {
         if self.topology != topology {
             self.flush(cache);
@@ -212,17 +272,21 @@ impl RenderLayer {
         topology: Topology,
         vertices: VertexBuffer,
         indices: IndexBuffer,
+        instances: Option<InstanceBuffer>,
         cache: &mut DrawCache,
     ) {
         self.flush(cache);
         self.main_texture = texture;
+        self.update_bindings();
         self.calls.push(DrawCall {
-            shader: self.shader.clone(),
+            shader: self.call_shader(cache),
             bindings: self.bindings.clone(),
             blend_mode: self.blend_mode,
             clip_rect: self.scissor_rect,
+            stencil: self.stencil,
             vertices,
             indices,
+            instances,
             topology,
         });
     }
@@ -234,7 +298,9 @@ pub struct DrawCall {
     pub bindings: Bindings,
     pub blend_mode: BlendMode,
     pub clip_rect: Option<Rect<u32>>,
+    pub stencil: StencilMode,
     pub vertices: VertexBuffer,
     pub indices: IndexBuffer,
+    pub instances: Option<InstanceBuffer>,
     pub topology: Topology,
 }
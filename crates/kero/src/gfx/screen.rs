@@ -191,7 +191,8 @@ impl Screen {
         self.mouse_pos = win_rect.map_pos(ctx.mouse.pos(), &self.scr_rect).round();
     }
 
-    /// Map a window position to an on-screen position.
+    /// Map a window position, such as [`Mouse::pos`](crate::input::Mouse::pos) or
+    /// [`Touch::pos`](crate::input::Touch::pos), to an on-screen position.
     #[inline]
     pub fn map_pos(&self, pos: Vec2F) -> Vec2F {
         self.win_rect.map_pos(pos, &self.scr_rect)
@@ -1,21 +1,25 @@
 use crate::gfx::{
-    BindingValue, Bindings, BlendMode, ParamDefs, ParamType, Sampler, Texture, Topology, Vertex,
+    BindingValue, Bindings, BlendMode, Instance, ParamDef, ParamDefs, ParamType, STENCIL_FORMAT,
+    Sampler, StencilMode, Texture, Topology, Vertex,
 };
 use naga::valid::{Capabilities, ValidationFlags, Validator};
-use naga::{FunctionResult, Scalar, ScalarKind, ShaderStage, TypeInner, VectorSize};
+use naga::{FunctionResult, Scalar, ScalarKind, ShaderStage, Span, TypeInner, VectorSize};
 use std::cmp::Ordering;
-use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use std::fmt::{Debug, Formatter};
+use std::collections::hash_map::Entry;
+use std::fmt::{Debug, Display, Formatter};
 use std::hash::Hash;
-use std::sync::{Arc, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::SystemTime;
 use wgpu::util::{BufferInitDescriptor, DeviceExt};
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
     BindGroupLayoutEntry, BindingResource, Buffer, BufferBinding, BufferBindingType, BufferSize,
-    BufferUsages, ColorTargetState, ColorWrites, Device, FragmentState, FrontFace,
-    MultisampleState, PipelineLayout, PipelineLayoutDescriptor, PolygonMode, PrimitiveState, Queue,
-    RenderPipeline, RenderPipelineDescriptor, SamplerBindingType, SamplerDescriptor, ShaderModule,
+    BufferUsages, ColorTargetState, ColorWrites, CompareFunction, DepthBiasState,
+    DepthStencilState, Device, FragmentState, FrontFace, MultisampleState, PipelineLayout,
+    PipelineLayoutDescriptor, PolygonMode, PrimitiveState, Queue, RenderPipeline,
+    RenderPipelineDescriptor, SamplerBindingType, SamplerDescriptor, ShaderModule,
     ShaderModuleDescriptor, ShaderSource, ShaderStages, TextureSampleType, TextureViewDescriptor,
     TextureViewDimension, VertexState,
 };
@@ -57,18 +61,188 @@ impl PartialOrd for Shader {
 
 #[derive(Debug)]
 struct Inner {
+    program: RwLock<Arc<Program>>,
+    watch: Option<Mutex<Watch>>,
+}
+
+/// The compiled shader, which is replaced when a watched shader is reloaded.
+#[derive(Debug)]
+struct Program {
     shader: ShaderModule,
-    param_defs: ParamDefs,
+    param_defs: Arc<ParamDefs>,
+    removed_params: Vec<ParamDef>,
     bind_group_layout: BindGroupLayout,
     bind_group_cache: RwLock<BindGroupCache>,
     pipeline_cache: RwLock<PipelineCache>,
 }
 
+/// The file a watched shader is loaded from.
+#[derive(Debug)]
+struct Watch {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    error: Option<String>,
+}
+
 impl Shader {
     /// The maximum amount of bindings allowed in a shader.
     pub const MAX_BINDINGS: usize = 16;
 
-    pub(crate) fn new(device: &Device, source: &str) -> Self {
+    pub(crate) fn new(device: &Device, source: &str) -> Result<Self, ShaderError> {
+        Ok(Self(Arc::new(Inner {
+            program: RwLock::new(Arc::new(Program::new(device, source)?)),
+            watch: None,
+        })))
+    }
+
+    /// Load a shader from a file that will be reloaded whenever the file changes.
+    pub(crate) fn new_watched(device: &Device, path: PathBuf) -> Result<Self, ShaderError> {
+        let modified = modified_time(&path);
+        let source = std::fs::read_to_string(&path)?;
+        Ok(Self(Arc::new(Inner {
+            program: RwLock::new(Arc::new(Program::new(device, &source)?)),
+            watch: Some(Mutex::new(Watch {
+                path,
+                modified,
+                error: None,
+            })),
+        })))
+    }
+
+    #[inline]
+    fn program(&self) -> Arc<Program> {
+        self.0.program.read().unwrap().clone()
+    }
+
+    pub(crate) fn request_pipeline(
+        &self,
+        device: &Device,
+        topology: Topology,
+        format: wgpu::TextureFormat,
+        blend_mode: BlendMode,
+        stencil: Option<StencilMode>,
+    ) -> RenderPipeline {
+        let program = self.program();
+        program
+            .pipeline_cache
+            .write()
+            .unwrap()
+            .request(
+                device,
+                &program.shader,
+                topology,
+                format,
+                blend_mode,
+                stencil,
+            )
+            .clone()
+    }
+
+    pub(crate) fn request_bind_group(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        bindings: &Bindings,
+        samplers: &mut HashMap<Sampler, wgpu::Sampler>,
+        frame: u64,
+    ) -> BindGroup {
+        let program = self.program();
+        program
+            .bind_group_cache
+            .write()
+            .unwrap()
+            .request(
+                device,
+                queue,
+                bindings,
+                samplers,
+                &program.bind_group_layout,
+                frame,
+            )
+            .clone()
+    }
+
+    /// All parameters defined on the shader.
+    #[inline]
+    pub fn param_defs(&self) -> Arc<ParamDefs> {
+        self.program().param_defs.clone()
+    }
+
+    /// If the parameter used to exist on this shader, but was removed or had its type changed
+    /// when the shader was reloaded. Setting these parameters is ignored instead of panicking,
+    /// so drawing code keeps working while the shader is being edited.
+    pub(crate) fn was_param_removed(&self, name: &str, ty: ParamType) -> bool {
+        self.program()
+            .removed_params
+            .iter()
+            .any(|def| def.name == name && def.ty == ty)
+    }
+
+    /// The file this shader is watching, if it was loaded with
+    /// [`Graphics::watch_shader`](super::Graphics::watch_shader).
+    #[inline]
+    pub fn path(&self) -> Option<PathBuf> {
+        let watch = self.0.watch.as_ref()?;
+        Some(watch.lock().unwrap().path.clone())
+    }
+
+    /// If the most recent reload of the watched shader failed, the error it failed with. The
+    /// shader keeps using the last version that compiled until the file is fixed.
+    #[inline]
+    pub fn reload_error(&self) -> Option<String> {
+        let watch = self.0.watch.as_ref()?;
+        watch.lock().unwrap().error.clone()
+    }
+
+    /// If this shader is watching a file and the file has changed, recompile the shader.
+    /// Returns `true` if the shader was reloaded. If compiling fails, the shader keeps using its
+    /// previous version and the error is returned.
+    pub(crate) fn reload_if_changed(&self, device: &Device) -> Result<bool, ShaderError> {
+        let Some(watch) = self.0.watch.as_ref() else {
+            return Ok(false);
+        };
+        let mut watch = watch.lock().unwrap();
+        let modified = modified_time(&watch.path);
+        if modified == watch.modified {
+            return Ok(false);
+        }
+
+        // only try each version of the file once, even if it fails to compile
+        watch.modified = modified;
+        let result = std::fs::read_to_string(&watch.path)
+            .map_err(ShaderError::from)
+            .and_then(|source| Program::new(device, &source));
+        let mut program = match result {
+            Ok(program) => program,
+            Err(err) => {
+                watch.error = Some(err.to_string());
+                return Err(err);
+            }
+        };
+        watch.error = None;
+
+        // remember params that disappeared so setting them doesn't panic
+        let prev = self.program();
+        program.removed_params = prev
+            .param_defs
+            .defs
+            .iter()
+            .chain(prev.removed_params.iter())
+            .filter(|def| !program.param_defs.defs.contains(def))
+            .cloned()
+            .collect();
+
+        *self.0.program.write().unwrap() = Arc::new(program);
+        Ok(true)
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl Program {
+    fn new(device: &Device, source: &str) -> Result<Self, ShaderError> {
         // get the shared footer code for the shader, but re-position the
         // bindings in @group(0) so they trail after the user-defined ones
         let footer = {
@@ -81,24 +255,32 @@ impl Shader {
                 .replace("$1", &format!("{}", next + 1))
                 .replace("$2", &format!("{}", next + 2))
         };
+        let user_source = source;
         let source = format!("{source}\n{footer}");
 
         // parse the module so we can validate it
-        let module = match naga::front::wgsl::parse_str(&source) {
-            Ok(module) => module,
-            Err(err) => {
-                err.emit_to_stderr(&source);
-                std::process::exit(0);
-            }
-        };
+        let module = naga::front::wgsl::parse_str(&source).map_err(|err| {
+            ShaderError::Parse(ShaderDiagnostic::new(
+                user_source,
+                &source,
+                err.message().to_string(),
+                err.labels().map(|(span, msg)| (span, msg.to_string())),
+                err.emit_to_string(&source),
+            ))
+        })?;
 
         // validate the module
-        if let Err(err) =
-            Validator::new(ValidationFlags::default(), Capabilities::default()).validate(&module)
-        {
-            err.emit_to_stderr(&source);
-            std::process::exit(0);
-        };
+        Validator::new(ValidationFlags::default(), Capabilities::default())
+            .validate(&module)
+            .map_err(|err| {
+                ShaderError::Validation(ShaderDiagnostic::new(
+                    user_source,
+                    &source,
+                    err.as_inner().to_string(),
+                    err.spans().cloned(),
+                    err.emit_to_string(&source),
+                ))
+            })?;
 
         // make sure it has a valid @vertex entry point
         {
@@ -107,25 +289,33 @@ impl Shader {
                 .iter()
                 .find(|e| e.stage == ShaderStage::Vertex)
             else {
-                panic!("shader has no @vertex entry point");
+                return Err(ShaderError::Interface(
+                    "shader has no @vertex entry point".to_string(),
+                ));
             };
             let Some(name) = main.function.name.as_ref() else {
-                panic!("@vertex entry point has no name");
+                return Err(ShaderError::Interface(
+                    "@vertex entry point has no name".to_string(),
+                ));
             };
             let args = &main.function.arguments;
             if args.len() != 1
                 || args[0].binding.is_some()
                 || module.types[args[0].ty].name != Some("Vertex".to_string())
             {
-                panic!("invalid arguments to @vertex entry point {name:?}, expected `Vertex`");
+                return Err(ShaderError::Interface(format!(
+                    "invalid arguments to @vertex entry point {name:?}, expected `Vertex`"
+                )));
             }
             let Some(ret) = main.function.result.as_ref() else {
-                panic!("@vertex entry point {name:?} has no return value, expected `-> Fragment`");
+                return Err(ShaderError::Interface(format!(
+                    "@vertex entry point {name:?} has no return value, expected `-> Fragment`"
+                )));
             };
             if ret.binding.is_some() || module.types[ret.ty].name != Some("Fragment".to_string()) {
-                panic!(
+                return Err(ShaderError::Interface(format!(
                     "@vertex entry point {name:?} has invalid return value, expected `-> Fragment`"
-                );
+                )));
             }
         }
 
@@ -136,17 +326,23 @@ impl Shader {
                 .iter()
                 .find(|e| e.stage == ShaderStage::Fragment)
             else {
-                panic!("shader has no @fragment entry point");
+                return Err(ShaderError::Interface(
+                    "shader has no @fragment entry point".to_string(),
+                ));
             };
             let Some(name) = main.function.name.as_ref() else {
-                panic!("@fragment entry point has no name");
+                return Err(ShaderError::Interface(
+                    "@fragment entry point has no name".to_string(),
+                ));
             };
             let args = &main.function.arguments;
             if args.len() != 1
                 || args[0].binding.is_some()
                 || module.types[args[0].ty].name != Some("Fragment".to_string())
             {
-                panic!("invalid arguments to @fragment entry point {name:?}, expected `Fragment`");
+                return Err(ShaderError::Interface(format!(
+                    "invalid arguments to @fragment entry point {name:?}, expected `Fragment`"
+                )));
             }
             let good = if let Some(FunctionResult {
                 ty,
@@ -167,22 +363,21 @@ impl Shader {
                 false
             };
             if !good {
-                panic!(
+                return Err(ShaderError::Interface(format!(
                     "@fragment entry point {name:?} has invalid return value, expected `-> @location(0) vec4f`"
-                );
+                )));
             }
         }
 
         // get the user-made parameter definitions (@group(0))
-        let param_defs = ParamDefs::new(&module);
+        let param_defs = ParamDefs::new(&module)?;
 
         // cap bindings
-        if param_defs.defs.len() > Self::MAX_BINDINGS {
-            panic!(
-                "shader has {} bindings which exceeds the maximum of {}",
-                param_defs.defs.len(),
-                Self::MAX_BINDINGS
-            );
+        if param_defs.defs.len() > Shader::MAX_BINDINGS {
+            return Err(ShaderError::TooManyBindings {
+                count: param_defs.defs.len(),
+                max: Shader::MAX_BINDINGS,
+            });
         }
 
         // create the bind group layout for this shader
@@ -231,57 +426,47 @@ impl Shader {
             push_constant_ranges: &[],
         });
 
-        Self(Arc::new(Inner {
+        Ok(Self {
             shader,
-            param_defs,
+            param_defs: Arc::new(param_defs),
+            removed_params: Vec::new(),
             bind_group_layout,
             bind_group_cache: RwLock::default(),
             pipeline_cache: RwLock::new(PipelineCache::new(pipeline_layout)),
-        }))
+        })
     }
+}
 
-    pub(crate) fn request_pipeline(
-        &self,
-        device: &Device,
-        topology: Topology,
-        format: wgpu::TextureFormat,
-        blend_mode: BlendMode,
-    ) -> RenderPipeline {
-        self.0
-            .pipeline_cache
-            .write()
-            .unwrap()
-            .request(device, &self.0.shader, topology, format, blend_mode)
-            .clone()
-    }
+/// Keeps track of watched shaders so they can be checked for changes every frame.
+#[derive(Debug, Default)]
+pub(crate) struct ShaderWatcher {
+    shaders: Vec<Weak<Inner>>,
+}
 
-    pub(crate) fn request_bind_group(
-        &self,
-        device: &Device,
-        queue: &Queue,
-        bindings: &Bindings,
-        samplers: &mut HashMap<Sampler, wgpu::Sampler>,
-        frame: u64,
-    ) -> BindGroup {
-        self.0
-            .bind_group_cache
-            .write()
-            .unwrap()
-            .request(
-                device,
-                queue,
-                bindings,
-                samplers,
-                &self.0.bind_group_layout,
-                frame,
-            )
-            .clone()
+impl ShaderWatcher {
+    pub fn add(&mut self, shader: &Shader) {
+        self.shaders.push(Arc::downgrade(&shader.0));
     }
 
-    /// All parameters defined on the shader.
-    #[inline]
-    pub fn param_defs(&self) -> &ParamDefs {
-        &self.0.param_defs
+    /// Reload any watched shaders whose files have changed, forgetting about ones that have
+    /// been dropped. Shaders that fail to reload keep their previous version.
+    pub fn update(&mut self, device: &Device) {
+        self.shaders.retain(|shader| {
+            let Some(shader) = shader.upgrade().map(Shader) else {
+                return false;
+            };
+            match shader.reload_if_changed(device) {
+                Ok(true) => log::info!("reloaded shader {:?}", shader.path().unwrap()),
+                Ok(false) => {}
+                Err(err) => {
+                    log::error!(
+                        "failed to reload shader {:?}:\n{err}",
+                        shader.path().unwrap()
+                    )
+                }
+            }
+            true
+        });
     }
 }
 
@@ -306,12 +491,14 @@ impl PipelineCache {
         topology: Topology,
         format: wgpu::TextureFormat,
         blend_mode: BlendMode,
+        stencil: Option<StencilMode>,
     ) -> &RenderPipeline {
         self.cache
             .entry(PipelineKey {
                 topology,
                 format,
                 blend_mode,
+                stencil,
             })
             .or_insert_with(|| {
                 device.create_render_pipeline(&RenderPipelineDescriptor {
@@ -321,7 +508,7 @@ impl PipelineCache {
                         module: shader,
                         entry_point: None,
                         compilation_options: Default::default(),
-                        buffers: &[Vertex::LAYOUT],
+                        buffers: &[Vertex::LAYOUT, Instance::LAYOUT],
                     },
                     primitive: PrimitiveState {
                         topology: topology.into(),
@@ -332,7 +519,13 @@ impl PipelineCache {
                         polygon_mode: PolygonMode::Fill,
                         conservative: false,
                     },
-                    depth_stencil: None,
+                    depth_stencil: stencil.map(|stencil| DepthStencilState {
+                        format: STENCIL_FORMAT,
+                        depth_write_enabled: false,
+                        depth_compare: CompareFunction::Always,
+                        stencil: stencil.state(),
+                        bias: DepthBiasState::default(),
+                    }),
                     multisample: MultisampleState {
                         count: 1,
                         mask: !0,
@@ -345,7 +538,7 @@ impl PipelineCache {
                         targets: &[Some(ColorTargetState {
                             format,
                             blend: Some(blend_mode.into()),
-                            write_mask: ColorWrites::ALL,
+                            write_mask: stencil.map_or(ColorWrites::ALL, StencilMode::color_writes),
                         })],
                     }),
                     multiview: None,
@@ -360,6 +553,7 @@ struct PipelineKey {
     topology: Topology,
     format: wgpu::TextureFormat,
     blend_mode: BlendMode,
+    stencil: Option<StencilMode>,
 }
 
 #[derive(Debug, Default)]
@@ -536,3 +730,139 @@ struct CachedGroup {
     buffers: Vec<Buffer>,
     bind_group: BindGroup,
 }
+
+/// An error compiling a shader.
+#[derive(Debug, thiserror::Error)]
+pub enum ShaderError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Parse(ShaderDiagnostic),
+
+    #[error("{0}")]
+    Validation(ShaderDiagnostic),
+
+    #[error("{0}")]
+    Interface(String),
+
+    #[error("shader has {count} bindings which exceeds the maximum of {max}")]
+    TooManyBindings { count: usize, max: usize },
+}
+
+impl ShaderError {
+    /// The compiler diagnostic, if this is a parse or validation error.
+    #[inline]
+    pub fn diagnostic(&self) -> Option<&ShaderDiagnostic> {
+        match self {
+            Self::Parse(diag) | Self::Validation(diag) => Some(diag),
+            _ => None,
+        }
+    }
+}
+
+/// A parse or validation error reported by the shader compiler.
+#[derive(Debug, Clone)]
+pub struct ShaderDiagnostic {
+    /// The compiler's error message.
+    pub message: String,
+
+    /// Locations in the source the error refers to, the first being the primary one.
+    pub labels: Vec<ShaderLabel>,
+
+    /// The full error report, with the offending source lines underlined.
+    pub report: String,
+}
+
+impl ShaderDiagnostic {
+    fn new(
+        user_source: &str,
+        source: &str,
+        message: String,
+        labels: impl Iterator<Item = (Span, String)>,
+        report: String,
+    ) -> Self {
+        Self {
+            message,
+            labels: labels
+                .filter_map(|(span, message)| {
+                    let span = ShaderSpan::new(user_source, source, span)?;
+                    Some(ShaderLabel { span, message })
+                })
+                .collect(),
+            report,
+        }
+    }
+
+    /// The primary location of the error, if it has one.
+    #[inline]
+    pub fn span(&self) -> Option<ShaderSpan> {
+        self.labels.first().map(|label| label.span)
+    }
+}
+
+impl Display for ShaderDiagnostic {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.report.trim_end())
+    }
+}
+
+/// A location in a shader's source, described by a [`ShaderDiagnostic`].
+#[derive(Debug, Clone)]
+pub struct ShaderLabel {
+    pub span: ShaderSpan,
+    pub message: String,
+}
+
+/// A span of a shader's source code.
+///
+/// Every shader has a shared footer appended to it before being compiled. Spans that land in the
+/// user's source have the same line numbers as it, while spans that land in the footer have
+/// `in_footer` set and are numbered from the start of the footer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ShaderSpan {
+    /// 1-based line number.
+    pub line: u32,
+
+    /// 1-based column, in bytes.
+    pub column: u32,
+
+    /// Length of the span, in bytes.
+    pub length: u32,
+
+    /// If the span is in the shared footer rather than the user's source.
+    pub in_footer: bool,
+}
+
+impl ShaderSpan {
+    fn new(user_source: &str, source: &str, span: Span) -> Option<Self> {
+        if !span.is_defined() {
+            return None;
+        }
+        let loc = span.location(source);
+
+        // the footer starts on the line after the user's source
+        let user_lines = user_source.matches('\n').count() as u32 + 1;
+        let in_footer = loc.line_number > user_lines;
+        Some(Self {
+            line: match in_footer {
+                true => loc.line_number - user_lines,
+                false => loc.line_number,
+            },
+            column: loc.line_position,
+            length: loc.length,
+            in_footer,
+        })
+    }
+}
+
+impl Display for ShaderSpan {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.in_footer {
+            true => write!(f, "footer:{}:{}", self.line, self.column),
+            false => write!(f, "{}:{}", self.line, self.column),
+        }
+    }
+}
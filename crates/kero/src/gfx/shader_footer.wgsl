@@ -3,6 +3,11 @@ struct Vertex {
     @location(1) tex: vec2f,
     @location(2) col: vec4f,
     @location(3) mode: vec4f,
+    @location(4) inst_x_axis: vec2f,
+    @location(5) inst_y_axis: vec2f,
+    @location(6) inst_offset: vec2f,
+    @location(7) inst_tex: vec4f,
+    @location(8) inst_col: vec4f,
 };
 
 struct Fragment {
@@ -23,13 +28,23 @@ var main_sampler: sampler;
 
 fn vert_default(vert: Vertex) -> Fragment {
     var frag: Fragment;
-    frag.pos = view_matrix * vec4f(vert.pos, 0.0, 1.0);
-    frag.tex = vert.tex;
-    frag.col = vert.col;
+    frag.pos = view_matrix * vec4f(instance_pos(vert), 0.0, 1.0);
+    frag.tex = instance_tex(vert);
+    frag.col = vert.col * vert.inst_col;
     frag.mode = vert.mode;
     return frag;
 }
 
+// the vertex position, transformed by its instance
+fn instance_pos(vert: Vertex) -> vec2f {
+    return vert.inst_x_axis * vert.pos.x + vert.inst_y_axis * vert.pos.y + vert.inst_offset;
+}
+
+// the texture coordinate, mapped into its instance's texture rect
+fn instance_tex(vert: Vertex) -> vec2f {
+    return vert.inst_tex.xy + vert.tex * vert.inst_tex.zw;
+}
+
 fn frag_default(frag: Fragment) -> vec4f {
     var pixel = textureSample(main_texture, main_sampler, frag.tex);
     return apply_mode(pixel, frag.col, frag.mode);
@@ -0,0 +1,14 @@
+@vertex
+fn vert_main(vert: Vertex) -> Fragment {
+    return vert_default(vert);
+}
+
+@fragment
+fn frag_main(frag: Fragment) -> @location(0) vec4f {
+    // leave fully transparent pixels out of the mask
+    let color = frag_default(frag);
+    if color.a <= 0.0 {
+        discard;
+    }
+    return color;
+}
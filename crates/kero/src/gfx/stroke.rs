@@ -0,0 +1,528 @@
+use crate::color::Rgba8;
+use crate::gfx::Vertex;
+use crate::math::{Affine2F, Vec2F, vec2};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+use strum::{EnumCount, FromRepr, VariantArray};
+
+/// Shape drawn where two segments of a [`Stroke`] meet.
+#[derive(
+    Default,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumCount,
+    FromRepr,
+    VariantArray,
+)]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet in a sharp corner. Corners that would be
+    /// longer than the stroke's [miter limit](Stroke::miter_limit) are beveled instead.
+    #[default]
+    Miter,
+
+    /// The corner is rounded off.
+    Round,
+
+    /// The corner is cut off flat.
+    Bevel,
+}
+
+/// Shape drawn at the open ends of a [`Stroke`].
+#[derive(
+    Default,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumCount,
+    FromRepr,
+    VariantArray,
+)]
+pub enum LineCap {
+    /// The stroke ends flat, exactly at the end point.
+    #[default]
+    Butt,
+
+    /// The stroke ends in a half-circle centered on the end point.
+    Round,
+
+    /// The stroke ends flat, extended past the end point by half its width.
+    Square,
+}
+
+/// How lines are stroked by [`Draw::stroke_lines`](crate::gfx::Draw::stroke_lines) and
+/// [`Draw::stroke_path`](crate::gfx::Draw::stroke_path).
+///
+/// ```ignore
+/// // a 4px wide dashed line with rounded ends
+/// let stroke = Stroke::new(4.0)
+///     .with_cap(LineCap::Round)
+///     .with_dashes([12.0, 8.0]);
+/// draw.stroke_lines([vec2(10.0, 10.0), vec2(200.0, 40.0)], &stroke, Rgba8::WHITE, false);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stroke {
+    /// Width of the stroke. Strokes that aren't a positive, finite width draw nothing.
+    pub width: f32,
+
+    /// Shape of the corners.
+    pub join: LineJoin,
+
+    /// Shape of the open ends, including the ends of each dash.
+    pub cap: LineCap,
+
+    /// Maximum length of a [miter join](LineJoin::Miter), as a multiple of the stroke's width.
+    pub miter_limit: f32,
+
+    /// Alternating lengths of the dashes and the gaps between them. If empty, the stroke is
+    /// solid. If the list has an odd number of lengths, it is repeated to make it even. If the
+    /// dashes are so short that a stroke would be split into many thousands of them, that stroke
+    /// is drawn solid.
+    pub dashes: Vec<f32>,
+
+    /// Distance into the dash pattern that the stroke starts at.
+    pub dash_offset: f32,
+}
+
+impl Default for Stroke {
+    #[inline]
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl Stroke {
+    /// A solid stroke of the width with miter joins and butt caps.
+    #[inline]
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            miter_limit: 4.0,
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+
+    /// Returns the stroke with the join.
+    #[inline]
+    pub fn with_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Returns the stroke with the cap.
+    #[inline]
+    pub fn with_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Returns the stroke with the miter limit.
+    #[inline]
+    pub fn with_miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Returns the stroke with the dash pattern.
+    #[inline]
+    pub fn with_dashes(mut self, dashes: impl IntoIterator<Item = f32>) -> Self {
+        self.dashes = dashes.into_iter().collect();
+        self
+    }
+
+    /// Returns the stroke with the dash offset.
+    #[inline]
+    pub fn with_dash_offset(mut self, dash_offset: f32) -> Self {
+        self.dash_offset = dash_offset;
+        self
+    }
+
+    /// The dash pattern with an even number of lengths, or none if the stroke is solid. Patterns
+    /// that would split a stroke of the length into too many dashes are drawn solid.
+    fn dash_pattern(&self, length: f32) -> Option<Vec<f32>> {
+        let valid = self.dashes.iter().all(|&len| len >= 0.0 && len.is_finite());
+        let total: f32 = self.dashes.iter().sum();
+        if !valid || total <= 0.0 {
+            return None;
+        }
+        let pieces = length / total * self.dashes.len() as f32;
+        if pieces.is_nan() || pieces > MAX_DASHES {
+            return None;
+        }
+        let mut pattern = self.dashes.clone();
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_within(..);
+        }
+        Some(pattern)
+    }
+
+    /// Split the points into the dashes of the pattern.
+    fn split_dashes(&self, pattern: &[f32], points: &[Vec2F], closed: bool) -> Vec<Vec<Vec2F>> {
+        let total: f32 = pattern.iter().sum();
+        let mut idx = 0;
+        let mut phase = self.dash_offset.rem_euclid(total);
+        while phase > 0.0 && phase >= pattern[idx] {
+            phase -= pattern[idx];
+            idx = (idx + 1) % pattern.len();
+        }
+        let mut remaining = pattern[idx] - phase;
+        let mut on = idx % 2 == 0;
+
+        let mut dashes = Vec::new();
+        let mut dash = Vec::new();
+        if on {
+            dash.push(points[0]);
+        }
+        let last = closed.then(|| points[0]);
+        let ends = points[1..].iter().copied().chain(last);
+        for (a, b) in points.iter().copied().zip(ends) {
+            let len = a.dist(b);
+            let mut t = 0.0;
+            while len - t > remaining {
+                t += remaining;
+                let p = a + (b - a) * (t / len);
+                dash.push(p);
+                if on {
+                    dashes.push(std::mem::take(&mut dash));
+                }
+                on = !on;
+                idx = (idx + 1) % pattern.len();
+                remaining = pattern[idx];
+            }
+            remaining -= len - t;
+            if on {
+                dash.push(b);
+            }
+        }
+        if on && !dash.is_empty() {
+            dashes.push(dash);
+        }
+        dashes
+    }
+
+    /// Tessellate a stroke along the points into triangles.
+    pub(crate) fn tessellate(
+        &self,
+        points: impl IntoIterator<Item = Vec2F>,
+        closed: bool,
+        verts: &mut Vec<Vertex>,
+        inds: &mut Vec<u32>,
+        mat: &Affine2F,
+        color: Rgba8,
+    ) {
+        if !self.width.is_finite() || self.width <= 0.0 {
+            return;
+        }
+
+        // drop repeated points, which have no direction to stroke along
+        let mut points: Vec<Vec2F> = points.into_iter().collect();
+        points.dedup_by(|b, a| a.sqr_dist(*b) <= EPSILON);
+        if closed && points.len() > 1 && points[0].sqr_dist(points[points.len() - 1]) <= EPSILON {
+            points.pop();
+        }
+        if points.is_empty() {
+            return;
+        }
+
+        // scale of the matrix, so curves stay smooth when drawn larger
+        let scale = f32::max(
+            mat.transform_vec2(Vec2F::X_AXIS).len(),
+            mat.transform_vec2(Vec2F::Y_AXIS).len(),
+        );
+        let mut mesh = StrokeMesh {
+            stroke: self,
+            half: self.width * 0.5,
+            circle_segs: f32::max(f32::floor(f32::sqrt(self.width * scale) * PI), 3.0)
+                .min(MAX_CIRCLE_SEGS),
+            verts,
+            inds,
+            mat,
+            color,
+        };
+
+        let mut length: f32 = points.windows(2).map(|pair| pair[0].dist(pair[1])).sum();
+        if closed {
+            length += points[0].dist(points[points.len() - 1]);
+        }
+        match self.dash_pattern(length) {
+            Some(pattern) => {
+                for mut dash in self.split_dashes(&pattern, &points, closed) {
+                    dash.dedup_by(|b, a| a.sqr_dist(*b) <= EPSILON);
+                    mesh.polyline(&dash, false);
+                }
+            }
+            None => mesh.polyline(&points, closed),
+        }
+    }
+}
+
+/// Points closer together than this (squared) are treated as the same point.
+const EPSILON: f32 = 1e-8;
+
+/// Most segments a full circle of a join, cap or dot will be drawn with, the same limit paths use
+/// when flattening a curve or arc.
+const MAX_CIRCLE_SEGS: f32 = 1020.0;
+
+/// Most dashes and gaps a single stroke will be split into before it's drawn solid instead.
+const MAX_DASHES: f32 = 16384.0;
+
+/// Vertices of the stroke on either side of a point, before and after it.
+#[derive(Copy, Clone)]
+struct Corner {
+    left_in: u32,
+    right_in: u32,
+    left_out: u32,
+    right_out: u32,
+}
+
+impl Corner {
+    #[inline]
+    fn new(left: u32, right: u32) -> Self {
+        Self {
+            left_in: left,
+            right_in: right,
+            left_out: left,
+            right_out: right,
+        }
+    }
+
+    /// The corner with its sides swapped, for when it faces the other way.
+    #[inline]
+    fn flipped(self) -> Self {
+        Self {
+            left_in: self.right_out,
+            right_in: self.left_out,
+            left_out: self.right_in,
+            right_out: self.left_in,
+        }
+    }
+}
+
+struct StrokeMesh<'a> {
+    stroke: &'a Stroke,
+    half: f32,
+    circle_segs: f32,
+    verts: &'a mut Vec<Vertex>,
+    inds: &'a mut Vec<u32>,
+    mat: &'a Affine2F,
+    color: Rgba8,
+}
+
+impl StrokeMesh<'_> {
+    #[inline]
+    fn vert(&mut self, p: Vec2F) -> u32 {
+        let i = self.verts.len() as u32;
+        self.verts
+            .push(Vertex::veto(self.mat.transform_pos2(p), self.color));
+        i
+    }
+
+    #[inline]
+    fn tri(&mut self, a: u32, b: u32, c: u32) {
+        self.inds.extend_from_slice(&[a, b, c]);
+    }
+
+    /// Fill an arc around the center, starting at `first` (which is `start` away from the
+    /// center) and sweeping the angle to end at `last`. The triangles fan out from `pivot`.
+    fn arc(&mut self, pivot: u32, center: Vec2F, start: Vec2F, sweep: f32, first: u32, last: u32) {
+        let steps = f32::max(f32::ceil(self.circle_segs * sweep.abs() / TAU), 1.0) as u32;
+        let mut prev = first;
+        for k in 1..steps {
+            let (sin, cos) = f32::sin_cos(sweep * k as f32 / steps as f32);
+            let offset = vec2(start.x * cos - start.y * sin, start.x * sin + start.y * cos);
+            let next = self.vert(center + offset);
+            self.tri(pivot, prev, next);
+            prev = next;
+        }
+        self.tri(pivot, prev, last);
+    }
+
+    /// Stroke a series of points, none of which repeat.
+    fn polyline(&mut self, points: &[Vec2F], closed: bool) {
+        let n = points.len();
+        match n {
+            0 => return,
+            1 => return self.dot(points[0]),
+            _ => {}
+        }
+
+        let seg_count = if closed { n } else { n - 1 };
+        let segs: Vec<(Vec2F, f32)> = (0..seg_count)
+            .map(|i| {
+                let diff = points[(i + 1) % n] - points[i];
+                let len = diff.len();
+                (diff / len, len)
+            })
+            .collect();
+
+        let corners: Vec<Corner> = (0..n)
+            .map(|i| {
+                if !closed && i == 0 {
+                    self.cap(points[0], -segs[0].0).flipped()
+                } else if !closed && i == n - 1 {
+                    self.cap(points[i], segs[i - 1].0)
+                } else {
+                    self.join(points[i], segs[(i + n - 1) % n], segs[i])
+                }
+            })
+            .collect();
+
+        for i in 0..seg_count {
+            let a = corners[i];
+            let b = corners[(i + 1) % n];
+            self.tri(a.left_out, a.right_out, b.right_in);
+            self.tri(a.left_out, b.right_in, b.left_in);
+        }
+    }
+
+    /// A stroke with no length, which only has caps.
+    fn dot(&mut self, p: Vec2F) {
+        let h = self.half;
+        match self.stroke.cap {
+            LineCap::Butt => {}
+            LineCap::Round => {
+                let center = self.vert(p);
+                let first = self.vert(p + vec2(h, 0.0));
+                let steps = self.circle_segs as u32;
+                let mut prev = first;
+                for k in 1..steps {
+                    let (sin, cos) = f32::sin_cos(TAU * k as f32 / steps as f32);
+                    let next = self.vert(p + vec2(cos, sin) * h);
+                    self.tri(center, prev, next);
+                    prev = next;
+                }
+                self.tri(center, prev, first);
+            }
+            LineCap::Square => {
+                let a = self.vert(p + vec2(-h, -h));
+                let b = self.vert(p + vec2(h, -h));
+                let c = self.vert(p + vec2(h, h));
+                let d = self.vert(p + vec2(-h, h));
+                self.tri(a, b, c);
+                self.tri(a, c, d);
+            }
+        }
+    }
+
+    /// The cap at an end point, where `dir` points away from the stroke. The returned corner's
+    /// sides are relative to `dir`.
+    fn cap(&mut self, p: Vec2F, dir: Vec2F) -> Corner {
+        let h = self.half;
+        let norm = dir.turn_right();
+        let base = match self.stroke.cap {
+            LineCap::Square => p + dir * h,
+            _ => p,
+        };
+        let left = self.vert(base + norm * h);
+        let right = self.vert(base - norm * h);
+        if self.stroke.cap == LineCap::Round {
+            let center = self.vert(p);
+            self.arc(center, p, norm * h, -PI, left, right);
+        }
+        Corner::new(left, right)
+    }
+
+    /// The join at a point between the incoming and outgoing segments.
+    fn join(&mut self, p: Vec2F, (d0, len0): (Vec2F, f32), (d1, len1): (Vec2F, f32)) -> Corner {
+        let h = self.half;
+        let n0 = d0.turn_right();
+        let n1 = d1.turn_right();
+        let cross = d0.cross(d1);
+        let dot = d0.dot(d1);
+
+        // (nearly) straight, so both segments can share the same vertices
+        if cross.abs() <= 1e-4 && dot > 0.0 {
+            let norm = (n0 + n1).norm();
+            let left = self.vert(p + norm * h);
+            let right = self.vert(p - norm * h);
+            return Corner::new(left, right);
+        }
+
+        // the side the path turns towards is the inside of the corner
+        let side = if cross > 0.0 { 1.0 } else { -1.0 };
+        let sum = n0 + n1;
+        let sum_len = sum.len();
+        let (mid, cos_half) = match sum_len > 1e-4 {
+            true => (sum / sum_len, (sum / sum_len).dot(n0)),
+            false => (n0, 0.0),
+        };
+        let miter_len = match cos_half > 1e-4 {
+            true => h / cos_half,
+            false => f32::INFINITY,
+        };
+
+        // if the segments are long enough, their inner edges meet at a single point, otherwise
+        // they overlap and the join fans out from the point itself
+        let along = f32::sqrt(f32::max(miter_len * miter_len - h * h, 0.0));
+        let shared = along <= f32::min(len0, len1) * 0.5;
+        let (inner_in, inner_out, pivot) = if shared {
+            let inner = self.vert(p + mid * (side * miter_len));
+            (inner, inner, inner)
+        } else {
+            (
+                self.vert(p + n0 * (side * h)),
+                self.vert(p + n1 * (side * h)),
+                self.vert(p),
+            )
+        };
+
+        let miter = self.stroke.join == LineJoin::Miter
+            && cos_half > 1e-4
+            && 1.0 / cos_half <= self.stroke.miter_limit;
+        let tip = p - mid * (side * miter_len);
+        let (outer_in, outer_out) = if miter && shared {
+            let outer = self.vert(tip);
+            (outer, outer)
+        } else {
+            let outer_in = self.vert(p - n0 * (side * h));
+            let outer_out = self.vert(p - n1 * (side * h));
+            match self.stroke.join {
+                LineJoin::Miter if miter => {
+                    let tip = self.vert(tip);
+                    self.tri(pivot, outer_in, tip);
+                    self.tri(pivot, tip, outer_out);
+                }
+                LineJoin::Round => {
+                    let sweep = side * f32::atan2(cross.abs(), dot);
+                    self.arc(pivot, p, n0 * (-side * h), sweep, outer_in, outer_out);
+                }
+                _ => self.tri(pivot, outer_in, outer_out),
+            }
+            (outer_in, outer_out)
+        };
+
+        if side > 0.0 {
+            Corner {
+                left_in: inner_in,
+                right_in: outer_in,
+                left_out: inner_out,
+                right_out: outer_out,
+            }
+        } else {
+            Corner {
+                left_in: outer_in,
+                right_in: inner_in,
+                left_out: outer_out,
+                right_out: inner_out,
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::gfx::{Graphics, Texture};
+use crate::gfx::{Graphics, Texture, create_stencil_texture};
 use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
@@ -20,6 +20,7 @@ pub struct Surface(Rc<Inner>);
 
 struct Inner {
     texture: Texture,
+    stencil: Option<wgpu::Texture>,
 
     #[cfg(feature = "lua")]
     texture_userdata: mlua::AnyUserData,
@@ -47,7 +48,11 @@ impl PartialOrd for Surface {
 
 impl Surface {
     #[allow(unused_variables)]
-    pub(crate) fn new(gfx: &Graphics, texture: Texture) -> Self {
+    pub(crate) fn new(gfx: &Graphics, texture: Texture, stencil: bool) -> Self {
+        let stencil = stencil.then(|| {
+            let size = texture.size();
+            create_stencil_texture(gfx.device(), size.x, size.y)
+        });
         Self(Rc::new(Inner {
             #[cfg(feature = "lua")]
             texture_userdata: gfx
@@ -57,6 +62,7 @@ impl Surface {
                 .unwrap(),
 
             texture,
+            stencil,
         }))
     }
 
@@ -66,6 +72,18 @@ impl Surface {
         &self.0.texture
     }
 
+    /// If the surface has a stencil buffer, so [masks](super::Draw::begin_mask) can be drawn to
+    /// it.
+    #[inline]
+    pub fn has_stencil(&self) -> bool {
+        self.0.stencil.is_some()
+    }
+
+    #[inline]
+    pub(crate) fn stencil(&self) -> Option<&wgpu::Texture> {
+        self.0.stencil.as_ref()
+    }
+
     #[cfg(feature = "lua")]
     pub fn texture_userdata(&self) -> &mlua::AnyUserData {
         &self.0.texture_userdata
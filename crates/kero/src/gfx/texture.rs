@@ -1,6 +1,8 @@
-use crate::gfx::{SubTexture, TextureFormat};
-use crate::grid::VecGrid;
+use crate::gfx::{SubTexture, TextureFormat, TexturePixel, TextureReadback};
+use crate::grid::{Grid, VecGrid};
+use crate::img::Image;
 use crate::math::{Numeric, RectU, Vec2U};
+use bytemuck::cast_slice;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
@@ -45,6 +47,7 @@ impl PartialOrd for Texture {
 #[derive(Debug)]
 pub(crate) struct Inner {
     pub texture: wgpu::Texture,
+    device: Device,
     queue: Queue,
     size: Vec2U,
     format: TextureFormat,
@@ -58,7 +61,8 @@ impl Texture {
         format: TextureFormat,
         surface: bool,
     ) -> Self {
-        let mut usage = TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING;
+        let mut usage =
+            TextureUsages::COPY_SRC | TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING;
         if surface {
             usage |= TextureUsages::RENDER_ATTACHMENT;
         }
@@ -78,6 +82,7 @@ impl Texture {
         });
         Self(Arc::new(Inner {
             texture,
+            device: device.clone(),
             queue,
             size,
             format,
@@ -86,14 +91,21 @@ impl Texture {
 
     pub(crate) fn upload_bytes(&self, data: &[u8]) {
         assert_eq!(data.len(), self.size_in_bytes());
-        let (width, height) = self.0.size.into();
-        let bytes_per_row = Some(self.0.format.bytes_per_pixel().to_u32() * width);
-        let rows_per_image = Some(height);
+        self.upload_region_bytes(RectU::sized(self.0.size), data);
+    }
+
+    fn upload_region_bytes(&self, rect: RectU, data: &[u8]) {
+        let bytes_per_row = Some(self.0.format.bytes_per_pixel().to_u32() * rect.w);
+        let rows_per_image = Some(rect.h);
         self.0.queue.write_texture(
             TexelCopyTextureInfo {
                 texture: &self.0.texture,
                 mip_level: 0,
-                origin: Origin3d::ZERO,
+                origin: Origin3d {
+                    x: rect.x,
+                    y: rect.y,
+                    z: 0,
+                },
                 aspect: TextureAspect::All,
             },
             data,
@@ -103,43 +115,103 @@ impl Texture {
                 rows_per_image,
             },
             Extent3d {
-                width,
-                height,
+                width: rect.w,
+                height: rect.h,
                 depth_or_array_layers: 1,
             },
         );
     }
 
-    // pub fn upload_pixels<P: TexturePixel>(&self, pixels: &[P]) -> Result<(), TextureUploadError> {
-    //     if P::TEXTURE_FORMAT != self.format() {
-    //         return Err(TextureUploadError::FormatMismatch {
-    //             expected: self.format(),
-    //             got: P::TEXTURE_FORMAT,
-    //         });
-    //     }
-    //     if pixels.len() < self.pixel_count() {
-    //         return Err(TextureUploadError::InsufficientPixels {
-    //             expected: self.pixel_count(),
-    //             got: pixels.len(),
-    //         });
-    //     }
-    //     self.upload_bytes(cast_slice(pixels));
-    //     Ok(())
-    // }
-
-    // pub fn upload_img<P: TexturePixel, S: AsRef<[P::Channel]>>(
-    //     &self,
-    //     img: &Image<P, S>,
-    // ) -> Result<(), TextureUploadError> {
-    //     if self.size() == img.size() {
-    //         self.upload_pixels(img.pixels())
-    //     } else {
-    //         Err(TextureUploadError::InvalidSize {
-    //             expected: self.size(),
-    //             got: img.size(),
-    //         })
-    //     }
-    // }
+    /// Replace all the texture's pixels. The pixel type must match the texture's format.
+    pub fn upload_pixels<P: TexturePixel>(&self, pixels: &[P]) -> Result<(), TextureUploadError> {
+        self.upload_region(RectU::sized(self.0.size), pixels)
+    }
+
+    /// Replace all the texture's pixels with the image's. The image must be the same size as the
+    /// texture, and its pixel type must match the texture's format.
+    pub fn upload_img<P: TexturePixel, S: AsRef<[P::Channel]>>(
+        &self,
+        img: &Image<P, S>,
+    ) -> Result<(), TextureUploadError> {
+        if self.size() == img.size() {
+            self.upload_pixels(img.pixels())
+        } else {
+            Err(TextureUploadError::InvalidSize {
+                expected: self.size(),
+                got: img.size(),
+            })
+        }
+    }
+
+    /// Replace the pixels in a region of the texture. The pixels are laid out row by row, and
+    /// their type must match the texture's format.
+    pub fn upload_region<P: TexturePixel>(
+        &self,
+        rect: impl Into<RectU>,
+        pixels: &[P],
+    ) -> Result<(), TextureUploadError> {
+        let rect = rect.into();
+        if P::TEXTURE_FORMAT != self.format() {
+            return Err(TextureUploadError::FormatMismatch {
+                expected: self.format(),
+                got: P::TEXTURE_FORMAT,
+            });
+        }
+        if !RectU::sized(self.size()).contains_rect(&rect) {
+            return Err(TextureUploadError::OutOfBounds {
+                rect,
+                size: self.size(),
+            });
+        }
+        let count = rect.w.to_usize() * rect.h.to_usize();
+        if pixels.len() < count {
+            return Err(TextureUploadError::InsufficientPixels {
+                expected: count,
+                got: pixels.len(),
+            });
+        }
+        if count > 0 {
+            self.upload_region_bytes(rect, cast_slice(&pixels[..count]));
+        }
+        Ok(())
+    }
+
+    /// Read all of the texture's pixels back from the GPU, blocking until they arrive.
+    ///
+    /// Panics if the pixel type does not match the texture's format.
+    #[inline]
+    pub fn read_pixels<P: TexturePixel>(&self) -> Image<P> {
+        self.read_region(RectU::sized(self.0.size))
+    }
+
+    /// Read a region of the texture's pixels back from the GPU, blocking until they arrive.
+    ///
+    /// Panics if the pixel type does not match the texture's format, or if the region is empty
+    /// or not fully within the texture.
+    #[inline]
+    pub fn read_region<P: TexturePixel>(&self, rect: impl Into<RectU>) -> Image<P> {
+        self.read_region_async(rect).wait()
+    }
+
+    /// Start reading a region of the texture's pixels back from the GPU. The returned readback
+    /// can be checked in later frames, so the game doesn't have to stall waiting on the GPU.
+    ///
+    /// Panics if the pixel type does not match the texture's format, or if the region is empty
+    /// or not fully within the texture.
+    pub fn read_region_async<P: TexturePixel>(&self, rect: impl Into<RectU>) -> TextureReadback<P> {
+        let rect = rect.into();
+        assert_eq!(
+            P::TEXTURE_FORMAT,
+            self.format(),
+            "pixel type does not match the texture format"
+        );
+        assert!(
+            rect.w > 0 && rect.h > 0 && RectU::sized(self.size()).contains_rect(&rect),
+            "region {rect:?} is empty or outside of the texture"
+        );
+
+        TextureReadback::new(&self.0.device, &self.0.queue, &self.0.texture, rect)
+    }
 
     /// Size of the texture in pixels.
     #[inline]
@@ -195,21 +267,24 @@ impl Texture {
     }
 }
 
-// /// An error uploading data to a texture.
-// #[derive(Debug, thiserror::Error)]
-// pub enum TextureUploadError {
-//     #[error("tried to upload pixels of type {expected:?} to texture of type {got:?}")]
-//     FormatMismatch {
-//         expected: TextureFormat,
-//         got: TextureFormat,
-//     },
-//
-//     #[error("tried to upload {got:?} pixels to texture that requires at least {expected:?}")]
-//     InsufficientPixels { expected: usize, got: usize },
-//
-//     #[error("tried to upload an image of size ({got}) to a texture of size ({expected})")]
-//     InvalidSize { expected: Vec2U, got: Vec2U },
-// }
+/// An error uploading data to a texture.
+#[derive(Debug, thiserror::Error)]
+pub enum TextureUploadError {
+    #[error("tried to upload pixels of type {got:?} to texture of type {expected:?}")]
+    FormatMismatch {
+        expected: TextureFormat,
+        got: TextureFormat,
+    },
+
+    #[error("tried to upload {got:?} pixels to texture that requires at least {expected:?}")]
+    InsufficientPixels { expected: usize, got: usize },
+
+    #[error("tried to upload an image of size ({got}) to a texture of size ({expected})")]
+    InvalidSize { expected: Vec2U, got: Vec2U },
+
+    #[error("tried to upload to region {rect:?} of a texture of size ({size})")]
+    OutOfBounds { rect: RectU, size: Vec2U },
+}
 
 impl AsRef<Texture> for Texture {
     #[inline]
@@ -0,0 +1,155 @@
+use crate::gfx::TexturePixel;
+use crate::img::Image;
+use crate::math::{Numeric, RectU, Vec2U};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use wgpu::{
+    Buffer, BufferAsyncError, BufferDescriptor, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT,
+    CommandEncoderDescriptor, Device, Extent3d, MapMode, Origin3d, PollType, Queue,
+    TexelCopyBufferInfo, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect,
+};
+
+/// A pending copy of a texture's pixels from the GPU.
+///
+/// The copy happens in the background, so you can request it in one frame and pick up the result
+/// in a later frame with [`try_take`](Self::try_take), or block until it's ready with
+/// [`wait`](Self::wait).
+///
+/// Created with [`Texture::read_region_async`](super::Texture::read_region_async).
+pub struct TextureReadback<P: TexturePixel> {
+    device: Device,
+    buffer: Buffer,
+    size: Vec2U,
+    row_size: usize,
+    padded_row_size: usize,
+    result: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+    marker: PhantomData<P>,
+}
+
+impl<P: TexturePixel> Debug for TextureReadback<P> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextureReadback")
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: TexturePixel> TextureReadback<P> {
+    /// Copy a region of the texture into a buffer and start mapping it. The texture's pixels must
+    /// be the same size as `P`, but do not need to be the same format.
+    pub(crate) fn new(
+        device: &Device,
+        queue: &Queue,
+        texture: &wgpu::Texture,
+        rect: RectU,
+    ) -> Self {
+        // rows copied into a buffer must be aligned
+        let row_size = size_of::<P>().to_u32() * rect.w;
+        let padded_row_size = row_size.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        // copy the texture region into a buffer we can map
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: (padded_row_size * rect.h) as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_buffer(
+            TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: rect.x,
+                    y: rect.y,
+                    z: 0,
+                },
+                aspect: TextureAspect::All,
+            },
+            TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_size),
+                    rows_per_image: Some(rect.h),
+                },
+            },
+            Extent3d {
+                width: rect.w,
+                height: rect.h,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit([encoder.finish()]);
+
+        // start mapping the buffer, storing the result when it's done
+        let result = Arc::new(Mutex::new(None));
+        buffer.slice(..).map_async(MapMode::Read, {
+            let result = result.clone();
+            move |res| *result.lock().unwrap() = Some(res)
+        });
+
+        Self {
+            device: device.clone(),
+            buffer,
+            size: rect.size(),
+            row_size: row_size.to_usize(),
+            padded_row_size: padded_row_size.to_usize(),
+            result,
+            marker: PhantomData,
+        }
+    }
+
+    /// Size of the region being read.
+    #[inline]
+    pub fn size(&self) -> Vec2U {
+        self.size
+    }
+
+    /// If the pixels have arrived from the GPU. This does not block.
+    pub fn is_ready(&self) -> bool {
+        _ = self.device.poll(PollType::Poll);
+        self.result.lock().unwrap().is_some()
+    }
+
+    /// Take the pixels if they have arrived, otherwise give back the pending readback.
+    pub fn try_take(self) -> Result<Image<P>, Self> {
+        if self.is_ready() {
+            Ok(self.finish())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Block until the pixels arrive from the GPU and return them.
+    pub fn wait(self) -> Image<P> {
+        if self.result.lock().unwrap().is_none() {
+            self.device
+                .poll(PollType::wait_indefinitely())
+                .expect("failed to read texture from the graphics device");
+        }
+        self.finish()
+    }
+
+    fn finish(self) -> Image<P> {
+        self.result
+            .lock()
+            .unwrap()
+            .take()
+            .expect("texture readback was not ready")
+            .expect("failed to map texture readback buffer");
+
+        // copy the rows out of the buffer, stripping their padding
+        let mapped = self.buffer.slice(..).get_mapped_range();
+        let mut bytes = Vec::with_capacity(self.row_size * self.size.y as usize);
+        for row in mapped.chunks_exact(self.padded_row_size) {
+            bytes.extend_from_slice(&row[..self.row_size]);
+        }
+        drop(mapped);
+        self.buffer.unmap();
+
+        Image::from_raw(self.size, bytemuck::pod_collect_to_vec(&bytes))
+    }
+}
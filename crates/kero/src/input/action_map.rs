@@ -0,0 +1,379 @@
+use crate::input::binding;
+use crate::input::input_contexts::ContextsState;
+use crate::input::{
+    ActionAxisData, ActionMapData, Binding, GamepadAxis, InputContexts, VirtualAxis, VirtualButton,
+    VirtualSource,
+};
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::rc::{Rc, Weak};
+
+#[cfg(feature = "lua")]
+pub type ActionMapRef = mlua::UserDataRef<ActionMap>;
+
+/// Handle to a named set of actions, such as the controls for gameplay, a menu, or a dialog box.
+///
+/// Each action is a [`VirtualButton`] or [`VirtualAxis`] looked up by name. An action map only
+/// receives input while it is pushed onto an [`InputContexts`] stack and no map above it is
+/// [blocking](Self::set_blocking), so opening a pause menu can silence gameplay without every
+/// gameplay action having to check whether the menu is open:
+///
+/// ```ignore
+/// let gameplay = ActionMap::new("gameplay", &VirtualSource::last_active(ctx));
+/// gameplay.button("jump").set_bindings([Key::Space.into(), GamepadButton::South.into()]);
+/// ctx.input_contexts.push(&gameplay);
+///
+/// let pause = ActionMap::new("pause", &VirtualSource::last_active(ctx));
+/// pause.set_blocking(true);
+/// pause.button("back").set_bindings([Key::Escape.into(), GamepadButton::East.into()]);
+///
+/// // while the pause map is pushed, this is always false
+/// if gameplay.pressed("jump") {
+///     self.player.jump();
+/// }
+/// ```
+///
+/// Querying an action's [`VirtualButton`] or [`VirtualAxis`] directly bypasses the stack, so
+/// query actions through the map instead and use the handles for rebinding.
+#[derive(Clone)]
+pub struct ActionMap(Rc<Inner>);
+
+impl Debug for ActionMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ActionMap").field(&self.0.name).finish()
+    }
+}
+
+impl PartialEq for ActionMap {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for ActionMap {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Rc::as_ptr(&self.0).partial_cmp(&Rc::as_ptr(&other.0))
+    }
+}
+
+struct Inner {
+    name: String,
+    source: VirtualSource,
+    buttons: RefCell<BTreeMap<String, VirtualButton>>,
+    axes: RefCell<BTreeMap<String, VirtualAxis>>,
+    blocking: Cell<bool>,
+    consumes: Cell<bool>,
+    contexts: RefCell<Weak<ContextsState>>,
+}
+
+/// The inputs hidden from an action map by the maps above it.
+#[derive(Default)]
+pub(crate) struct Hidden {
+    pub bindings: Vec<Binding>,
+    pub axes: Vec<GamepadAxis>,
+}
+
+impl Hidden {
+    /// Hide the binding from the maps below.
+    pub fn add(&mut self, binding: Binding) {
+        if let Binding::Axis { axis, .. } = binding
+            && !self.axes.contains(&axis)
+        {
+            self.axes.push(axis);
+        }
+        self.bindings.push(binding);
+    }
+
+    /// If the binding is hidden.
+    fn hides(&self, binding: &Binding) -> bool {
+        match *binding {
+            Binding::Axis { axis, .. } if self.axes.contains(&axis) => true,
+            _ => self.bindings.iter().any(|b| b.same_input(binding)),
+        }
+    }
+
+    /// The bindings that aren't hidden.
+    fn visible(&self, bindings: Vec<Binding>) -> Vec<Binding> {
+        let mut bindings = bindings;
+        bindings.retain(|b| !self.hides(b));
+        bindings
+    }
+}
+
+impl ActionMap {
+    /// Create an action map with no actions.
+    pub fn new(name: impl Into<String>, source: &VirtualSource) -> Self {
+        Self(Rc::new(Inner {
+            name: name.into(),
+            source: source.clone(),
+            buttons: RefCell::new(BTreeMap::new()),
+            axes: RefCell::new(BTreeMap::new()),
+            blocking: Cell::new(false),
+            consumes: Cell::new(true),
+            contexts: RefCell::new(Weak::new()),
+        }))
+    }
+
+    /// Create an action map with the actions and settings from the data.
+    pub fn from_data(
+        name: impl Into<String>,
+        source: &VirtualSource,
+        data: &ActionMapData,
+    ) -> Self {
+        let this = Self::new(name, source);
+        this.set_blocking(data.blocking);
+        this.set_consumes(data.consumes);
+        for (name, bindings) in &data.buttons {
+            this.button(name).set_bindings(bindings.iter().copied());
+        }
+        for (name, data) in &data.axes {
+            let axis = this.axis(name);
+            axis.set_axis(data.axis);
+            axis.neg().set_bindings(data.neg.iter().copied());
+            axis.pos().set_bindings(data.pos.iter().copied());
+        }
+        this
+    }
+
+    /// The map's actions and settings as data, so they can be saved.
+    pub fn to_data(&self) -> ActionMapData {
+        ActionMapData {
+            blocking: self.blocking(),
+            consumes: self.consumes(),
+            buttons: self
+                .0
+                .buttons
+                .borrow()
+                .iter()
+                .map(|(name, btn)| (name.clone(), btn.bindings()))
+                .collect(),
+            axes: self
+                .0
+                .axes
+                .borrow()
+                .iter()
+                .map(|(name, axis)| {
+                    let data = ActionAxisData {
+                        axis: axis.axis(),
+                        neg: axis.neg().bindings(),
+                        pos: axis.pos().bindings(),
+                    };
+                    (name.clone(), data)
+                })
+                .collect(),
+        }
+    }
+
+    /// The map's name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// The input source the map's actions listen to.
+    #[inline]
+    pub fn source(&self) -> &VirtualSource {
+        &self.0.source
+    }
+
+    /// If the maps below this one receive no input while it is active.
+    #[inline]
+    pub fn blocking(&self) -> bool {
+        self.0.blocking.get()
+    }
+
+    /// Set whether the maps below this one receive no input while it is active, such as for a
+    /// pause menu. The default is false.
+    #[inline]
+    pub fn set_blocking(&self, blocking: bool) {
+        self.0.blocking.set(blocking);
+    }
+
+    /// If the map hides the inputs its actions are bound to from the maps below it.
+    #[inline]
+    pub fn consumes(&self) -> bool {
+        self.0.consumes.get()
+    }
+
+    /// Set whether the map hides the inputs its actions are bound to from the maps below it
+    /// while it is active. The default is true, so if a dialog box and gameplay both bind
+    /// <kbd>Space</kbd>, pressing it advances the dialog without also jumping.
+    ///
+    /// Turn this off for maps that should share their inputs, and [consume](Self::consume)
+    /// individual presses instead.
+    #[inline]
+    pub fn set_consumes(&self, consumes: bool) {
+        self.0.consumes.set(consumes);
+    }
+
+    /// The button with the name, creating one with no bindings if it doesn't exist.
+    pub fn button(&self, name: &str) -> VirtualButton {
+        self.0
+            .buttons
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_insert_with(|| VirtualButton::empty(&self.0.source))
+            .clone()
+    }
+
+    /// The axis with the name, creating one with no bindings if it doesn't exist.
+    pub fn axis(&self, name: &str) -> VirtualAxis {
+        self.0
+            .axes
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_insert_with(|| VirtualAxis::empty(&self.0.source))
+            .clone()
+    }
+
+    /// The button with the name, if it exists.
+    #[inline]
+    pub fn get_button(&self, name: &str) -> Option<VirtualButton> {
+        self.0.buttons.borrow().get(name).cloned()
+    }
+
+    /// The axis with the name, if it exists.
+    #[inline]
+    pub fn get_axis(&self, name: &str) -> Option<VirtualAxis> {
+        self.0.axes.borrow().get(name).cloned()
+    }
+
+    /// Remove the button with the name, returning it if it existed.
+    #[inline]
+    pub fn remove_button(&self, name: &str) -> Option<VirtualButton> {
+        self.0.buttons.borrow_mut().remove(name)
+    }
+
+    /// Remove the axis with the name, returning it if it existed.
+    #[inline]
+    pub fn remove_axis(&self, name: &str) -> Option<VirtualAxis> {
+        self.0.axes.borrow_mut().remove(name)
+    }
+
+    /// Names of all the map's buttons, in sorted order.
+    #[inline]
+    pub fn button_names(&self) -> Vec<String> {
+        self.0.buttons.borrow().keys().cloned().collect()
+    }
+
+    /// Names of all the map's axes, in sorted order.
+    #[inline]
+    pub fn axis_names(&self) -> Vec<String> {
+        self.0.axes.borrow().keys().cloned().collect()
+    }
+
+    /// The stack the map is pushed onto, if any.
+    #[inline]
+    pub fn contexts(&self) -> Option<InputContexts> {
+        InputContexts::upgrade(&self.0.contexts.borrow())
+    }
+
+    #[inline]
+    pub(crate) fn set_contexts(&self, contexts: Weak<ContextsState>) {
+        *self.0.contexts.borrow_mut() = contexts;
+    }
+
+    /// If the map is receiving input, meaning it is pushed onto a stack and no map above it is
+    /// blocking.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.hidden().is_some()
+    }
+
+    /// The inputs hidden from this map, or none if it isn't receiving input.
+    #[inline]
+    fn hidden(&self) -> Option<Hidden> {
+        self.contexts()?.hidden(self)
+    }
+
+    /// Hide all of the inputs the map's actions are bound to.
+    pub(crate) fn hide_inputs(&self, hidden: &mut Hidden) {
+        for btn in self.0.buttons.borrow().values() {
+            btn.bindings().into_iter().for_each(|b| hidden.add(b));
+        }
+        for axis in self.0.axes.borrow().values() {
+            if let Some(axis) = axis.axis()
+                && !hidden.axes.contains(&axis)
+            {
+                hidden.axes.push(axis);
+            }
+            axis.neg()
+                .bindings()
+                .into_iter()
+                .for_each(|b| hidden.add(b));
+            axis.pos()
+                .bindings()
+                .into_iter()
+                .for_each(|b| hidden.add(b));
+        }
+    }
+
+    /// The button's bindings that the map can see, or none if the map isn't receiving input or
+    /// the button doesn't exist.
+    fn visible_bindings(&self, name: &str) -> Option<Vec<Binding>> {
+        let hidden = self.hidden()?;
+        let btn = self.get_button(name)?;
+        Some(hidden.visible(btn.bindings()))
+    }
+
+    /// If the button with the name is down.
+    pub fn down(&self, name: &str) -> bool {
+        self.visible_bindings(name)
+            .is_some_and(|b| binding::any_down(&b, &self.0.source))
+    }
+
+    /// If the button with the name was pressed this frame. Like [`VirtualButton::pressed`], this
+    /// treats the button's bindings as if they were one button.
+    pub fn pressed(&self, name: &str) -> bool {
+        self.visible_bindings(name)
+            .is_some_and(|b| binding::any_pressed(&b, &self.0.source))
+    }
+
+    /// If the button with the name was released this frame. Like [`VirtualButton::released`],
+    /// this treats the button's bindings as if they were one button.
+    pub fn released(&self, name: &str) -> bool {
+        self.visible_bindings(name)
+            .is_some_and(|b| binding::any_released(&b, &self.0.source))
+    }
+
+    /// Value of the button with the name, which is the highest value of any of its bindings.
+    pub fn value(&self, name: &str) -> f32 {
+        self.visible_bindings(name)
+            .map_or(0.0, |b| binding::max_value(&b, &self.0.source))
+    }
+
+    /// Value of the axis with the name from `-1.0` to `1.0`.
+    pub fn axis_value(&self, name: &str) -> f32 {
+        let (Some(hidden), Some(axis)) = (self.hidden(), self.get_axis(name)) else {
+            return 0.0;
+        };
+        let source = &self.0.source;
+        let mut value = 0.0;
+        if let Some(pad_axis) = axis.axis()
+            && !hidden.axes.contains(&pad_axis)
+        {
+            let raw = source.read(|pad| pad.axis(pad_axis)).unwrap_or(0.0);
+            value += axis.response().apply_axis(raw);
+        }
+        value -= binding::max_value(&hidden.visible(axis.neg().bindings()), source);
+        value += binding::max_value(&hidden.visible(axis.pos().bindings()), source);
+        value.clamp(-1.0, 1.0)
+    }
+
+    /// Consume the button's press for the rest of the frame, hiding the bindings that pressed it
+    /// from the maps below this one. This only matters for maps that don't
+    /// [consume](Self::set_consumes) all of their inputs, and should be called before the maps
+    /// below are updated.
+    pub fn consume(&self, name: &str) {
+        let (Some(contexts), Some(bindings)) = (self.contexts(), self.visible_bindings(name))
+        else {
+            return;
+        };
+        let pressed = bindings.into_iter().filter(|b| b.pressed(&self.0.source));
+        contexts.consume(self, pressed);
+    }
+}
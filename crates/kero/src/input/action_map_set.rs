@@ -0,0 +1,186 @@
+use crate::core::Context;
+use crate::input::{ActionMap, Binding, GamepadAxis, VirtualSource};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use thiserror::Error;
+
+/// The actions and settings of an [`ActionMap`], as stored in an [`ActionMapSet`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionMapData {
+    /// See [`ActionMap::set_blocking`].
+    pub blocking: bool,
+
+    /// See [`ActionMap::set_consumes`].
+    pub consumes: bool,
+
+    /// Bindings of each of the map's buttons.
+    pub buttons: BTreeMap<String, Vec<Binding>>,
+
+    /// Each of the map's axes.
+    pub axes: BTreeMap<String, ActionAxisData>,
+}
+
+impl Default for ActionMapData {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            blocking: false,
+            consumes: true,
+            buttons: BTreeMap::new(),
+            axes: BTreeMap::new(),
+        }
+    }
+}
+
+/// A [`VirtualAxis`](crate::input::VirtualAxis) of an [`ActionMapData`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionAxisData {
+    /// The gamepad axis.
+    pub axis: Option<GamepadAxis>,
+
+    /// Bindings of the negative button.
+    pub neg: Vec<Binding>,
+
+    /// Bindings of the positive button.
+    pub pos: Vec<Binding>,
+}
+
+/// Error loading or saving an [`ActionMapSet`].
+#[derive(Debug, Error)]
+pub enum ActionMapSetError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A collection of named [action maps](ActionMap) that can be loaded from and saved to a JSON
+/// file, so a game's input contexts can be defined as data:
+///
+/// ```json
+/// {
+///   "gameplay": {
+///     "buttons": {
+///       "jump": [{ "Key": "Space" }, { "Button": "South" }],
+///       "pause": [{ "Key": "Escape" }, { "Button": "Start" }]
+///     },
+///     "axes": {
+///       "move": { "axis": "LeftX", "neg": [{ "Key": "ArrowLeft" }], "pos": [{ "Key": "ArrowRight" }] }
+///     }
+///   },
+///   "pause": {
+///     "blocking": true,
+///     "buttons": {
+///       "back": [{ "Key": "Escape" }, { "Button": "East" }]
+///     }
+///   }
+/// }
+/// ```
+///
+/// ```ignore
+/// let maps = ActionMapSet::load_from_file("assets/input.json")?;
+/// let source = VirtualSource::last_active(ctx);
+/// let gameplay = maps.create("gameplay", &source).unwrap();
+/// ctx.input_contexts.push(&gameplay);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ActionMapSet(BTreeMap<String, ActionMapData>);
+
+impl ActionMapSet {
+    /// Create an empty set.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The map data with the name.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&ActionMapData> {
+        self.0.get(name)
+    }
+
+    /// Replace the map data with the name.
+    #[inline]
+    pub fn set(&mut self, name: impl Into<String>, data: ActionMapData) {
+        self.0.insert(name.into(), data);
+    }
+
+    /// Remove the map data with the name, returning it if it existed.
+    #[inline]
+    pub fn remove(&mut self, name: &str) -> Option<ActionMapData> {
+        self.0.remove(name)
+    }
+
+    /// Names of all the maps in the set, in sorted order.
+    #[inline]
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Store the map's current actions and settings in the set under its name.
+    #[inline]
+    pub fn capture(&mut self, map: &ActionMap) {
+        self.set(map.name(), map.to_data());
+    }
+
+    /// Create the map with the name, or none if the set has no map with the name.
+    #[inline]
+    pub fn create(&self, name: &str, source: &VirtualSource) -> Option<ActionMap> {
+        let data = self.0.get(name)?;
+        Some(ActionMap::from_data(name, source, data))
+    }
+
+    /// Create every map in the set, in sorted order.
+    #[inline]
+    pub fn create_all(&self, source: &VirtualSource) -> Vec<ActionMap> {
+        self.0
+            .iter()
+            .map(|(name, data)| ActionMap::from_data(name.as_str(), source, data))
+            .collect()
+    }
+
+    /// Load a set as JSON from the reader.
+    #[inline]
+    pub fn load<R: Read>(r: R) -> Result<Self, ActionMapSetError> {
+        Ok(serde_json::from_reader(r)?)
+    }
+
+    /// Load a set from a JSON file.
+    #[inline]
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, ActionMapSetError> {
+        Self::load(BufReader::new(File::open(path)?))
+    }
+
+    /// Load a set from a JSON file in the game's [config directory](Context::config_dir).
+    #[inline]
+    pub fn load_config(ctx: &Context, file_name: &str) -> Result<Self, ActionMapSetError> {
+        Self::load_from_file(ctx.config_dir().join(file_name))
+    }
+
+    /// Save the set as JSON to the writer.
+    pub fn save<W: Write>(&self, mut w: W) -> Result<(), ActionMapSetError> {
+        serde_json::to_writer_pretty(&mut w, self)?;
+        w.flush()?;
+        Ok(())
+    }
+
+    /// Save the set to a JSON file.
+    #[inline]
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ActionMapSetError> {
+        self.save(BufWriter::new(File::create(path)?))
+    }
+
+    /// Save the set to a JSON file in the game's [config directory](Context::config_dir),
+    /// creating the directory if it doesn't exist.
+    pub fn save_config(&self, ctx: &Context, file_name: &str) -> Result<(), ActionMapSetError> {
+        std::fs::create_dir_all(ctx.config_dir())?;
+        self.save_to_file(ctx.config_dir().join(file_name))
+    }
+}
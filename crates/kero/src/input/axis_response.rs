@@ -0,0 +1,135 @@
+use crate::math::{Vec2F, vec2};
+
+/// Shapes how far an axis is pushed once its deadzones have been removed, mapping `0.0..=1.0` to
+/// `0.0..=1.0`.
+#[derive(Debug, Copy, Clone, Default)]
+pub enum ResponseCurve {
+    /// The output is the same as the input.
+    #[default]
+    Linear,
+
+    /// The output is the input squared, giving finer control near the center.
+    Quadratic,
+
+    /// The output is the input cubed, giving even finer control near the center.
+    Cubic,
+
+    /// The output is calculated by the function.
+    Custom(fn(f32) -> f32),
+}
+
+impl ResponseCurve {
+    /// Apply the curve to a value from `0.0` to `1.0`.
+    #[inline]
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::Quadratic => t * t,
+            Self::Cubic => t * t * t,
+            Self::Custom(f) => f(t),
+        }
+    }
+}
+
+/// Deadzones and a response curve that clean up raw gamepad axis values.
+///
+/// Worn or cheap sticks rarely rest at exactly zero, so a small deadzone around the center keeps
+/// them from drifting. Values between the deadzones are rescaled so the output still smoothly
+/// covers the full range.
+///
+/// - The radial deadzone ignores a stick while it's within this distance of the center.
+/// - The axial deadzone ignores each axis of a stick while it's within this distance of the
+///   center, which makes it easier to push a stick in a straight line.
+/// - The outer deadzone treats a stick as fully pushed once it's within this distance of the
+///   edge, since many sticks never quite reach it.
+#[derive(Debug, Copy, Clone)]
+pub struct AxisResponse {
+    pub radial_deadzone: f32,
+    pub axial_deadzone: f32,
+    pub outer_deadzone: f32,
+    pub curve: ResponseCurve,
+}
+
+impl Default for AxisResponse {
+    #[inline]
+    fn default() -> Self {
+        Self::RAW
+    }
+}
+
+impl AxisResponse {
+    /// No deadzones and a linear curve, leaving values untouched.
+    pub const RAW: Self = Self {
+        radial_deadzone: 0.0,
+        axial_deadzone: 0.0,
+        outer_deadzone: 0.0,
+        curve: ResponseCurve::Linear,
+    };
+
+    /// Set the radial deadzone.
+    #[inline]
+    pub fn with_radial_deadzone(self, radial_deadzone: f32) -> Self {
+        Self {
+            radial_deadzone,
+            ..self
+        }
+    }
+
+    /// Set the axial deadzone.
+    #[inline]
+    pub fn with_axial_deadzone(self, axial_deadzone: f32) -> Self {
+        Self {
+            axial_deadzone,
+            ..self
+        }
+    }
+
+    /// Set the outer deadzone.
+    #[inline]
+    pub fn with_outer_deadzone(self, outer_deadzone: f32) -> Self {
+        Self {
+            outer_deadzone,
+            ..self
+        }
+    }
+
+    /// Set the response curve.
+    #[inline]
+    pub fn with_curve(self, curve: ResponseCurve) -> Self {
+        Self { curve, ..self }
+    }
+
+    /// Apply the response to a single axis value from `-1.0` to `1.0`. Since a single axis has
+    /// no other axis to measure a radius with, the larger of the radial and axial deadzones is
+    /// used.
+    pub fn apply_axis(&self, value: f32) -> f32 {
+        let inner = self.radial_deadzone.max(self.axial_deadzone);
+        let t = rescale(value.abs(), inner, 1.0 - self.outer_deadzone);
+        self.curve.apply(t).copysign(value)
+    }
+
+    /// Apply the response to a stick's value, with each axis from `-1.0` to `1.0`. The result's
+    /// length is never more than `1.0`.
+    pub fn apply_stick(&self, value: Vec2F) -> Vec2F {
+        // remove the axial deadzone from each axis
+        let axial = |v: f32| rescale(v.abs(), self.axial_deadzone, 1.0).copysign(v);
+        let value = vec2(axial(value.x), axial(value.y));
+
+        // remove the radial and outer deadzones from the stick's distance from the center
+        let len = value.len();
+        if len == 0.0 {
+            return Vec2F::ZERO;
+        }
+        let t = rescale(len, self.radial_deadzone, 1.0 - self.outer_deadzone);
+        value * (self.curve.apply(t) / len)
+    }
+}
+
+/// Rescale `val` so that `min` maps to `0.0` and `max` maps to `1.0`, clamping it to that range.
+#[inline]
+fn rescale(val: f32, min: f32, max: f32) -> f32 {
+    if max <= min {
+        return if val > min { 1.0 } else { 0.0 };
+    }
+    ((val - min) / (max - min)).clamp(0.0, 1.0)
+}
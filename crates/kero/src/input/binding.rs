@@ -0,0 +1,407 @@
+use crate::core::Context;
+use crate::input::{
+    GamepadAxis, GamepadButton, Key, Keyboard, MouseButton, VirtualButton, VirtualSource,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::ops::BitOr;
+use std::path::Path;
+use thiserror::Error;
+
+/// A single input that can trigger a [`VirtualButton`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Binding {
+    /// A keyboard key.
+    Key(Key),
+
+    /// A keyboard key that only triggers while the modifiers are held, like `Ctrl+S`.
+    Chord { modifiers: Modifiers, key: Key },
+
+    /// A gamepad button.
+    Button(GamepadButton),
+
+    /// A mouse button.
+    Mouse(MouseButton),
+
+    /// A gamepad axis that is down while it is past the threshold. A positive threshold is passed
+    /// when the axis is above it, and a negative one when the axis is below it.
+    Axis { axis: GamepadAxis, threshold: f32 },
+}
+
+impl From<Key> for Binding {
+    #[inline]
+    fn from(value: Key) -> Self {
+        Self::Key(value)
+    }
+}
+
+impl From<GamepadButton> for Binding {
+    #[inline]
+    fn from(value: GamepadButton) -> Self {
+        Self::Button(value)
+    }
+}
+
+impl From<MouseButton> for Binding {
+    #[inline]
+    fn from(value: MouseButton) -> Self {
+        Self::Mouse(value)
+    }
+}
+
+impl Binding {
+    /// A key that only triggers while the modifiers are held.
+    #[inline]
+    pub const fn chord(modifiers: Modifiers, key: Key) -> Self {
+        Self::Chord { modifiers, key }
+    }
+
+    /// A gamepad axis that is down while it is past the threshold.
+    #[inline]
+    pub const fn axis(axis: GamepadAxis, threshold: f32) -> Self {
+        Self::Axis { axis, threshold }
+    }
+
+    /// If both bindings listen to the same input. Axis bindings listen to the same input if they
+    /// are on the same axis and in the same direction.
+    pub fn same_input(&self, other: &Self) -> bool {
+        match (*self, *other) {
+            (
+                Self::Axis {
+                    axis: a,
+                    threshold: t,
+                },
+                Self::Axis {
+                    axis: b,
+                    threshold: u,
+                },
+            ) => a == b && (t < 0.0) == (u < 0.0),
+            (a, b) => a == b,
+        }
+    }
+
+    /// If the binding is down.
+    pub(crate) fn down(&self, source: &VirtualSource) -> bool {
+        match *self {
+            Self::Key(key) => source.keyboard().down(key),
+            Self::Chord { modifiers, key } => {
+                let kb = source.keyboard();
+                kb.down(key) && modifiers.down(kb)
+            }
+            Self::Button(btn) => source.read(|pad| pad.down(btn)).unwrap_or(false),
+            Self::Mouse(btn) => source.mouse().is_some_and(|mouse| mouse.down(btn)),
+            Self::Axis { axis, threshold } => source
+                .read(|pad| past(pad.axis(axis), threshold))
+                .unwrap_or(false),
+        }
+    }
+
+    /// If the binding was pressed this frame.
+    pub(crate) fn pressed(&self, source: &VirtualSource) -> bool {
+        match *self {
+            Self::Key(key) => source.keyboard().pressed(key),
+            Self::Chord { modifiers, key } => {
+                let kb = source.keyboard();
+                kb.pressed(key) && modifiers.down(kb)
+            }
+            Self::Button(btn) => source.read(|pad| pad.pressed(btn)).unwrap_or(false),
+            Self::Mouse(btn) => source.mouse().is_some_and(|mouse| mouse.pressed(btn)),
+            Self::Axis { axis, threshold } => source
+                .read(|pad| {
+                    past(pad.axis(axis), threshold) && !past(pad.prev_axis(axis), threshold)
+                })
+                .unwrap_or(false),
+        }
+    }
+
+    /// If the binding was released this frame.
+    pub(crate) fn released(&self, source: &VirtualSource) -> bool {
+        match *self {
+            Self::Key(key) => source.keyboard().released(key),
+            Self::Chord { modifiers, key } => {
+                let kb = source.keyboard();
+                was_down(kb, key) && modifiers.was_down(kb) && !(kb.down(key) && modifiers.down(kb))
+            }
+            Self::Button(btn) => source.read(|pad| pad.released(btn)).unwrap_or(false),
+            Self::Mouse(btn) => source.mouse().is_some_and(|mouse| mouse.released(btn)),
+            Self::Axis { axis, threshold } => source
+                .read(|pad| {
+                    !past(pad.axis(axis), threshold) && past(pad.prev_axis(axis), threshold)
+                })
+                .unwrap_or(false),
+        }
+    }
+
+    /// If the binding's state changed this frame.
+    pub(crate) fn changed(&self, source: &VirtualSource) -> bool {
+        match *self {
+            Self::Button(btn) => source.read(|pad| pad.btn_changed(btn)).unwrap_or(false),
+            _ => self.pressed(source) || self.released(source),
+        }
+    }
+
+    /// Value of the binding from `0.0` to `1.0`.
+    pub(crate) fn value(&self, source: &VirtualSource) -> f32 {
+        match *self {
+            Self::Button(btn) => source.read(|pad| pad.value(btn)).unwrap_or(0.0),
+            Self::Axis { axis, threshold } => source
+                .read(|pad| pad.axis(axis))
+                .filter(|&val| past(val, threshold))
+                .map_or(0.0, |val| val.abs().min(1.0)),
+            _ => {
+                if self.down(source) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// If any of the bindings are down.
+pub(crate) fn any_down(bindings: &[Binding], source: &VirtualSource) -> bool {
+    bindings.iter().any(|b| b.down(source))
+}
+
+/// If any of the bindings were pressed this frame, treating them as one button that doesn't count
+/// as pressed while another of its bindings was already down.
+pub(crate) fn any_pressed(bindings: &[Binding], source: &VirtualSource) -> bool {
+    let mut pressed = false;
+    for binding in bindings {
+        if binding.pressed(source) {
+            pressed = true;
+        } else if binding.down(source) {
+            return false;
+        }
+    }
+    pressed
+}
+
+/// If any of the bindings were released this frame, treating them as one button that doesn't
+/// count as released while another of its bindings is still down.
+pub(crate) fn any_released(bindings: &[Binding], source: &VirtualSource) -> bool {
+    let mut released = false;
+    for binding in bindings {
+        if binding.down(source) {
+            return false;
+        } else if binding.released(source) {
+            released = true;
+        }
+    }
+    released
+}
+
+/// The highest value of any of the bindings.
+pub(crate) fn max_value(bindings: &[Binding], source: &VirtualSource) -> f32 {
+    bindings.iter().map(|b| b.value(source)).fold(0.0, f32::max)
+}
+
+/// If the axis value is past the threshold, in the direction of the threshold.
+#[inline]
+fn past(val: f32, threshold: f32) -> bool {
+    if threshold < 0.0 {
+        val <= threshold
+    } else {
+        val >= threshold
+    }
+}
+
+/// If the key was down at the start of this frame.
+#[inline]
+fn was_down(kb: &Keyboard, key: Key) -> bool {
+    (kb.down(key) && !kb.pressed(key)) || kb.released(key)
+}
+
+/// Modifier keys that must be held for a [`Binding::Chord`]. Either the left or right version of a
+/// modifier key counts.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub cmd: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Self = Self {
+        ctrl: false,
+        shift: false,
+        alt: false,
+        cmd: false,
+    };
+    pub const CTRL: Self = Self {
+        ctrl: true,
+        ..Self::NONE
+    };
+    pub const SHIFT: Self = Self {
+        shift: true,
+        ..Self::NONE
+    };
+    pub const ALT: Self = Self {
+        alt: true,
+        ..Self::NONE
+    };
+    pub const CMD: Self = Self {
+        cmd: true,
+        ..Self::NONE
+    };
+
+    /// If all of the modifiers are down.
+    #[inline]
+    pub fn down(&self, kb: &Keyboard) -> bool {
+        (!self.ctrl || kb.ctrl())
+            && (!self.shift || kb.shift())
+            && (!self.alt || kb.alt())
+            && (!self.cmd || kb.cmd())
+    }
+
+    /// If all of the modifiers were down at the start of this frame.
+    fn was_down(&self, kb: &Keyboard) -> bool {
+        let either = |a, b| was_down(kb, a) || was_down(kb, b);
+        (!self.ctrl || either(Key::ControlLeft, Key::ControlRight))
+            && (!self.shift || either(Key::ShiftLeft, Key::ShiftRight))
+            && (!self.alt || either(Key::AltLeft, Key::AltRight))
+            && (!self.cmd || either(Key::SuperLeft, Key::SuperRight))
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self {
+            ctrl: self.ctrl || rhs.ctrl,
+            shift: self.shift || rhs.shift,
+            alt: self.alt || rhs.alt,
+            cmd: self.cmd || rhs.cmd,
+        }
+    }
+}
+
+/// Error loading or saving a [`BindingSet`].
+#[derive(Debug, Error)]
+pub enum BindingSetError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A named collection of bindings, such as the player's control settings.
+///
+/// Capture each [`VirtualButton`]'s bindings into the set by name, save it to the game's config
+/// directory, and then apply it back onto the buttons the next time the game starts:
+///
+/// ```ignore
+/// let mut bindings = BindingSet::load_config(ctx, "controls.json").unwrap_or_default();
+/// bindings.apply("jump", &jump);
+/// // ...the player rebinds jump...
+/// bindings.capture("jump", &jump);
+/// bindings.save_config(ctx, "controls.json")?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BindingSet(BTreeMap<String, Vec<Binding>>);
+
+impl BindingSet {
+    /// Create an empty binding set.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The bindings with the name, or none if there are none.
+    #[inline]
+    pub fn get(&self, name: &str) -> &[Binding] {
+        self.0.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Replace the bindings with the name.
+    #[inline]
+    pub fn set(&mut self, name: impl Into<String>, bindings: impl IntoIterator<Item = Binding>) {
+        self.0.insert(name.into(), bindings.into_iter().collect());
+    }
+
+    /// Remove the bindings with the name, returning them if they existed.
+    #[inline]
+    pub fn remove(&mut self, name: &str) -> Option<Vec<Binding>> {
+        self.0.remove(name)
+    }
+
+    /// Names of all the bindings in the set, in sorted order.
+    #[inline]
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Every binding in the set except the ones with the name. Pass these to
+    /// [`InputCapture::with_in_use`](crate::input::InputCapture::with_in_use) when rebinding `name`
+    /// so that it can't take another action's bindings.
+    #[inline]
+    pub fn in_use_except(&self, name: &str) -> impl Iterator<Item = Binding> {
+        self.0
+            .iter()
+            .filter(move |(n, _)| n.as_str() != name)
+            .flat_map(|(_, bindings)| bindings.iter().copied())
+    }
+
+    /// Store the button's current bindings in the set under the name.
+    #[inline]
+    pub fn capture(&mut self, name: impl Into<String>, button: &VirtualButton) {
+        self.set(name, button.bindings());
+    }
+
+    /// Replace the button's bindings with the ones stored under the name. If the set has no
+    /// bindings with the name, the button is left unchanged and this returns false.
+    pub fn apply(&self, name: &str, button: &VirtualButton) -> bool {
+        let Some(bindings) = self.0.get(name) else {
+            return false;
+        };
+        button.set_bindings(bindings.iter().copied());
+        true
+    }
+
+    /// Load a binding set as JSON from the reader.
+    #[inline]
+    pub fn load<R: Read>(r: R) -> Result<Self, BindingSetError> {
+        Ok(serde_json::from_reader(r)?)
+    }
+
+    /// Load a binding set from a JSON file.
+    #[inline]
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, BindingSetError> {
+        Self::load(BufReader::new(File::open(path)?))
+    }
+
+    /// Load a binding set from a JSON file in the game's [config directory](Context::config_dir).
+    #[inline]
+    pub fn load_config(ctx: &Context, file_name: &str) -> Result<Self, BindingSetError> {
+        Self::load_from_file(ctx.config_dir().join(file_name))
+    }
+
+    /// Save the binding set as JSON to the writer.
+    pub fn save<W: Write>(&self, mut w: W) -> Result<(), BindingSetError> {
+        serde_json::to_writer_pretty(&mut w, self)?;
+        w.flush()?;
+        Ok(())
+    }
+
+    /// Save the binding set to a JSON file.
+    #[inline]
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), BindingSetError> {
+        self.save(BufWriter::new(File::create(path)?))
+    }
+
+    /// Save the binding set to a JSON file in the game's [config directory](Context::config_dir),
+    /// creating the directory if it doesn't exist.
+    pub fn save_config(&self, ctx: &Context, file_name: &str) -> Result<(), BindingSetError> {
+        std::fs::create_dir_all(ctx.config_dir())?;
+        self.save_to_file(ctx.config_dir().join(file_name))
+    }
+}
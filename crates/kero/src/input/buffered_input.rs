@@ -0,0 +1,114 @@
+use crate::core::Time;
+
+#[cfg(feature = "lua")]
+pub type BufferedInputRef = mlua::UserDataRef<BufferedInput>;
+#[cfg(feature = "lua")]
+pub type BufferedInputMut = mlua::UserDataRefMut<BufferedInput>;
+
+/// How long a [`BufferedInput`] stays active after it was last triggered.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BufferWindow {
+    /// A duration in seconds, measured with [`Time::since_startup`].
+    Seconds(f32),
+
+    /// A number of frames, measured with [`Time::frame`].
+    Frames(u32),
+}
+
+impl From<f32> for BufferWindow {
+    #[inline]
+    fn from(value: f32) -> Self {
+        Self::Seconds(value)
+    }
+}
+
+impl From<u32> for BufferWindow {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self::Frames(value)
+    }
+}
+
+/// Remembers that something happened for a short window of time afterwards, until it is consumed.
+///
+/// This is useful for making controls feel forgiving. For example, buffering a jump press so it
+/// still counts if it happens just before the player lands, or giving the player "coyote time"
+/// where they can still jump just after walking off a ledge:
+///
+/// ```ignore
+/// // every update
+/// self.coyote.update(&ctx.time, self.is_grounded);
+/// if self.jump_btn.pressed_buffered(0.1) && self.coyote.active(&ctx.time) {
+///     self.jump_btn.consume_press();
+///     self.coyote.consume();
+///     // jump!
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BufferedInput {
+    window: BufferWindow,
+    last: Option<(f32, u64)>,
+}
+
+impl BufferedInput {
+    /// Create a buffer that stays active for the window after it was last triggered.
+    #[inline]
+    pub fn new(window: impl Into<BufferWindow>) -> Self {
+        Self {
+            window: window.into(),
+            last: None,
+        }
+    }
+
+    /// How long the buffer stays active after it was last triggered.
+    #[inline]
+    pub fn window(&self) -> BufferWindow {
+        self.window
+    }
+
+    /// Set how long the buffer stays active after it was last triggered.
+    #[inline]
+    pub fn set_window(&mut self, window: impl Into<BufferWindow>) {
+        self.window = window.into();
+    }
+
+    /// Trigger the buffer if `condition` is true. Call this every update with a condition, such
+    /// as whether the player is standing on the ground, to keep the buffer active for a window of
+    /// time after the condition stops being true.
+    #[inline]
+    pub fn update(&mut self, time: &Time, condition: bool) {
+        if condition {
+            self.trigger(time);
+        }
+    }
+
+    /// Trigger the buffer, making it active for its window of time starting now.
+    #[inline]
+    pub fn trigger(&mut self, time: &Time) {
+        self.last = Some((time.since_startup(), time.frame()));
+    }
+
+    /// If the buffer was triggered within its window of time and hasn't been consumed.
+    #[inline]
+    pub fn active(&self, time: &Time) -> bool {
+        self.active_within(time, self.window)
+    }
+
+    /// If the buffer was triggered within the window of time and hasn't been consumed.
+    #[inline]
+    pub fn active_within(&self, time: &Time, window: impl Into<BufferWindow>) -> bool {
+        let Some((last_time, last_frame)) = self.last else {
+            return false;
+        };
+        match window.into() {
+            BufferWindow::Seconds(secs) => time.since_startup() - last_time <= secs,
+            BufferWindow::Frames(frames) => time.frame() - last_frame <= frames as u64,
+        }
+    }
+
+    /// Consume the buffer, making it inactive until it is triggered again.
+    #[inline]
+    pub fn consume(&mut self) {
+        self.last = None;
+    }
+}
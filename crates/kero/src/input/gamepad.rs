@@ -1,10 +1,15 @@
-use crate::input::{GamepadButton, GamepadStatus};
+use crate::input::{
+    AxisResponse, GamepadButton, GamepadFrame, GamepadStatus, GamepadStick, RumbleEffect,
+};
+use crate::math::{Vec2F, vec2};
+use gilrs::ff::{BaseEffect, BaseEffectType, EffectBuilder, Repeat, Replay, Ticks};
 use gilrs::{GamepadId, Gilrs};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
-use std::{rc::Rc, time::SystemTime};
-use strum::EnumCount;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+use strum::{EnumCount, VariantArray};
 
 use super::GamepadAxis;
 
@@ -36,17 +41,22 @@ impl PartialOrd for Gamepad {
 }
 
 struct State {
-    id: GamepadId,
+    id: Option<GamepadId>,
+    slot: u32,
     name: String,
     status: Cell<GamepadStatus>,
     down: Cell<[bool; GamepadButton::COUNT]>,
     btn_value: Cell<[f32; GamepadButton::COUNT]>,
     axis_value: Cell<[f32; GamepadAxis::COUNT]>,
-    phases: [Phase; 2],
+    stick_response: Cell<[AxisResponse; GamepadStick::COUNT]>,
+    phases: [Phase; 3],
     phase: Cell<usize>,
     last_update: Cell<SystemTime>,
     connect_time: SystemTime,
     connected: Cell<bool>,
+    gilrs: Option<Rc<RefCell<Gilrs>>>,
+    rumble_scale: Rc<Cell<f32>>,
+    rumbles: RefCell<Vec<RumbleEffect>>,
 }
 
 #[derive(Clone)]
@@ -57,6 +67,7 @@ struct Phase {
     repeated: Cell<[bool; GamepadButton::COUNT]>,
     btn_changed: Cell<[bool; GamepadButton::COUNT]>,
     axis_changed: Cell<[bool; GamepadAxis::COUNT]>,
+    axis_start: Cell<[f32; GamepadAxis::COUNT]>,
 }
 
 impl Default for Phase {
@@ -68,33 +79,43 @@ impl Default for Phase {
             repeated: Cell::new([false; _]),
             btn_changed: Cell::new([false; _]),
             axis_changed: Cell::new([false; _]),
+            axis_start: Cell::new([0.0; _]),
         }
     }
 }
 
 impl Gamepad {
     pub(crate) fn new(
-        id: GamepadId,
+        id: Option<GamepadId>,
+        slot: u32,
         name: String,
         status: GamepadStatus,
         connect_time: SystemTime,
+        gilrs: Option<Rc<RefCell<Gilrs>>>,
+        rumble_scale: Rc<Cell<f32>>,
     ) -> Self {
         Self(Rc::new(State {
             id,
+            slot,
             name,
             status: Cell::new(status),
             down: Cell::new([false; _]),
             btn_value: Cell::new([0.0; _]),
             axis_value: Cell::new([0.0; _]),
+            stick_response: Cell::new([AxisResponse::RAW; _]),
             phases: std::array::repeat(Phase::default()),
             phase: Cell::new(0),
             last_update: Cell::new(connect_time),
             connect_time,
             connected: Cell::new(true),
+            gilrs,
+            rumble_scale,
+            rumbles: RefCell::new(Vec::new()),
         }))
     }
 
     pub(crate) fn disconnect(&self) {
+        self.stop_rumble();
         self.0.connected.set(false);
         self.0.status.set(GamepadStatus::Unknown);
         self.0.down.set([false; _]);
@@ -107,6 +128,7 @@ impl Gamepad {
             phase.released.set([false; _]);
             phase.btn_changed.set([false; _]);
             phase.axis_changed.set([false; _]);
+            phase.axis_start.set([0.0; _]);
         }
     }
 
@@ -175,27 +197,154 @@ impl Gamepad {
         Cell::as_array_of_cells(&self.phase().btn_changed)[btn as usize].get()
     }
 
+    /// All buttons that are currently down.
+    #[inline]
+    pub fn currently_down(&self) -> impl Iterator<Item = GamepadButton> {
+        GamepadButton::VARIANTS
+            .iter()
+            .copied()
+            .filter(|btn| self.down(*btn))
+    }
+
+    /// All buttons that were pressed this frame.
+    #[inline]
+    pub fn currently_pressed(&self) -> impl Iterator<Item = GamepadButton> {
+        GamepadButton::VARIANTS
+            .iter()
+            .copied()
+            .filter(|btn| self.pressed(*btn))
+    }
+
     /// The button value from `0.0` (fully up) to `1.0` (fully down).
     #[inline]
     pub fn value(&self, btn: GamepadButton) -> f32 {
         Cell::as_array_of_cells(&self.0.btn_value)[btn as usize].get()
     }
 
-    /// The axis value from `-1.0` to `1.0`.
+    /// The raw axis value from `-1.0` to `1.0`, without any deadzones applied. Use
+    /// [`stick`](Self::stick) to read a thumbstick with its response applied.
     #[inline]
     pub fn axis(&self, axis: GamepadAxis) -> f32 {
         Cell::as_array_of_cells(&self.0.axis_value)[axis as usize].get()
     }
 
+    /// The stick's value with its [response](Self::set_stick_response) applied. Its length is
+    /// never more than `1.0`.
+    #[inline]
+    pub fn stick(&self, stick: GamepadStick) -> Vec2F {
+        let value = vec2(self.axis(stick.x_axis()), self.axis(stick.y_axis()));
+        self.stick_response(stick).apply_stick(value)
+    }
+
+    /// The deadzones and response curve applied to the stick. By default, values are left raw.
+    #[inline]
+    pub fn stick_response(&self, stick: GamepadStick) -> AxisResponse {
+        Cell::as_array_of_cells(&self.0.stick_response)[stick as usize].get()
+    }
+
+    #[inline]
+    pub fn set_stick_response(&self, stick: GamepadStick, response: AxisResponse) {
+        Cell::as_array_of_cells(&self.0.stick_response)[stick as usize].set(response);
+    }
+
+    /// The axis value at the start of this frame, from `-1.0` to `1.0`.
+    #[inline]
+    pub fn prev_axis(&self, axis: GamepadAxis) -> f32 {
+        Cell::as_array_of_cells(&self.phase().axis_start)[axis as usize].get()
+    }
+
     /// If the axis changed this frame.
     #[inline]
     pub fn axis_changed(&self, axis: GamepadAxis) -> bool {
         Cell::as_array_of_cells(&self.phase().axis_changed)[axis as usize].get()
     }
 
+    /// If the gamepad supports rumble.
+    pub fn supports_rumble(&self) -> bool {
+        let (Some(id), Some(gilrs)) = (self.0.id, &self.0.gilrs) else {
+            return false;
+        };
+        gilrs
+            .borrow()
+            .connected_gamepad(id)
+            .is_some_and(|pad| pad.is_ff_supported())
+    }
+
+    /// Rumble the gamepad for `duration` seconds. The `strong` motor is the low frequency one,
+    /// and the `weak` motor is the high frequency one, each from `0.0` (off) to `1.0` (full
+    /// strength), and scaled by [`Gamepads::rumble_scale`](super::Gamepads::rumble_scale).
+    ///
+    /// Returns `None` if the gamepad doesn't support rumble, or if `duration` is not finite or
+    /// too long to schedule. Negative durations are treated as `0.0`.
+    pub fn rumble(&self, strong: f32, weak: f32, duration: f32) -> Option<RumbleEffect> {
+        let (Some(id), Some(gilrs)) = (self.0.id, &self.0.gilrs) else {
+            return None;
+        };
+        if !self.0.connected.get() {
+            return None;
+        }
+        let scale = self.0.rumble_scale.get();
+        let magnitude = |val: f32| ((val * scale).clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+        if !duration.is_finite() {
+            return None;
+        }
+        let duration = Duration::try_from_secs_f32(duration.max(0.0)).ok()?;
+        let end = Instant::now().checked_add(duration)?;
+        let ticks = Ticks::from_ms(duration.as_millis().min(u32::MAX as u128) as u32);
+        let scheduling = Replay {
+            play_for: ticks,
+            ..Default::default()
+        };
+        let effect = EffectBuilder::new()
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong {
+                    magnitude: magnitude(strong),
+                },
+                scheduling,
+                ..Default::default()
+            })
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Weak {
+                    magnitude: magnitude(weak),
+                },
+                scheduling,
+                ..Default::default()
+            })
+            .gamepads(&[id])
+            .repeat(Repeat::For(ticks))
+            .finish(&mut gilrs.borrow_mut())
+            .and_then(|effect| effect.play().map(|_| effect));
+        match effect {
+            Ok(effect) => {
+                let effect = RumbleEffect::new(effect, end);
+                self.0.rumbles.borrow_mut().push(effect.clone());
+                Some(effect)
+            }
+            Err(err) => {
+                log::debug!("failed to rumble gamepad: {err}");
+                None
+            }
+        }
+    }
+
+    /// Stop all of the gamepad's rumble effects.
+    pub fn stop_rumble(&self) {
+        for effect in self.0.rumbles.take() {
+            effect.stop();
+        }
+    }
+
+    /// Let go of rumble effects that have finished playing.
+    pub(crate) fn remove_finished_rumbles(&self) {
+        self.0.rumbles.borrow_mut().retain(RumbleEffect::is_playing);
+    }
+
     #[inline]
     pub(crate) fn update_status(&self, gilrs: &Gilrs, time: SystemTime) {
-        let power_info = gilrs.connected_gamepad(self.0.id).unwrap().power_info();
+        let power_info = gilrs
+            .connected_gamepad(self.0.id.unwrap())
+            .unwrap()
+            .power_info();
         self.0.status.set(GamepadStatus::from(power_info));
         self.0.last_update.set(time);
     }
@@ -239,6 +388,45 @@ impl Gamepad {
         self.0.phase.set(1);
     }
 
+    pub(crate) fn set_fixed_update_phase(&self) {
+        self.0.phase.set(2);
+    }
+
+    /// The gamepad's current state, for recording.
+    pub(crate) fn frame(&self) -> GamepadFrame {
+        let phase = self.phase();
+        GamepadFrame {
+            slot: self.0.slot,
+            name: self.0.name.clone(),
+            was_connected: phase.was_connected.get(),
+            down: self.0.down.get(),
+            pressed: phase.pressed.get(),
+            released: phase.released.get(),
+            repeated: phase.repeated.get(),
+            btn_changed: phase.btn_changed.get(),
+            btn_value: self.0.btn_value.get(),
+            axis_value: self.0.axis_value.get(),
+            axis_changed: phase.axis_changed.get(),
+            axis_start: phase.axis_start.get(),
+        }
+    }
+
+    /// Replace the gamepad's state with a recorded one.
+    pub(crate) fn apply_frame(&self, frame: &GamepadFrame) {
+        self.0.down.set(frame.down);
+        self.0.btn_value.set(frame.btn_value);
+        self.0.axis_value.set(frame.axis_value);
+        for phase in &self.0.phases {
+            phase.was_connected.set(frame.was_connected);
+            phase.pressed.set(frame.pressed);
+            phase.released.set(frame.released);
+            phase.repeated.set(frame.repeated);
+            phase.btn_changed.set(frame.btn_changed);
+            phase.axis_changed.set(frame.axis_changed);
+            phase.axis_start.set(frame.axis_start);
+        }
+    }
+
     pub(crate) fn clear_phase(&self) {
         let phase = self.phase();
         phase.was_connected.set(false);
@@ -247,5 +435,6 @@ impl Gamepad {
         phase.released.set([false; _]);
         phase.btn_changed.set([false; _]);
         phase.axis_changed.set([false; _]);
+        phase.axis_start.set(self.0.axis_value.get());
     }
 }
@@ -0,0 +1,44 @@
+use crate::input::GamepadAxis;
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, FromRepr, VariantArray};
+
+/// A gamepad thumbstick.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumCount,
+    FromRepr,
+    VariantArray,
+)]
+pub enum GamepadStick {
+    Left,
+    Right,
+}
+
+impl GamepadStick {
+    /// The stick's horizontal axis.
+    #[inline]
+    pub const fn x_axis(self) -> GamepadAxis {
+        match self {
+            Self::Left => GamepadAxis::LeftX,
+            Self::Right => GamepadAxis::RightX,
+        }
+    }
+
+    /// The stick's vertical axis.
+    #[inline]
+    pub const fn y_axis(self) -> GamepadAxis {
+        match self {
+            Self::Left => GamepadAxis::LeftY,
+            Self::Right => GamepadAxis::RightY,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Gamepad, GamepadAxis, GamepadButton, GamepadStatus};
+use super::{Gamepad, GamepadAxis, GamepadButton, GamepadFrame, GamepadStatus};
 use crate::core::Context;
 use fnv::FnvHashMap;
 use gilrs::{Event, EventType, GamepadId, Gilrs};
@@ -22,8 +22,11 @@ impl Debug for Gamepads {
 }
 
 struct State {
-    gilrs: Option<RefCell<Gilrs>>,
+    gilrs: Option<Rc<RefCell<Gilrs>>>,
+    rumble_scale: Rc<Cell<f32>>,
     gamepads: RefCell<FnvHashMap<GamepadId, Pad>>,
+    replaying: Cell<bool>,
+    replay_pads: RefCell<FnvHashMap<u32, Pad>>,
     last_active: Cell<SystemTime>,
 }
 
@@ -37,8 +40,11 @@ struct Pad {
 impl Gamepads {
     pub(crate) fn new() -> Self {
         Self(Rc::new(State {
-            gilrs: Gilrs::new().ok().map(RefCell::new),
+            gilrs: Gilrs::new().ok().map(|g| Rc::new(RefCell::new(g))),
+            rumble_scale: Rc::new(Cell::new(1.0)),
             gamepads: RefCell::new(FnvHashMap::default()),
+            replaying: Cell::new(false),
+            replay_pads: RefCell::new(FnvHashMap::default()),
             last_active: Cell::new(SystemTime::UNIX_EPOCH),
         }))
     }
@@ -49,6 +55,12 @@ impl Gamepads {
             return;
         };
         let mut gamepads = self.0.gamepads.borrow_mut();
+
+        // let go of rumble effects that have finished playing
+        for pad in gamepads.values() {
+            pad.pad.remove_finished_rumbles();
+        }
+
         while let Some(Event {
             id, event, time, ..
         }) = gilrs.next_event()
@@ -99,7 +111,15 @@ impl Gamepads {
                     let pad = gilrs.connected_gamepad(id).unwrap();
                     let name = pad.name().to_string();
                     let status = GamepadStatus::from(pad.power_info());
-                    let pad = Gamepad::new(id, name, status, time);
+                    let pad = Gamepad::new(
+                        Some(id),
+                        usize::from(id) as u32,
+                        name,
+                        status,
+                        time,
+                        self.0.gilrs.clone(),
+                        self.0.rumble_scale.clone(),
+                    );
                     let pad = Pad {
                         #[cfg(feature = "lua")]
                         userdata: ctx.lua.upgrade().create_userdata(pad.clone()).unwrap(),
@@ -118,80 +138,157 @@ impl Gamepads {
         }
     }
 
+    /// Collect values from the connected gamepads, which are the replayed ones while input is
+    /// being replayed.
+    fn collect<T>(&self, f: impl FnMut(&Pad) -> Option<T>) -> SmallVec<[T; 8]> {
+        if self.0.replaying.get() {
+            self.0.replay_pads.borrow().values().filter_map(f).collect()
+        } else {
+            self.0.gamepads.borrow().values().filter_map(f).collect()
+        }
+    }
+
+    /// Every gamepad, including the real ones hidden by a replay.
+    fn every_pad(&self) -> SmallVec<[Gamepad; 8]> {
+        let gamepads = self.0.gamepads.borrow();
+        let replay_pads = self.0.replay_pads.borrow();
+        gamepads
+            .values()
+            .chain(replay_pads.values())
+            .map(|pad| pad.pad.clone())
+            .collect()
+    }
+
     #[inline]
     pub(crate) fn clear_phase(&self) {
-        for pad in self.0.gamepads.borrow().values() {
-            pad.pad.clear_phase();
+        for pad in self.every_pad() {
+            pad.clear_phase();
         }
     }
 
     #[inline]
     pub(crate) fn set_update_phase(&self) {
-        for pad in self.0.gamepads.borrow().values() {
-            pad.pad.set_update_phase();
+        for pad in self.every_pad() {
+            pad.set_update_phase();
         }
     }
 
     #[inline]
     pub(crate) fn set_render_phase(&self) {
-        for pad in self.0.gamepads.borrow().values() {
-            pad.pad.set_render_phase();
+        for pad in self.every_pad() {
+            pad.set_render_phase();
+        }
+    }
+
+    #[inline]
+    pub(crate) fn set_fixed_update_phase(&self) {
+        for pad in self.every_pad() {
+            pad.set_fixed_update_phase();
+        }
+    }
+
+    /// Replace the real gamepads with replayed ones.
+    pub(crate) fn start_replay(&self) {
+        self.0.replay_pads.borrow_mut().clear();
+        self.0.replaying.set(true);
+    }
+
+    /// Give control back to the real gamepads.
+    pub(crate) fn stop_replay(&self) {
+        for (_, pad) in self.0.replay_pads.borrow_mut().drain() {
+            pad.pad.disconnect();
+        }
+        self.0.replaying.set(false);
+    }
+
+    /// The state of every connected gamepad, for recording.
+    pub(crate) fn frames(&self) -> Vec<GamepadFrame> {
+        let mut frames = self.collect(|pad| Some(pad.pad.frame())).into_vec();
+        frames.sort_by_key(|frame| frame.slot);
+        frames
+    }
+
+    /// Replace the replayed gamepads with recorded ones, connecting and disconnecting them to
+    /// match the recording.
+    #[allow(unused_variables)]
+    pub(crate) fn apply_frames(&self, ctx: &Context, frames: &[GamepadFrame]) {
+        let mut pads = self.0.replay_pads.borrow_mut();
+        pads.retain(|slot, pad| {
+            let keep = frames.iter().any(|frame| frame.slot == *slot);
+            if !keep {
+                pad.pad.disconnect();
+            }
+            keep
+        });
+        for frame in frames {
+            let pad = pads.entry(frame.slot).or_insert_with(|| {
+                let pad = Gamepad::new(
+                    None,
+                    frame.slot,
+                    frame.name.clone(),
+                    GamepadStatus::Unknown,
+                    SystemTime::now(),
+                    None,
+                    self.0.rumble_scale.clone(),
+                );
+                Pad {
+                    #[cfg(feature = "lua")]
+                    userdata: ctx.lua.upgrade().create_userdata(pad.clone()).unwrap(),
+                    pad,
+                }
+            });
+            pad.pad.apply_frame(frame);
+        }
+    }
+
+    /// How strongly gamepads rumble, from `0.0` (never) to `1.0` (full strength). Every rumble's
+    /// strength is multiplied by this, so it can be exposed as an accessibility setting.
+    #[inline]
+    pub fn rumble_scale(&self) -> f32 {
+        self.0.rumble_scale.get()
+    }
+
+    #[inline]
+    pub fn set_rumble_scale(&self, scale: f32) {
+        self.0.rumble_scale.set(scale.clamp(0.0, 1.0));
+    }
+
+    /// Stop all rumble effects on every gamepad.
+    pub fn stop_rumble(&self) {
+        for pad in self.every_pad() {
+            pad.stop_rumble();
         }
     }
 
     /// How many gamepads are connected.
     #[inline]
     pub fn count(&self) -> usize {
-        self.0.gamepads.borrow().len()
+        self.collect(|_| Some(())).len()
     }
 
     /// All connected gamepads.
     #[inline]
     pub fn all(&self) -> impl Iterator<Item = Gamepad> {
-        self.0
-            .gamepads
-            .borrow()
-            .values()
-            .map(|pad| pad.pad.clone())
-            .collect::<SmallVec<[_; 8]>>()
-            .into_iter()
+        self.collect(|pad| Some(pad.pad.clone())).into_iter()
     }
 
     #[cfg(feature = "lua")]
     #[inline]
     pub fn all_lua(&self) -> impl Iterator<Item = mlua::AnyUserData> {
-        self.0
-            .gamepads
-            .borrow()
-            .values()
-            .map(|pad| pad.userdata.clone())
-            .collect::<SmallVec<[_; 8]>>()
-            .into_iter()
+        self.collect(|pad| Some(pad.userdata.clone())).into_iter()
     }
 
     /// All gamepads that connected this frame.
     #[inline]
     pub fn newly_connected(&self) -> impl Iterator<Item = Gamepad> {
-        self.0
-            .gamepads
-            .borrow()
-            .values()
-            .filter(|pad| pad.pad.was_connected())
-            .map(|pad| pad.pad.clone())
-            .collect::<SmallVec<[_; 8]>>()
+        self.collect(|pad| pad.pad.was_connected().then(|| pad.pad.clone()))
             .into_iter()
     }
 
     #[cfg(feature = "lua")]
     #[inline]
     pub(crate) fn newly_connected_lua(&self) -> impl Iterator<Item = mlua::AnyUserData> {
-        self.0
-            .gamepads
-            .borrow()
-            .values()
-            .filter(|pad| pad.pad.was_connected())
-            .map(|pad| pad.userdata.clone())
-            .collect::<SmallVec<[_; 8]>>()
+        self.collect(|pad| pad.pad.was_connected().then(|| pad.userdata.clone()))
             .into_iter()
     }
 
@@ -204,22 +301,17 @@ impl Gamepads {
     /// The most recently active gamepad.
     #[inline]
     pub fn last_active(&self) -> Option<Gamepad> {
-        self.0
-            .gamepads
-            .borrow()
-            .values()
-            .max_by_key(|pad| pad.pad.last_update())
-            .map(|pad| pad.pad.clone())
+        self.collect(|pad| Some(pad.pad.clone()))
+            .into_iter()
+            .max_by_key(|pad| pad.last_update())
     }
 
     #[cfg(feature = "lua")]
     #[inline]
     pub(crate) fn last_active_lua(&self) -> Option<mlua::AnyUserData> {
-        self.0
-            .gamepads
-            .borrow()
-            .values()
-            .max_by_key(|pad| pad.pad.last_update())
-            .map(|pad| pad.userdata.clone())
+        self.collect(|pad| Some((pad.pad.last_update(), pad.userdata.clone())))
+            .into_iter()
+            .max_by_key(|(time, _)| *time)
+            .map(|(_, userdata)| userdata)
     }
 }
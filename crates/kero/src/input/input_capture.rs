@@ -0,0 +1,180 @@
+use crate::core::Context;
+use crate::input::{Binding, GamepadAxis, Gamepads, Key, Keyboard, Modifiers, Mouse};
+use strum::VariantArray;
+
+/// The result of an [`InputCapture`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CaptureResult {
+    /// The player pushed an input that isn't in use.
+    Bound(Binding),
+
+    /// The player pushed an input that is already in use. The capture keeps listening, so this
+    /// can be used to show a message while waiting for another input.
+    InUse(Binding),
+
+    /// The player pushed the cancel input.
+    Cancelled,
+}
+
+/// Listens for the next key, mouse button, gamepad button, or gamepad axis the player pushes, for
+/// rebinding controls in an options menu.
+///
+/// Call [`poll`](Self::poll) once per update until it returns a result. The capture waits for all
+/// inputs to be let go of before it starts listening, so the press that opened the prompt isn't
+/// captured. Keys pressed while holding modifiers are captured as [chords](Binding::Chord), and
+/// modifier keys on their own are captured when they are released.
+#[derive(Debug, Clone)]
+pub struct InputCapture {
+    keyboard: Keyboard,
+    mouse: Mouse,
+    gamepads: Gamepads,
+    cancel: Option<Binding>,
+    in_use: Vec<Binding>,
+    axis_threshold: f32,
+    chords: bool,
+    ready: bool,
+    modifier: Option<Key>,
+}
+
+impl InputCapture {
+    /// Create a capture that listens to all of the game's input devices.
+    #[inline]
+    pub fn new(ctx: &Context) -> Self {
+        Self::new_ext(&ctx.keyboard, &ctx.mouse, &ctx.gamepads)
+    }
+
+    /// Create a capture that listens to the input devices.
+    pub fn new_ext(keyboard: &Keyboard, mouse: &Mouse, gamepads: &Gamepads) -> Self {
+        Self {
+            keyboard: keyboard.clone(),
+            mouse: mouse.clone(),
+            gamepads: gamepads.clone(),
+            cancel: Some(Binding::Key(Key::Escape)),
+            in_use: Vec::new(),
+            axis_threshold: 0.5,
+            chords: true,
+            ready: false,
+            modifier: None,
+        }
+    }
+
+    /// Set the input that cancels the capture, or none to not allow cancelling. The default is
+    /// [`Key::Escape`].
+    #[inline]
+    pub fn with_cancel(mut self, cancel: impl Into<Option<Binding>>) -> Self {
+        self.cancel = cancel.into();
+        self
+    }
+
+    /// Reject any of these bindings, reporting them as [`CaptureResult::InUse`].
+    #[inline]
+    pub fn with_in_use(mut self, bindings: impl IntoIterator<Item = Binding>) -> Self {
+        self.in_use.extend(bindings);
+        self
+    }
+
+    /// How far a gamepad axis must be pushed from the center to be captured. The default is `0.5`.
+    #[inline]
+    pub fn with_axis_threshold(mut self, threshold: f32) -> Self {
+        assert!(threshold > 0.0 && threshold <= 1.0);
+        self.axis_threshold = threshold;
+        self
+    }
+
+    /// Set whether keys pressed while holding modifiers are captured as chords. If false, modifier
+    /// keys are captured as soon as they are pressed like any other key. The default is true.
+    #[inline]
+    pub fn with_chords(mut self, chords: bool) -> Self {
+        self.chords = chords;
+        self
+    }
+
+    /// Start listening again, waiting for all inputs to be let go of first.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.ready = false;
+        self.modifier = None;
+    }
+
+    /// Check for the next input, returning a result once the player has pushed one.
+    pub fn poll(&mut self) -> Option<CaptureResult> {
+        if !self.ready {
+            if self.any_down() {
+                return None;
+            }
+            self.ready = true;
+        }
+        let binding = self.next_binding()?;
+        self.modifier = None;
+        Some(
+            if self
+                .cancel
+                .is_some_and(|cancel| cancel.same_input(&binding))
+            {
+                CaptureResult::Cancelled
+            } else if self.in_use.iter().any(|b| b.same_input(&binding)) {
+                CaptureResult::InUse(binding)
+            } else {
+                CaptureResult::Bound(binding)
+            },
+        )
+    }
+
+    /// If any key, button, or axis is being held.
+    fn any_down(&self) -> bool {
+        self.keyboard.currently_down().next().is_some()
+            || self.mouse.currently_down().next().is_some()
+            || self.gamepads.all().any(|pad| {
+                pad.currently_down().next().is_some()
+                    || GamepadAxis::VARIANTS
+                        .iter()
+                        .any(|&axis| pad.axis(axis).abs() >= self.axis_threshold)
+            })
+    }
+
+    /// The binding for an input that was pushed this frame.
+    fn next_binding(&mut self) -> Option<Binding> {
+        let kb = &self.keyboard;
+        for key in kb.currently_pressed() {
+            // wait to see if the modifier is part of a chord
+            if self.chords && key.is_modifier() {
+                self.modifier = Some(key);
+                continue;
+            }
+            let modifiers = Modifiers {
+                ctrl: kb.ctrl(),
+                shift: kb.shift(),
+                alt: kb.alt(),
+                cmd: kb.cmd(),
+            };
+            return Some(if self.chords && modifiers != Modifiers::NONE {
+                Binding::chord(modifiers, key)
+            } else {
+                Binding::Key(key)
+            });
+        }
+
+        // a modifier was let go of without pressing another key
+        if let Some(key) = self.modifier.filter(|key| kb.released(*key)) {
+            return Some(Binding::Key(key));
+        }
+
+        if let Some(btn) = self.mouse.currently_pressed().next() {
+            return Some(Binding::Mouse(btn));
+        }
+
+        for pad in self.gamepads.all() {
+            if let Some(btn) = pad.currently_pressed().next() {
+                return Some(Binding::Button(btn));
+            }
+            for &axis in GamepadAxis::VARIANTS {
+                let (val, prev) = (pad.axis(axis), pad.prev_axis(axis));
+                if val.abs() >= self.axis_threshold && prev.abs() < self.axis_threshold {
+                    return Some(Binding::axis(axis, self.axis_threshold.copysign(val)));
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,182 @@
+use crate::core::Time;
+use crate::input::action_map::Hidden;
+use crate::input::{ActionMap, Binding};
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::rc::{Rc, Weak};
+
+/// Handle to the stack of [action maps](ActionMap) receiving input.
+///
+/// This handle can be cloned and passed around freely to give objects access to the stack.
+///
+/// Maps higher in the stack take priority over the ones below them. A map that is
+/// [blocking](ActionMap::set_blocking) stops every map below it from receiving input, and a map
+/// that [consumes](ActionMap::set_consumes) its inputs hides them from the maps below, so a press
+/// is only ever seen by the topmost map that wants it.
+///
+/// ```ignore
+/// // open the pause menu on top of gameplay
+/// if self.gameplay.pressed("pause") {
+///     ctx.input_contexts.push(&self.pause_menu);
+/// }
+///
+/// // and close it again
+/// if self.pause_menu.pressed("back") {
+///     ctx.input_contexts.remove(&self.pause_menu);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct InputContexts(Rc<ContextsState>);
+
+impl Debug for InputContexts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("InputContexts").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for InputContexts {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+pub(crate) struct ContextsState {
+    time: Time,
+    stack: RefCell<Vec<ActionMap>>,
+    consumed: RefCell<Vec<(usize, Binding)>>,
+    consumed_frame: Cell<u64>,
+}
+
+impl InputContexts {
+    pub(crate) fn new(time: Time) -> Self {
+        Self(Rc::new(ContextsState {
+            time,
+            stack: RefCell::new(Vec::new()),
+            consumed: RefCell::new(Vec::new()),
+            consumed_frame: Cell::new(0),
+        }))
+    }
+
+    #[inline]
+    pub(crate) fn upgrade(weak: &Weak<ContextsState>) -> Option<Self> {
+        weak.upgrade().map(Self)
+    }
+
+    /// Push the map onto the top of the stack. If the map is already in this or another stack,
+    /// it is moved.
+    pub fn push(&self, map: &ActionMap) {
+        if let Some(contexts) = map.contexts() {
+            contexts.remove(map);
+        }
+        map.set_contexts(Rc::downgrade(&self.0));
+        self.0.stack.borrow_mut().push(map.clone());
+    }
+
+    /// Remove the map at the top of the stack and return it.
+    pub fn pop(&self) -> Option<ActionMap> {
+        let map = self.0.stack.borrow_mut().pop()?;
+        map.set_contexts(Weak::new());
+        Some(map)
+    }
+
+    /// Remove the map from the stack, returning false if it wasn't in the stack.
+    pub fn remove(&self, map: &ActionMap) -> bool {
+        let mut stack = self.0.stack.borrow_mut();
+        let Some(idx) = stack.iter().position(|m| m == map) else {
+            return false;
+        };
+        stack.remove(idx).set_contexts(Weak::new());
+        true
+    }
+
+    /// Remove all maps from the stack.
+    pub fn clear(&self) {
+        for map in self.0.stack.borrow_mut().drain(..) {
+            map.set_contexts(Weak::new());
+        }
+    }
+
+    /// The map at the top of the stack.
+    #[inline]
+    pub fn top(&self) -> Option<ActionMap> {
+        self.0.stack.borrow().last().cloned()
+    }
+
+    /// The highest map in the stack with the name.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<ActionMap> {
+        self.0
+            .stack
+            .borrow()
+            .iter()
+            .rev()
+            .find(|m| m.name() == name)
+            .cloned()
+    }
+
+    /// If the map is in the stack.
+    #[inline]
+    pub fn contains(&self, map: &ActionMap) -> bool {
+        self.0.stack.borrow().contains(map)
+    }
+
+    /// How many maps are in the stack.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.stack.borrow().len()
+    }
+
+    /// If the stack is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.stack.borrow().is_empty()
+    }
+
+    /// All maps in the stack, from bottom to top.
+    #[inline]
+    pub fn maps(&self) -> Vec<ActionMap> {
+        self.0.stack.borrow().clone()
+    }
+
+    /// The consumed bindings, cleared whenever a new frame starts.
+    fn consumed(&self) -> std::cell::RefMut<'_, Vec<(usize, Binding)>> {
+        let frame = self.0.time.frame();
+        let mut consumed = self.0.consumed.borrow_mut();
+        if self.0.consumed_frame.replace(frame) != frame {
+            consumed.clear();
+        }
+        consumed
+    }
+
+    /// Hide the bindings from the maps below the map for the rest of the frame.
+    pub(crate) fn consume(&self, map: &ActionMap, bindings: impl IntoIterator<Item = Binding>) {
+        let Some(idx) = self.0.stack.borrow().iter().position(|m| m == map) else {
+            return;
+        };
+        self.consumed()
+            .extend(bindings.into_iter().map(|binding| (idx, binding)));
+    }
+
+    /// The inputs hidden from the map by the maps above it, or none if the map isn't in the
+    /// stack or a map above it is blocking.
+    pub(crate) fn hidden(&self, map: &ActionMap) -> Option<Hidden> {
+        let stack = self.0.stack.borrow();
+        let idx = stack.iter().position(|m| m == map)?;
+        let mut hidden = Hidden::default();
+        for above in &stack[idx + 1..] {
+            if above.blocking() {
+                return None;
+            }
+            if above.consumes() {
+                above.hide_inputs(&mut hidden);
+            }
+        }
+        for &(consumer, binding) in self.consumed().iter() {
+            if consumer > idx {
+                hidden.add(binding);
+            }
+        }
+        Some(hidden)
+    }
+}
@@ -0,0 +1,469 @@
+use crate::input::{GamepadAxis, GamepadButton, Gamepads, Key, Keyboard, Mouse, MouseButton};
+use crate::math::{Vec2F, vec2};
+use crate::rand::Rand;
+use compact_str::CompactString;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use strum::EnumCount;
+use thiserror::Error;
+
+const MAGIC: &[u8; 4] = b"KINP";
+const VERSION: u16 = 2;
+
+/// Error loading or saving an [`InputRecording`].
+#[derive(Debug, Error)]
+pub enum InputRecordingError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("not an input recording")]
+    InvalidHeader,
+
+    #[error("unsupported input recording version: {0}")]
+    UnsupportedVersion(u16),
+
+    #[error("input recording contains invalid text")]
+    InvalidText,
+}
+
+/// The state of the keyboard, mouse, and gamepads at every fixed update of a play session, which
+/// can be saved to a file and replayed with [`InputRecorder::start_replay`].
+///
+/// A replay feeds the recorded input back to [`Game::fixed_update`](crate::core::Game::fixed_update)
+/// exactly as it was recorded. As long as the game's logic runs in its fixed updates and its
+/// random numbers come from a [`Rand`] seeded with [`seed`](Self::seed), it will play out the same
+/// way every time.
+#[derive(Clone, Default)]
+pub struct InputRecording {
+    seed: u64,
+    frames: Vec<InputFrame>,
+}
+
+impl Debug for InputRecording {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputRecording")
+            .field("seed", &self.seed)
+            .field("len", &self.frames.len())
+            .finish()
+    }
+}
+
+impl InputRecording {
+    /// The seed that was active when the recording was made. See [`InputRecorder::seed`].
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// How many fixed updates were recorded.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// If no fixed updates were recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Load a recording from the reader.
+    pub fn load<R: Read>(mut r: R) -> Result<Self, InputRecordingError> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(InputRecordingError::InvalidHeader);
+        }
+        let version = u16::from_le_bytes(read_bytes(&mut r)?);
+        if version != VERSION {
+            return Err(InputRecordingError::UnsupportedVersion(version));
+        }
+        let seed = u64::from_le_bytes(read_bytes(&mut r)?);
+        let len = read_u32(&mut r)?;
+        let frames = (0..len)
+            .map(|_| InputFrame::read(&mut r))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { seed, frames })
+    }
+
+    /// Load a recording from a file.
+    #[inline]
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, InputRecordingError> {
+        Self::load(BufReader::new(File::open(path)?))
+    }
+
+    /// Save the recording to the writer.
+    pub fn save<W: Write>(&self, mut w: W) -> Result<(), InputRecordingError> {
+        w.write_all(MAGIC)?;
+        w.write_all(&VERSION.to_le_bytes())?;
+        w.write_all(&self.seed.to_le_bytes())?;
+        write_u32(&mut w, self.frames.len())?;
+        for frame in &self.frames {
+            frame.write(&mut w)?;
+        }
+        w.flush()?;
+        Ok(())
+    }
+
+    /// Save the recording to a file.
+    #[inline]
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), InputRecordingError> {
+        self.save(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Handle to the input recorder, which can record the player's input and replay it in place of
+/// the real devices.
+///
+/// This handle can be cloned and passed around freely to give objects access to the recorder.
+///
+/// Obtained from [`Context`](crate::core::Context).
+#[derive(Clone)]
+pub struct InputRecorder(Rc<State>);
+
+impl Debug for InputRecorder {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("InputRecorder").finish_non_exhaustive()
+    }
+}
+
+struct State {
+    keyboard: Keyboard,
+    mouse: Mouse,
+    gamepads: Gamepads,
+    seed: Cell<u64>,
+    recording: RefCell<Option<InputRecording>>,
+    save_path: RefCell<Option<PathBuf>>,
+    replay: RefCell<Option<(InputRecording, usize)>>,
+}
+
+impl InputRecorder {
+    pub(crate) fn new(keyboard: Keyboard, mouse: Mouse, gamepads: Gamepads) -> Self {
+        Self(Rc::new(State {
+            keyboard,
+            mouse,
+            gamepads,
+            seed: Cell::new(Rand::new().seed()),
+            recording: RefCell::new(None),
+            save_path: RefCell::new(None),
+            replay: RefCell::new(None),
+        }))
+    }
+
+    /// A seed for the game's random number generators. It is random for every play session, but
+    /// when replaying it is the seed of the recording, so seeding a [`Rand`] with it at the start
+    /// of a play session makes replays deterministic.
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.0.seed.get()
+    }
+
+    /// Start recording input, discarding any recording in progress.
+    #[inline]
+    pub fn start_recording(&self) {
+        *self.0.recording.borrow_mut() = Some(InputRecording {
+            seed: self.seed(),
+            frames: Vec::new(),
+        });
+    }
+
+    /// Start recording input, and save the recording to `path` when the game exits.
+    pub(crate) fn start_recording_to(&self, path: PathBuf) {
+        self.start_recording();
+        *self.0.save_path.borrow_mut() = Some(path);
+    }
+
+    /// Stop recording input and return the recording, if one was in progress.
+    #[inline]
+    pub fn stop_recording(&self) -> Option<InputRecording> {
+        self.0.save_path.borrow_mut().take();
+        self.0.recording.borrow_mut().take()
+    }
+
+    /// If input is being recorded.
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        self.0.recording.borrow().is_some()
+    }
+
+    /// Start replaying the recording, which replaces all keyboard, mouse, and gamepad input until
+    /// it finishes or is stopped. This also changes [`seed`](Self::seed) to the recording's seed.
+    pub fn start_replay(&self, recording: InputRecording) {
+        self.0.seed.set(recording.seed);
+        *self.0.replay.borrow_mut() = Some((recording, 0));
+        self.0.gamepads.start_replay();
+    }
+
+    /// Stop replaying input, giving control back to the real devices.
+    pub fn stop_replay(&self) {
+        if self.0.replay.borrow_mut().take().is_some() {
+            self.0.keyboard.reset();
+            self.0.mouse.reset();
+            self.0.gamepads.stop_replay();
+        }
+    }
+
+    /// If input is being replayed.
+    #[inline]
+    pub fn is_replaying(&self) -> bool {
+        self.0.replay.borrow().is_some()
+    }
+
+    /// How many fixed updates of the replay have been played so far.
+    #[inline]
+    pub fn replay_position(&self) -> Option<usize> {
+        self.0.replay.borrow().as_ref().map(|(_, pos)| *pos)
+    }
+
+    /// Called before every fixed update to feed the next replayed frame into the devices and
+    /// record their state.
+    pub(crate) fn before_fixed_update(&self, ctx: &crate::core::Context) {
+        // replay the next frame, stopping the replay when it runs out
+        let finished = match &mut *self.0.replay.borrow_mut() {
+            Some((recording, pos)) => match recording.frames.get(*pos) {
+                Some(frame) => {
+                    *pos += 1;
+                    self.0.keyboard.apply_frame(&frame.keyboard);
+                    self.0.mouse.apply_frame(&frame.mouse);
+                    self.0.gamepads.apply_frames(ctx, &frame.gamepads);
+                    false
+                }
+                None => true,
+            },
+            None => false,
+        };
+        if finished {
+            self.stop_replay();
+        }
+
+        // record the state the fixed update is about to see
+        if let Some(recording) = &mut *self.0.recording.borrow_mut() {
+            recording.frames.push(InputFrame {
+                keyboard: self.0.keyboard.frame(),
+                mouse: self.0.mouse.frame(),
+                gamepads: self.0.gamepads.frames(),
+            });
+        }
+    }
+
+    /// Save the recording started by
+    /// [`GameBuilder::with_input_recording`](crate::core::GameBuilder::with_input_recording).
+    pub(crate) fn finish(&self) -> Result<(), InputRecordingError> {
+        let Some(path) = self.0.save_path.borrow_mut().take() else {
+            return Ok(());
+        };
+        match self.0.recording.borrow_mut().take() {
+            Some(recording) => recording.save_to_file(path),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct InputFrame {
+    keyboard: KeyboardFrame,
+    mouse: MouseFrame,
+    gamepads: Vec<GamepadFrame>,
+}
+
+/// The state of the keyboard during a fixed update.
+#[derive(Clone)]
+pub(crate) struct KeyboardFrame {
+    pub down: [bool; Key::COUNT],
+    pub pressed: [bool; Key::COUNT],
+    pub released: [bool; Key::COUNT],
+    pub repeated: [bool; Key::COUNT],
+    pub text_input: CompactString,
+}
+
+/// The state of the mouse during a fixed update.
+#[derive(Clone)]
+pub(crate) struct MouseFrame {
+    pub pos: Vec2F,
+    pub scroll_lines: Vec2F,
+    pub scroll_delta: Vec2F,
+    pub motion: Vec2F,
+    pub down: [bool; MouseButton::COUNT],
+    pub pressed: [bool; MouseButton::COUNT],
+    pub released: [bool; MouseButton::COUNT],
+}
+
+/// The state of a gamepad during a fixed update.
+#[derive(Clone)]
+pub(crate) struct GamepadFrame {
+    pub slot: u32,
+    pub name: String,
+    pub was_connected: bool,
+    pub down: [bool; GamepadButton::COUNT],
+    pub pressed: [bool; GamepadButton::COUNT],
+    pub released: [bool; GamepadButton::COUNT],
+    pub repeated: [bool; GamepadButton::COUNT],
+    pub btn_changed: [bool; GamepadButton::COUNT],
+    pub btn_value: [f32; GamepadButton::COUNT],
+    pub axis_value: [f32; GamepadAxis::COUNT],
+    pub axis_changed: [bool; GamepadAxis::COUNT],
+    pub axis_start: [f32; GamepadAxis::COUNT],
+}
+
+impl InputFrame {
+    fn write<W: Write>(&self, w: &mut W) -> Result<(), InputRecordingError> {
+        let k = &self.keyboard;
+        write_bits(w, &k.down)?;
+        write_bits(w, &k.pressed)?;
+        write_bits(w, &k.released)?;
+        write_bits(w, &k.repeated)?;
+        write_str(w, &k.text_input)?;
+
+        let m = &self.mouse;
+        write_vec(w, m.pos)?;
+        write_vec(w, m.scroll_lines)?;
+        write_vec(w, m.scroll_delta)?;
+        write_vec(w, m.motion)?;
+        write_bits(w, &m.down)?;
+        write_bits(w, &m.pressed)?;
+        write_bits(w, &m.released)?;
+
+        write_u32(w, self.gamepads.len())?;
+        for pad in &self.gamepads {
+            w.write_all(&pad.slot.to_le_bytes())?;
+            write_str(w, &pad.name)?;
+            write_bits(w, &[pad.was_connected])?;
+            write_bits(w, &pad.down)?;
+            write_bits(w, &pad.pressed)?;
+            write_bits(w, &pad.released)?;
+            write_bits(w, &pad.repeated)?;
+            write_bits(w, &pad.btn_changed)?;
+            write_bits(w, &pad.axis_changed)?;
+            for val in pad
+                .btn_value
+                .iter()
+                .chain(&pad.axis_value)
+                .chain(&pad.axis_start)
+            {
+                w.write_all(&val.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read<R: Read>(r: &mut R) -> Result<Self, InputRecordingError> {
+        let keyboard = KeyboardFrame {
+            down: read_bits(r)?,
+            pressed: read_bits(r)?,
+            released: read_bits(r)?,
+            repeated: read_bits(r)?,
+            text_input: read_str(r)?.into(),
+        };
+
+        let mouse = MouseFrame {
+            pos: read_vec(r)?,
+            scroll_lines: read_vec(r)?,
+            scroll_delta: read_vec(r)?,
+            motion: read_vec(r)?,
+            down: read_bits(r)?,
+            pressed: read_bits(r)?,
+            released: read_bits(r)?,
+        };
+
+        let count = read_u32(r)?;
+        let mut gamepads = Vec::with_capacity(count.min(16) as usize);
+        for _ in 0..count {
+            let mut pad = GamepadFrame {
+                slot: u32::from_le_bytes(read_bytes(r)?),
+                name: read_str(r)?,
+                was_connected: read_bits::<_, 1>(r)?[0],
+                down: read_bits(r)?,
+                pressed: read_bits(r)?,
+                released: read_bits(r)?,
+                repeated: read_bits(r)?,
+                btn_changed: read_bits(r)?,
+                axis_changed: read_bits(r)?,
+                btn_value: [0.0; _],
+                axis_value: [0.0; _],
+                axis_start: [0.0; _],
+            };
+            for val in pad
+                .btn_value
+                .iter_mut()
+                .chain(&mut pad.axis_value)
+                .chain(&mut pad.axis_start)
+            {
+                *val = f32::from_le_bytes(read_bytes(r)?);
+            }
+            gamepads.push(pad);
+        }
+
+        Ok(Self {
+            keyboard,
+            mouse,
+            gamepads,
+        })
+    }
+}
+
+fn read_bytes<R: Read, const N: usize>(r: &mut R) -> Result<[u8; N], InputRecordingError> {
+    let mut bytes = [0; N];
+    r.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_u32<R: Read>(r: &mut R) -> Result<u32, InputRecordingError> {
+    Ok(u32::from_le_bytes(read_bytes(r)?))
+}
+
+fn write_u32<W: Write>(w: &mut W, val: usize) -> Result<(), InputRecordingError> {
+    let val = u32::try_from(val).expect("input recording is too large");
+    w.write_all(&val.to_le_bytes())?;
+    Ok(())
+}
+
+fn read_vec<R: Read>(r: &mut R) -> Result<Vec2F, InputRecordingError> {
+    let x = f32::from_le_bytes(read_bytes(r)?);
+    let y = f32::from_le_bytes(read_bytes(r)?);
+    Ok(vec2(x, y))
+}
+
+fn write_vec<W: Write>(w: &mut W, v: Vec2F) -> Result<(), InputRecordingError> {
+    w.write_all(&v.x.to_le_bytes())?;
+    w.write_all(&v.y.to_le_bytes())?;
+    Ok(())
+}
+
+fn read_str<R: Read>(r: &mut R) -> Result<String, InputRecordingError> {
+    let len = read_u32(r)?;
+    let mut bytes = Vec::new();
+    r.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len as usize {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    String::from_utf8(bytes).map_err(|_| InputRecordingError::InvalidText)
+}
+
+fn write_str<W: Write>(w: &mut W, s: &str) -> Result<(), InputRecordingError> {
+    write_u32(w, s.len())?;
+    w.write_all(s.as_bytes())?;
+    Ok(())
+}
+
+/// Read flags that were packed 8 to a byte.
+fn read_bits<R: Read, const N: usize>(r: &mut R) -> Result<[bool; N], InputRecordingError> {
+    let mut bytes = vec![0; N.div_ceil(8)];
+    r.read_exact(&mut bytes)?;
+    Ok(std::array::from_fn(|i| bytes[i / 8] & (1 << (i % 8)) != 0))
+}
+
+/// Write flags packed 8 to a byte.
+fn write_bits<W: Write>(w: &mut W, bits: &[bool]) -> Result<(), InputRecordingError> {
+    let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+    for (i, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+        bytes[i / 8] |= 1 << (i % 8);
+    }
+    w.write_all(&bytes)?;
+    Ok(())
+}
@@ -215,6 +215,24 @@ pub enum Key {
     F35,
 }
 
+impl Key {
+    /// If this is a control, shift, alt, or command key.
+    #[inline]
+    pub fn is_modifier(self) -> bool {
+        matches!(
+            self,
+            Self::ControlLeft
+                | Self::ControlRight
+                | Self::ShiftLeft
+                | Self::ShiftRight
+                | Self::AltLeft
+                | Self::AltRight
+                | Self::SuperLeft
+                | Self::SuperRight
+        )
+    }
+}
+
 impl TryFrom<Winit> for Key {
     type Error = Winit;
 
@@ -1,11 +1,11 @@
-use crate::input::Key;
+use crate::input::{Key, KeyboardFrame};
 use compact_str::CompactString;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::time::SystemTime;
 use strum::{EnumCount, VariantArray};
-use winit::event::{ElementState, KeyEvent};
+use winit::event::{ElementState, Ime, KeyEvent};
 use winit::keyboard::PhysicalKey;
 
 /// Handle to the keyboard state.
@@ -24,9 +24,12 @@ impl Debug for Keyboard {
 #[derive(Clone)]
 struct State {
     down: Cell<[bool; Key::COUNT]>,
-    phases: [Phase; 2],
+    phases: [Phase; 3],
     phase: Cell<usize>,
     last_active: Cell<SystemTime>,
+    ime_enabled: Cell<bool>,
+    preedit: RefCell<CompactString>,
+    preedit_cursor: Cell<Option<(usize, usize)>>,
 }
 
 impl Default for State {
@@ -41,6 +44,9 @@ impl Default for State {
             }),
             phase: Cell::new(0),
             last_active: Cell::new(SystemTime::now()),
+            ime_enabled: Cell::new(false),
+            preedit: RefCell::new(CompactString::default()),
+            preedit_cursor: Cell::new(None),
         }
     }
 }
@@ -129,12 +135,43 @@ impl Keyboard {
         text
     }
 
+    /// If the input method editor is enabled, allowing the player to compose text. Enable it with
+    /// [`Window::set_ime_allowed`](crate::core::Window::set_ime_allowed).
+    #[inline]
+    pub fn ime_enabled(&self) -> bool {
+        self.0.ime_enabled.get()
+    }
+
+    /// Text the player is composing with the input method editor but hasn't committed yet, or an
+    /// empty string if they aren't composing anything. Once committed, the text is added to
+    /// [`text_input`](Self::text_input).
+    #[inline]
+    pub fn preedit(&self) -> CompactString {
+        self.0.preedit.borrow().clone()
+    }
+
+    /// Byte range of the [`preedit`](Self::preedit) text that the composing cursor or selection
+    /// covers, or none if the cursor should be hidden.
+    #[inline]
+    pub fn preedit_cursor(&self) -> Option<(usize, usize)> {
+        self.0.preedit_cursor.get()
+    }
+
     /// All keys that are currently down.
     #[inline]
     pub fn currently_down(&self) -> impl Iterator<Item = Key> {
         Key::VARIANTS.iter().copied().filter(|key| self.down(*key))
     }
 
+    /// All keys that were pressed this frame.
+    #[inline]
+    pub fn currently_pressed(&self) -> impl Iterator<Item = Key> {
+        Key::VARIANTS
+            .iter()
+            .copied()
+            .filter(|key| self.pressed(*key))
+    }
+
     /// If the left or right control key is down.
     #[inline]
     pub fn ctrl(&self) -> bool {
@@ -179,6 +216,11 @@ impl Keyboard {
         self.0.phase.set(1);
     }
 
+    #[inline]
+    pub(crate) fn set_fixed_update_phase(&self) {
+        self.0.phase.set(2);
+    }
+
     #[inline]
     pub(crate) fn handle_event(&self, event: KeyEvent) {
         self.0.last_active.set(SystemTime::now());
@@ -220,6 +262,70 @@ impl Keyboard {
         }
     }
 
+    pub(crate) fn handle_ime(&self, ime: Ime) {
+        self.0.last_active.set(SystemTime::now());
+
+        match ime {
+            Ime::Enabled => {
+                self.0.ime_enabled.set(true);
+            }
+            Ime::Preedit(text, cursor) => {
+                *self.0.preedit.borrow_mut() = text.into();
+                self.0.preedit_cursor.set(cursor);
+            }
+            Ime::Commit(text) => {
+                self.0.preedit.borrow_mut().clear();
+                self.0.preedit_cursor.set(None);
+                for phase in &self.0.phases {
+                    let mut dst = phase.text_input.take();
+                    dst.push_str(&text);
+                    phase.text_input.set(dst);
+                }
+            }
+            Ime::Disabled => {
+                self.0.ime_enabled.set(false);
+                self.0.preedit.borrow_mut().clear();
+                self.0.preedit_cursor.set(None);
+            }
+        }
+    }
+
+    /// The keyboard's current state, for recording.
+    pub(crate) fn frame(&self) -> KeyboardFrame {
+        let phase = self.phase();
+        KeyboardFrame {
+            down: self.0.down.get(),
+            pressed: phase.pressed.get(),
+            released: phase.released.get(),
+            repeated: phase.repeated.get(),
+            text_input: self.text_input(),
+        }
+    }
+
+    /// Replace the keyboard's state with a recorded one.
+    pub(crate) fn apply_frame(&self, frame: &KeyboardFrame) {
+        self.0.down.set(frame.down);
+        for phase in &self.0.phases {
+            phase.pressed.set(frame.pressed);
+            phase.released.set(frame.released);
+            phase.repeated.set(frame.repeated);
+            phase.text_input.set(frame.text_input.clone());
+        }
+    }
+
+    /// Release all keys and clear all events.
+    pub(crate) fn reset(&self) {
+        self.0.down.set([false; _]);
+        self.0.preedit.borrow_mut().clear();
+        self.0.preedit_cursor.set(None);
+        for phase in &self.0.phases {
+            phase.pressed.set([false; _]);
+            phase.released.set([false; _]);
+            phase.repeated.set([false; _]);
+            phase.text_input.set(CompactString::default());
+        }
+    }
+
     #[inline]
     pub(crate) fn clear_phase(&self) {
         let phase = self.phase();
@@ -1,29 +1,55 @@
-//! Mouse, keyboard, and gamepad input handling.
+//! Mouse, keyboard, gamepad, and touch input handling.
 
+mod action_map;
+mod action_map_set;
+mod axis_response;
+mod binding;
+mod buffered_input;
 mod gamepad;
 mod gamepad_axis;
 mod gamepad_button;
 mod gamepad_status;
+mod gamepad_stick;
 mod gamepads;
+mod input_capture;
+mod input_contexts;
+mod input_recording;
 mod key;
 mod keyboard;
 mod mouse;
 mod mouse_button;
+mod rumble;
+mod text_edit;
+mod touch;
+mod touches;
 mod virtual_axis;
 mod virtual_button;
 mod virtual_controller;
 mod virtual_source;
 mod virtual_stick;
 
+pub use action_map::*;
+pub use action_map_set::*;
+pub use axis_response::*;
+pub use binding::*;
+pub use buffered_input::*;
 pub use gamepad::*;
 pub use gamepad_axis::*;
 pub use gamepad_button::*;
 pub use gamepad_status::*;
+pub use gamepad_stick::*;
 pub use gamepads::*;
+pub use input_capture::*;
+pub use input_contexts::*;
+pub use input_recording::*;
 pub use key::*;
 pub use keyboard::*;
 pub use mouse::*;
 pub use mouse_button::*;
+pub use rumble::*;
+pub use text_edit::*;
+pub use touch::*;
+pub use touches::*;
 pub use virtual_axis::*;
 pub use virtual_button::*;
 pub use virtual_controller::*;
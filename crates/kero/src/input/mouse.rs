@@ -1,10 +1,11 @@
-use crate::input::MouseButton;
+use crate::input::{MouseButton, MouseFrame};
 use crate::math::{Numeric, Vec2F, vec2};
 use dpi::{LogicalPosition, PhysicalPosition};
 use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::time::SystemTime;
+use strum::VariantArray;
 use winit::event::{ElementState, MouseScrollDelta};
 
 /// Handle to the mouse state.
@@ -23,7 +24,7 @@ impl Debug for Mouse {
 struct State {
     pos: Cell<Vec2F>,
     down: Cell<[bool; 3]>,
-    phases: [Phase; 2],
+    phases: [Phase; 3],
     phase: Cell<usize>,
     last_active: Cell<SystemTime>,
 }
@@ -32,6 +33,7 @@ struct State {
 struct Phase {
     scroll_lines: Cell<Vec2F>,
     scroll_delta: Cell<Vec2F>,
+    motion: Cell<Vec2F>,
     pressed: Cell<[bool; 3]>,
     released: Cell<[bool; 3]>,
 }
@@ -76,6 +78,16 @@ impl Mouse {
         self.phase().scroll_delta.get()
     }
 
+    /// How far the mouse moved this frame, straight from the device. Unlike changes in
+    /// [`pos`](Self::pos), this isn't stopped by the edges of the window or screen, keeps reporting
+    /// while the cursor is [locked](crate::core::CursorGrab::Locked), and isn't affected by cursor
+    /// acceleration on most platforms. The units depend on the device, so scale it by a
+    /// sensitivity setting.
+    #[inline]
+    pub fn motion(&self) -> Vec2F {
+        self.phase().motion.get()
+    }
+
     /// If the button is down.
     #[inline]
     pub fn down(&self, button: MouseButton) -> bool {
@@ -148,6 +160,24 @@ impl Mouse {
         self.released(MouseButton::Right)
     }
 
+    /// All mouse buttons that are currently down.
+    #[inline]
+    pub fn currently_down(&self) -> impl Iterator<Item = MouseButton> {
+        MouseButton::VARIANTS
+            .iter()
+            .copied()
+            .filter(|btn| self.down(*btn))
+    }
+
+    /// All mouse buttons that were pressed this frame.
+    #[inline]
+    pub fn currently_pressed(&self) -> impl Iterator<Item = MouseButton> {
+        MouseButton::VARIANTS
+            .iter()
+            .copied()
+            .filter(|btn| self.pressed(*btn))
+    }
+
     #[inline]
     pub(crate) fn set_update_phase(&self) {
         self.0.phase.set(0);
@@ -158,6 +188,11 @@ impl Mouse {
         self.0.phase.set(1);
     }
 
+    #[inline]
+    pub(crate) fn set_fixed_update_phase(&self) {
+        self.0.phase.set(2);
+    }
+
     #[inline]
     pub(crate) fn handle_move(&self, pos: LogicalPosition<f32>) {
         self.0.last_active.set(SystemTime::now());
@@ -169,17 +204,27 @@ impl Mouse {
     pub(crate) fn handle_scroll(&self, delta: MouseScrollDelta) {
         self.0.last_active.set(SystemTime::now());
 
-        let phase = &self.0.phases[self.0.phase.get()];
-        match delta {
-            MouseScrollDelta::LineDelta(x, y) => {
-                phase.scroll_lines.update(|s| s + vec2(x, y));
-            }
-            MouseScrollDelta::PixelDelta(PhysicalPosition { x, y }) => {
-                phase.scroll_delta.update(|s| s + vec2(x, y).to_f32());
+        for phase in &self.0.phases {
+            match delta {
+                MouseScrollDelta::LineDelta(x, y) => {
+                    phase.scroll_lines.update(|s| s + vec2(x, y));
+                }
+                MouseScrollDelta::PixelDelta(PhysicalPosition { x, y }) => {
+                    phase.scroll_delta.update(|s| s + vec2(x, y).to_f32());
+                }
             }
         }
     }
 
+    #[inline]
+    pub(crate) fn handle_motion(&self, (x, y): (f64, f64)) {
+        self.0.last_active.set(SystemTime::now());
+
+        for phase in &self.0.phases {
+            phase.motion.update(|m| m + vec2(x, y).to_f32());
+        }
+    }
+
     #[inline]
     pub(crate) fn handle_input(&self, button: winit::event::MouseButton, state: ElementState) {
         self.0.last_active.set(SystemTime::now());
@@ -204,11 +249,51 @@ impl Mouse {
         }
     }
 
+    /// The mouse's current state, for recording.
+    pub(crate) fn frame(&self) -> MouseFrame {
+        let phase = self.phase();
+        MouseFrame {
+            pos: self.0.pos.get(),
+            scroll_lines: phase.scroll_lines.get(),
+            scroll_delta: phase.scroll_delta.get(),
+            motion: phase.motion.get(),
+            down: self.0.down.get(),
+            pressed: phase.pressed.get(),
+            released: phase.released.get(),
+        }
+    }
+
+    /// Replace the mouse's state with a recorded one.
+    pub(crate) fn apply_frame(&self, frame: &MouseFrame) {
+        self.0.pos.set(frame.pos);
+        self.0.down.set(frame.down);
+        for phase in &self.0.phases {
+            phase.scroll_lines.set(frame.scroll_lines);
+            phase.scroll_delta.set(frame.scroll_delta);
+            phase.motion.set(frame.motion);
+            phase.pressed.set(frame.pressed);
+            phase.released.set(frame.released);
+        }
+    }
+
+    /// Release all buttons and clear all events.
+    pub(crate) fn reset(&self) {
+        self.0.down.set([false; _]);
+        for phase in &self.0.phases {
+            phase.scroll_lines.set(Vec2F::ZERO);
+            phase.scroll_delta.set(Vec2F::ZERO);
+            phase.motion.set(Vec2F::ZERO);
+            phase.pressed.set([false; _]);
+            phase.released.set([false; _]);
+        }
+    }
+
     #[inline]
     pub(crate) fn clear_phase(&self) {
         let phase = self.phase();
         phase.scroll_lines.set(Vec2F::ZERO);
         phase.scroll_delta.set(Vec2F::ZERO);
+        phase.motion.set(Vec2F::ZERO);
         phase.pressed.set([false; 3]);
         phase.released.set([false; 3]);
     }
@@ -1,9 +1,22 @@
+use serde::{Deserialize, Serialize};
 use strum::{EnumCount, FromRepr, VariantArray};
 use winit::event::MouseButton as Winit;
 
 /// A mouse button.
 #[derive(
-    Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, FromRepr, EnumCount, VariantArray,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    FromRepr,
+    EnumCount,
+    VariantArray,
 )]
 pub enum MouseButton {
     Left = 0,
@@ -0,0 +1,66 @@
+use gilrs::ff::Effect;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use std::time::Instant;
+
+#[cfg(feature = "lua")]
+pub type RumbleEffectRef = mlua::UserDataRef<RumbleEffect>;
+
+/// Handle to a rumble effect playing on a gamepad, started with
+/// [`Gamepad::rumble`](super::Gamepad::rumble).
+///
+/// The effect keeps playing until its duration runs out, even if this handle is dropped. This
+/// handle can be cloned and passed around freely, and used to stop the effect early.
+#[derive(Clone)]
+pub struct RumbleEffect(Rc<Inner>);
+
+impl Debug for RumbleEffect {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RumbleEffect").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for RumbleEffect {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for RumbleEffect {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Rc::as_ptr(&self.0).partial_cmp(&Rc::as_ptr(&other.0))
+    }
+}
+
+struct Inner {
+    effect: Effect,
+    end: Cell<Instant>,
+}
+
+impl RumbleEffect {
+    pub(crate) fn new(effect: Effect, end: Instant) -> Self {
+        Self(Rc::new(Inner {
+            effect,
+            end: Cell::new(end),
+        }))
+    }
+
+    /// If the effect is still playing.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        Instant::now() < self.0.end.get()
+    }
+
+    /// Stop the effect.
+    pub fn stop(&self) {
+        if let Err(err) = self.0.effect.stop() {
+            log::warn!("failed to stop rumble: {err}");
+        }
+        self.0.end.set(Instant::now());
+    }
+}
@@ -0,0 +1,705 @@
+use crate::core::{Clipboard, Context};
+use crate::input::{Key, Keyboard};
+use compact_str::CompactString;
+use std::ops::Range;
+
+#[cfg(feature = "lua")]
+pub type TextEditRef = mlua::UserDataRef<TextEdit>;
+#[cfg(feature = "lua")]
+pub type TextEditMut = mlua::UserDataRefMut<TextEdit>;
+
+/// How many edits can be undone.
+const MAX_UNDO: usize = 100;
+
+/// The editable contents of a text field, with a caret, selection, and undo history.
+///
+/// The text field is not drawn, only edited. Call [`update`](Self::update) every update while the
+/// field is focused to apply the player's typing, and then draw the
+/// [`display_text`](Self::display_text) with the caret and selection however the game likes.
+///
+/// Positions in the text, such as the [`caret`](Self::caret), are byte offsets that are always on
+/// a character boundary.
+///
+/// ```ignore
+/// // when the name field is focused
+/// ctx.window.set_ime_allowed(true);
+///
+/// // every update
+/// self.name.update(ctx);
+/// ctx.window.set_ime_cursor_area(caret_rect);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TextEdit {
+    text: String,
+    caret: usize,
+    anchor: usize,
+    multiline: bool,
+    max_chars: Option<usize>,
+    preedit: CompactString,
+    preedit_cursor: Option<(usize, usize)>,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_edit: Option<EditKind>,
+    revision: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Snapshot {
+    text: String,
+    caret: usize,
+    anchor: usize,
+}
+
+/// Kinds of edits that are grouped together into a single undo step when repeated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum EditKind {
+    Type,
+    Erase,
+}
+
+impl TextEdit {
+    /// Create an empty single-line text field.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with the text, placing the caret at the end of it.
+    #[inline]
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Set whether the field allows multiple lines. The default is false.
+    #[inline]
+    pub fn with_multiline(mut self, multiline: bool) -> Self {
+        self.multiline = multiline;
+        self
+    }
+
+    /// Limit how many characters the field can hold. The default is no limit.
+    #[inline]
+    pub fn with_max_chars(mut self, max_chars: impl Into<Option<usize>>) -> Self {
+        self.max_chars = max_chars.into();
+        self
+    }
+
+    /// The field's text.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace the field's text, placing the caret at the end of it and clearing the undo
+    /// history.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.revision += 1;
+        self.caret = self.text.len();
+        self.anchor = self.caret;
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+    }
+
+    /// If the field allows multiple lines.
+    #[inline]
+    pub fn multiline(&self) -> bool {
+        self.multiline
+    }
+
+    /// How many characters the field can hold, if it is limited.
+    #[inline]
+    pub fn max_chars(&self) -> Option<usize> {
+        self.max_chars
+    }
+
+    /// Position of the caret.
+    #[inline]
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Position the selection extends from to the caret. If this is the same as the caret,
+    /// nothing is selected.
+    #[inline]
+    pub fn anchor(&self) -> usize {
+        self.anchor
+    }
+
+    /// The selected range of the text, which is empty if nothing is selected.
+    #[inline]
+    pub fn selection(&self) -> Range<usize> {
+        self.caret.min(self.anchor)..self.caret.max(self.anchor)
+    }
+
+    /// If any text is selected.
+    #[inline]
+    pub fn has_selection(&self) -> bool {
+        self.caret != self.anchor
+    }
+
+    /// The selected text.
+    #[inline]
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection()]
+    }
+
+    /// Move the caret to the position. If `select` is true, the selection is extended to it.
+    pub fn set_caret(&mut self, pos: usize, select: bool) {
+        let pos = self.floor_boundary(pos);
+        self.move_to(pos, select);
+    }
+
+    /// Select the range of the text, placing the caret at the end of it.
+    pub fn select(&mut self, range: Range<usize>) {
+        self.anchor = self.floor_boundary(range.start);
+        self.caret = self.floor_boundary(range.end);
+        self.last_edit = None;
+    }
+
+    /// Select all of the text.
+    #[inline]
+    pub fn select_all(&mut self) {
+        self.select(0..self.text.len());
+    }
+
+    /// Move the caret one character left, or to the start of the selection.
+    pub fn move_left(&mut self, select: bool) {
+        let pos = if self.has_selection() && !select {
+            self.selection().start
+        } else {
+            self.prev_boundary(self.caret)
+        };
+        self.move_to(pos, select);
+    }
+
+    /// Move the caret one character right, or to the end of the selection.
+    pub fn move_right(&mut self, select: bool) {
+        let pos = if self.has_selection() && !select {
+            self.selection().end
+        } else {
+            self.next_boundary(self.caret)
+        };
+        self.move_to(pos, select);
+    }
+
+    /// Move the caret to the start of the word before it.
+    pub fn move_word_left(&mut self, select: bool) {
+        self.move_to(self.word_start(self.caret), select);
+    }
+
+    /// Move the caret to the start of the word after it.
+    pub fn move_word_right(&mut self, select: bool) {
+        self.move_to(self.word_end(self.caret), select);
+    }
+
+    /// Move the caret up a line, or to the start of the text if it's on the first line.
+    pub fn move_up(&mut self, select: bool) {
+        let start = self.line_start(self.caret);
+        let pos = if start == 0 {
+            0
+        } else {
+            let column = self.text[start..self.caret].chars().count();
+            self.column_pos(self.line_start(start - 1), column)
+        };
+        self.move_to(pos, select);
+    }
+
+    /// Move the caret down a line, or to the end of the text if it's on the last line.
+    pub fn move_down(&mut self, select: bool) {
+        let end = self.line_end(self.caret);
+        let pos = if end == self.text.len() {
+            end
+        } else {
+            let column = self.text[self.line_start(self.caret)..self.caret]
+                .chars()
+                .count();
+            self.column_pos(end + 1, column)
+        };
+        self.move_to(pos, select);
+    }
+
+    /// Move the caret to the start of its line.
+    pub fn move_line_start(&mut self, select: bool) {
+        self.move_to(self.line_start(self.caret), select);
+    }
+
+    /// Move the caret to the end of its line.
+    pub fn move_line_end(&mut self, select: bool) {
+        self.move_to(self.line_end(self.caret), select);
+    }
+
+    /// Move the caret to the start of the text.
+    #[inline]
+    pub fn move_to_start(&mut self, select: bool) {
+        self.move_to(0, select);
+    }
+
+    /// Move the caret to the end of the text.
+    #[inline]
+    pub fn move_to_end(&mut self, select: bool) {
+        self.move_to(self.text.len(), select);
+    }
+
+    /// Insert text at the caret, replacing the selection. Line breaks are removed if the field
+    /// isn't multiline, and other control characters are always removed. If the field has a
+    /// character limit, the text is cut short to fit.
+    pub fn insert(&mut self, text: &str) {
+        self.insert_as(text, Some(EditKind::Type));
+    }
+
+    /// Delete the selection, or the character before the caret.
+    pub fn backspace(&mut self) {
+        let kind = if self.has_selection() {
+            None
+        } else {
+            self.anchor = self.prev_boundary(self.caret);
+            Some(EditKind::Erase)
+        };
+        self.erase(kind);
+    }
+
+    /// Delete the selection, or the character after the caret.
+    pub fn delete(&mut self) {
+        let kind = if self.has_selection() {
+            None
+        } else {
+            self.anchor = self.next_boundary(self.caret);
+            Some(EditKind::Erase)
+        };
+        self.erase(kind);
+    }
+
+    /// Delete the selection, or the word before the caret.
+    pub fn delete_word_left(&mut self) {
+        let kind = if self.has_selection() {
+            None
+        } else {
+            self.anchor = self.word_start(self.caret);
+            Some(EditKind::Erase)
+        };
+        self.erase(kind);
+    }
+
+    /// Delete the selection, or the word after the caret.
+    pub fn delete_word_right(&mut self) {
+        let kind = if self.has_selection() {
+            None
+        } else {
+            self.anchor = self.word_end(self.caret);
+            Some(EditKind::Erase)
+        };
+        self.erase(kind);
+    }
+
+    /// Delete all of the text. This can be undone.
+    pub fn clear(&mut self) {
+        self.select_all();
+        self.erase(None);
+    }
+
+    /// The selected text to put on the clipboard, or none if nothing is selected.
+    #[inline]
+    pub fn copy(&self) -> Option<String> {
+        self.has_selection()
+            .then(|| self.selected_text().to_string())
+    }
+
+    /// Delete the selected text and return it to put on the clipboard, or none if nothing is
+    /// selected.
+    pub fn cut(&mut self) -> Option<String> {
+        let text = self.copy()?;
+        self.record(None);
+        self.replace_selection("");
+        Some(text)
+    }
+
+    /// Insert text from the clipboard at the caret, replacing the selection.
+    #[inline]
+    pub fn paste(&mut self, text: &str) {
+        self.insert_as(text, None);
+    }
+
+    /// If there are edits that can be undone.
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// If there are undone edits that can be redone.
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Undo the last edit, returning false if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo.pop() else {
+            return false;
+        };
+        let current = self.restore(snapshot);
+        self.redo.push(current);
+        true
+    }
+
+    /// Redo the last undone edit, returning false if there was nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+        let current = self.restore(snapshot);
+        self.undo.push(current);
+        true
+    }
+
+    /// Text the player is composing with the input method editor, which will be inserted at the
+    /// caret once it's committed. Updated by [`update`](Self::update).
+    #[inline]
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    /// Byte range of the [`preedit`](Self::preedit) text that the composing cursor or selection
+    /// covers, or none if the cursor should be hidden.
+    #[inline]
+    pub fn preedit_cursor(&self) -> Option<(usize, usize)> {
+        self.preedit_cursor
+    }
+
+    /// The text to draw, which is the field's text with any text being composed inserted at the
+    /// caret.
+    pub fn display_text(&self) -> String {
+        if self.preedit.is_empty() {
+            return self.text.clone();
+        }
+        let mut text = self.text.clone();
+        text.insert_str(self.caret, &self.preedit);
+        text
+    }
+
+    /// Position of the caret in the [`display_text`](Self::display_text).
+    #[inline]
+    pub fn display_caret(&self) -> usize {
+        match self.preedit_cursor {
+            Some((_, end)) => self.caret + end,
+            None => self.caret + self.preedit.len(),
+        }
+    }
+
+    /// Apply the keyboard's typing and editing keys to the field, returning true if the text
+    /// changed. Call this every update while the field is focused.
+    ///
+    /// This handles typed and composed text, moving the caret by character, word, and line,
+    /// selecting with shift, deleting, select all, undo/redo, and copying and pasting with the
+    /// game's [clipboard](crate::core::ContextData::clipboard).
+    #[inline]
+    pub fn update(&mut self, ctx: &Context) -> bool {
+        self.update_ext(&ctx.keyboard, &ctx.clipboard)
+    }
+
+    /// Apply the keyboard's typing and editing keys to the field, copying and pasting with the
+    /// clipboard, returning true if the text changed.
+    pub fn update_ext(&mut self, keyboard: &Keyboard, clipboard: &Clipboard) -> bool {
+        let kb = keyboard;
+        let revision = self.revision;
+
+        // committed text arrives even when the editor ends composition this frame
+        let shortcut = kb.ctrl_or_cmd();
+        if !shortcut || kb.alt() {
+            // line breaks are added by the enter keys instead, so drop the text they type
+            let typed = kb.text_input().as_str().replace(['\r', '\n'], "");
+            self.insert(&typed);
+        }
+
+        // while composing, the input method editor owns the editing keys
+        self.preedit = kb.preedit();
+        self.preedit_cursor = kb.preedit_cursor();
+        if !self.preedit.is_empty() {
+            return self.revision != revision;
+        }
+
+        let key = |key: Key| kb.pressed_or_repeated(key);
+        let select = kb.shift();
+        let mac = cfg!(target_os = "macos");
+        let word = if mac { kb.alt() } else { kb.ctrl() };
+        let line = mac && kb.cmd();
+
+        if shortcut && key(Key::A) {
+            self.select_all();
+        }
+        if shortcut && key(Key::Z) {
+            if select {
+                self.redo();
+            } else {
+                self.undo();
+            }
+        }
+        if shortcut && !mac && key(Key::Y) {
+            self.redo();
+        }
+        if shortcut
+            && key(Key::C)
+            && let Some(text) = self.copy()
+            && let Err(err) = clipboard.set_text(text)
+        {
+            log::warn!("failed to copy text: {err}");
+        }
+        if shortcut
+            && key(Key::X)
+            && let Some(text) = self.copy()
+        {
+            // only remove the text once it's safely on the clipboard
+            match clipboard.set_text(text) {
+                Ok(()) => {
+                    self.cut();
+                }
+                Err(err) => log::warn!("failed to cut text: {err}"),
+            }
+        }
+        if shortcut && key(Key::V) {
+            match clipboard.text() {
+                Ok(Some(text)) => self.paste(&text),
+                Ok(None) => {}
+                Err(err) => log::warn!("failed to paste text: {err}"),
+            }
+        }
+        if key(Key::ArrowLeft) {
+            if line {
+                self.move_line_start(select);
+            } else if word {
+                self.move_word_left(select);
+            } else {
+                self.move_left(select);
+            }
+        }
+        if key(Key::ArrowRight) {
+            if line {
+                self.move_line_end(select);
+            } else if word {
+                self.move_word_right(select);
+            } else {
+                self.move_right(select);
+            }
+        }
+        if key(Key::ArrowUp) {
+            if line || !self.multiline {
+                self.move_to_start(select);
+            } else {
+                self.move_up(select);
+            }
+        }
+        if key(Key::ArrowDown) {
+            if line || !self.multiline {
+                self.move_to_end(select);
+            } else {
+                self.move_down(select);
+            }
+        }
+        if key(Key::Home) {
+            if kb.ctrl() {
+                self.move_to_start(select);
+            } else {
+                self.move_line_start(select);
+            }
+        }
+        if key(Key::End) {
+            if kb.ctrl() {
+                self.move_to_end(select);
+            } else {
+                self.move_line_end(select);
+            }
+        }
+        if key(Key::Backspace) {
+            if word {
+                self.delete_word_left();
+            } else {
+                self.backspace();
+            }
+        }
+        if key(Key::Delete) {
+            if word {
+                self.delete_word_right();
+            } else {
+                self.delete();
+            }
+        }
+        if self.multiline && (key(Key::Enter) || key(Key::NumpadEnter)) {
+            self.insert("\n");
+        }
+
+        self.revision != revision
+    }
+
+    fn move_to(&mut self, pos: usize, select: bool) {
+        self.caret = pos;
+        if !select {
+            self.anchor = pos;
+        }
+        self.last_edit = None;
+    }
+
+    fn insert_as(&mut self, text: &str, kind: Option<EditKind>) {
+        let multiline = self.multiline;
+        let mut text: String = text
+            .replace("\r\n", "\n")
+            .chars()
+            .map(|c| if c == '\r' { '\n' } else { c })
+            .filter(|&c| !c.is_control() || (multiline && c == '\n'))
+            .collect();
+
+        // cut the text short to fit in the character limit
+        if let Some(max) = self.max_chars {
+            let kept = self.text.chars().count() - self.selected_text().chars().count();
+            let room = max.saturating_sub(kept);
+            if let Some((idx, _)) = text.char_indices().nth(room) {
+                text.truncate(idx);
+            }
+        }
+
+        if text.is_empty() {
+            return;
+        }
+
+        // replacing a selection starts a new undo step, which typing can continue
+        self.record(if self.has_selection() { None } else { kind });
+        self.replace_selection(&text);
+        self.last_edit = kind;
+    }
+
+    /// Delete the selection.
+    fn erase(&mut self, kind: Option<EditKind>) {
+        if !self.has_selection() {
+            return;
+        }
+        self.record(kind);
+        self.replace_selection("");
+    }
+
+    fn replace_selection(&mut self, text: &str) {
+        let range = self.selection();
+        let start = range.start;
+        self.text.replace_range(range, text);
+        self.revision += 1;
+        self.caret = start + text.len();
+        self.anchor = self.caret;
+    }
+
+    /// Save the current state to the undo history before an edit, unless the edit continues a
+    /// run of the same kind of edit.
+    fn record(&mut self, kind: Option<EditKind>) {
+        if kind.is_none() || kind != self.last_edit {
+            if self.undo.len() == MAX_UNDO {
+                self.undo.remove(0);
+            }
+            self.undo.push(self.snapshot());
+        }
+        self.redo.clear();
+        self.last_edit = kind;
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            text: self.text.clone(),
+            caret: self.caret,
+            anchor: self.anchor,
+        }
+    }
+
+    /// Restore the snapshot, returning the state it replaced.
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        let current = self.snapshot();
+        self.text = snapshot.text;
+        self.revision += 1;
+        self.caret = snapshot.caret;
+        self.anchor = snapshot.anchor;
+        self.last_edit = None;
+        current
+    }
+
+    fn floor_boundary(&self, pos: usize) -> usize {
+        let mut pos = pos.min(self.text.len());
+        while !self.text.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        pos
+    }
+
+    fn prev_boundary(&self, pos: usize) -> usize {
+        self.text[..pos]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self, pos: usize) -> usize {
+        self.text[pos..]
+            .chars()
+            .next()
+            .map_or(pos, |c| pos + c.len_utf8())
+    }
+
+    /// Start of the word before the position, skipping any whitespace before it.
+    fn word_start(&self, pos: usize) -> usize {
+        let mut chars = self.text[..pos]
+            .char_indices()
+            .rev()
+            .skip_while(|(_, c)| c.is_whitespace())
+            .peekable();
+        let Some(&(_, first)) = chars.peek() else {
+            return 0;
+        };
+        let class = char_class(first);
+        chars
+            .take_while(|(_, c)| char_class(*c) == class)
+            .last()
+            .map_or(0, |(i, _)| i)
+    }
+
+    /// Start of the word after the position, skipping the rest of the word the position is in
+    /// and any whitespace after it.
+    fn word_end(&self, pos: usize) -> usize {
+        let rest = &self.text[pos..];
+        let Some(first) = rest.chars().next() else {
+            return pos;
+        };
+        let class = char_class(first);
+        rest.char_indices()
+            .skip_while(|(_, c)| !c.is_whitespace() && char_class(*c) == class)
+            .find(|(_, c)| !c.is_whitespace())
+            .map_or(self.text.len(), |(i, _)| pos + i)
+    }
+
+    fn line_start(&self, pos: usize) -> usize {
+        self.text[..pos].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self, pos: usize) -> usize {
+        self.text[pos..]
+            .find('\n')
+            .map_or(self.text.len(), |i| pos + i)
+    }
+
+    /// Position of the column in the line that starts at `start`, or the end of the line if it's
+    /// shorter than that.
+    fn column_pos(&self, start: usize, column: usize) -> usize {
+        let end = self.line_end(start);
+        self.text[start..end]
+            .char_indices()
+            .nth(column)
+            .map_or(end, |(i, _)| start + i)
+    }
+}
+
+/// Characters of the same class are grouped into words.
+#[inline]
+fn char_class(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
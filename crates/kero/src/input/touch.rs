@@ -0,0 +1,66 @@
+use crate::math::Vec2F;
+use serde::{Deserialize, Serialize};
+use winit::event::TouchPhase as Winit;
+
+/// The stage of a [`Touch`] as of its most recent event.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+pub enum TouchPhase {
+    /// The finger touched the screen.
+    Started,
+
+    /// The finger moved while touching the screen.
+    Moved,
+
+    /// The finger was lifted from the screen.
+    Ended,
+
+    /// The system cancelled the touch, for example because the window lost focus.
+    Cancelled,
+}
+
+impl From<Winit> for TouchPhase {
+    #[inline]
+    fn from(value: Winit) -> Self {
+        match value {
+            Winit::Started => Self::Started,
+            Winit::Moved => Self::Moved,
+            Winit::Ended => Self::Ended,
+            Winit::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+/// A single finger touching the screen.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Touch {
+    /// Identifies the finger. It stays the same from when the touch starts until it ends, and may
+    /// be reused by a later touch.
+    pub id: u64,
+
+    /// The touch position in window coordinates.
+    pub pos: Vec2F,
+
+    /// The position the touch started at in window coordinates.
+    pub start_pos: Vec2F,
+
+    /// The stage of the touch as of its most recent event.
+    pub phase: TouchPhase,
+
+    /// How hard the screen is being pressed from `0.0` to `1.0`, or none if the device doesn't
+    /// support pressure sensitivity.
+    pub pressure: Option<f32>,
+}
+
+impl Touch {
+    /// How far the touch has moved since it started.
+    #[inline]
+    pub fn offset(&self) -> Vec2F {
+        self.pos - self.start_pos
+    }
+
+    /// If the touch has ended or was cancelled.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase, TouchPhase::Ended | TouchPhase::Cancelled)
+    }
+}
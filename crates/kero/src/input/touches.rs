@@ -0,0 +1,298 @@
+use crate::input::{Mouse, Touch, TouchPhase};
+use crate::math::{Degrees, RadiansF, Vec2F, vec2};
+use dpi::LogicalPosition;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use std::time::SystemTime;
+use winit::event::{ElementState, MouseButton};
+
+/// Handle to the touch screen and gesture state.
+///
+/// This handle can be cloned and passed around freely to give objects access to touch input.
+///
+/// Touch positions are in window coordinates, the same as [`Mouse::pos`], so they can be mapped
+/// with [`Screen::map_pos`](crate::gfx::Screen::map_pos). Touches are not captured by the
+/// [`InputRecorder`](crate::input::InputRecorder), but with
+/// [mouse emulation](Self::set_mouse_emulation) enabled the emulated mouse is.
+#[derive(Clone)]
+pub struct Touches(Rc<State>);
+
+impl Debug for Touches {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Touches").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    active: RefCell<Vec<Touch>>,
+    phases: [Phase; 3],
+    phase: Cell<usize>,
+    last_active: Cell<SystemTime>,
+    mouse: Mouse,
+    emulate_mouse: Cell<bool>,
+    mouse_touch: Cell<Option<u64>>,
+}
+
+#[derive(Debug, Default)]
+struct Phase {
+    pressed: RefCell<Vec<Touch>>,
+    released: RefCell<Vec<Touch>>,
+    pinch: Cell<f32>,
+    pan: Cell<Vec2F>,
+    rotation: Cell<f32>,
+    double_tapped: Cell<bool>,
+}
+
+impl Touches {
+    pub(crate) fn new(mouse: Mouse) -> Self {
+        Self(Rc::new(State {
+            active: RefCell::new(Vec::new()),
+            phases: std::array::from_fn(|_| Phase::default()),
+            phase: Cell::new(0),
+            last_active: Cell::new(SystemTime::UNIX_EPOCH),
+            mouse,
+            emulate_mouse: Cell::new(false),
+            mouse_touch: Cell::new(None),
+        }))
+    }
+
+    /// Time the touch state last changed.
+    #[inline]
+    pub fn last_active(&self) -> SystemTime {
+        self.0.last_active.get()
+    }
+
+    #[inline]
+    fn phase(&self) -> &Phase {
+        &self.0.phases[self.0.phase.get()]
+    }
+
+    /// If touches drive the mouse. While enabled, the first finger to touch the screen moves the
+    /// mouse and holds down its left button until it is lifted.
+    #[inline]
+    pub fn mouse_emulation(&self) -> bool {
+        self.0.emulate_mouse.get()
+    }
+
+    /// Set whether touches drive the mouse. The default is false.
+    pub fn set_mouse_emulation(&self, enabled: bool) {
+        self.0.emulate_mouse.set(enabled);
+        if !enabled && self.0.mouse_touch.take().is_some() {
+            self.0
+                .mouse
+                .handle_input(MouseButton::Left, ElementState::Released);
+        }
+    }
+
+    /// How many fingers are touching the screen.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.0.active.borrow().len()
+    }
+
+    /// If any fingers are touching the screen.
+    #[inline]
+    pub fn any(&self) -> bool {
+        !self.0.active.borrow().is_empty()
+    }
+
+    /// All fingers touching the screen, in the order they started touching it.
+    #[inline]
+    pub fn all(&self) -> impl Iterator<Item = Touch> {
+        self.0.active.borrow().clone().into_iter()
+    }
+
+    /// The touch with the id, if that finger is touching the screen.
+    #[inline]
+    pub fn get(&self, id: u64) -> Option<Touch> {
+        self.0.active.borrow().iter().find(|t| t.id == id).copied()
+    }
+
+    /// The finger that has been touching the screen the longest.
+    #[inline]
+    pub fn first(&self) -> Option<Touch> {
+        self.0.active.borrow().first().copied()
+    }
+
+    /// Touches that started this frame, as they were when they started.
+    #[inline]
+    pub fn pressed(&self) -> impl Iterator<Item = Touch> {
+        self.phase().pressed.borrow().clone().into_iter()
+    }
+
+    /// Touches that ended or were cancelled this frame.
+    #[inline]
+    pub fn released(&self) -> impl Iterator<Item = Touch> {
+        self.phase().released.borrow().clone().into_iter()
+    }
+
+    /// If the touch with the id started this frame.
+    #[inline]
+    pub fn id_pressed(&self, id: u64) -> bool {
+        self.phase().pressed.borrow().iter().any(|t| t.id == id)
+    }
+
+    /// If the touch with the id ended or was cancelled this frame.
+    #[inline]
+    pub fn id_released(&self, id: u64) -> bool {
+        self.phase().released.borrow().iter().any(|t| t.id == id)
+    }
+
+    /// How much the view was pinched this frame. Positive values zoom in and negative values
+    /// zoom out.
+    #[inline]
+    pub fn pinch_delta(&self) -> f32 {
+        self.phase().pinch.get()
+    }
+
+    /// How far two fingers panned this frame, in window coordinates.
+    #[inline]
+    pub fn pan_delta(&self) -> Vec2F {
+        self.phase().pan.get()
+    }
+
+    /// How far two fingers rotated this frame. Positive values are counter-clockwise.
+    #[inline]
+    pub fn rotation_delta(&self) -> RadiansF {
+        RadiansF::from_degrees(Degrees(self.phase().rotation.get()))
+    }
+
+    /// If a double-tap gesture happened this frame.
+    #[inline]
+    pub fn double_tapped(&self) -> bool {
+        self.phase().double_tapped.get()
+    }
+
+    #[inline]
+    pub(crate) fn set_update_phase(&self) {
+        self.0.phase.set(0);
+    }
+
+    #[inline]
+    pub(crate) fn set_render_phase(&self) {
+        self.0.phase.set(1);
+    }
+
+    #[inline]
+    pub(crate) fn set_fixed_update_phase(&self) {
+        self.0.phase.set(2);
+    }
+
+    pub(crate) fn handle_touch(
+        &self,
+        id: u64,
+        phase: winit::event::TouchPhase,
+        pos: LogicalPosition<f32>,
+        pressure: Option<f32>,
+    ) {
+        self.0.last_active.set(SystemTime::now());
+
+        let emulating = self.0.mouse_touch.get() == Some(id);
+        let phase = TouchPhase::from(phase);
+        let mut active = self.0.active.borrow_mut();
+        match phase {
+            TouchPhase::Started => {
+                let touch = Touch {
+                    id,
+                    pos: vec2(pos.x, pos.y),
+                    start_pos: vec2(pos.x, pos.y),
+                    phase,
+                    pressure,
+                };
+                active.retain(|t| t.id != id);
+                active.push(touch);
+                for phase in &self.0.phases {
+                    phase.pressed.borrow_mut().push(touch);
+                }
+
+                // the first finger down takes control of the mouse
+                if self.0.emulate_mouse.get() && self.0.mouse_touch.get().is_none() {
+                    self.0.mouse_touch.set(Some(id));
+                    self.0.mouse.handle_move(pos);
+                    self.0
+                        .mouse
+                        .handle_input(MouseButton::Left, ElementState::Pressed);
+                }
+            }
+            TouchPhase::Moved => {
+                let Some(touch) = active.iter_mut().find(|t| t.id == id) else {
+                    return;
+                };
+                touch.pos = vec2(pos.x, pos.y);
+                touch.phase = phase;
+                touch.pressure = pressure.or(touch.pressure);
+                if emulating {
+                    self.0.mouse.handle_move(pos);
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                let Some(idx) = active.iter().position(|t| t.id == id) else {
+                    return;
+                };
+                let mut touch = active.remove(idx);
+                touch.pos = vec2(pos.x, pos.y);
+                touch.phase = phase;
+                touch.pressure = pressure.or(touch.pressure);
+                for phase in &self.0.phases {
+                    phase.released.borrow_mut().push(touch);
+                }
+                if emulating {
+                    self.0.mouse_touch.set(None);
+                    self.0.mouse.handle_move(pos);
+                    self.0
+                        .mouse
+                        .handle_input(MouseButton::Left, ElementState::Released);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn handle_pinch(&self, delta: f64) {
+        // the platform may report NaN deltas
+        if delta.is_nan() {
+            return;
+        }
+        self.0.last_active.set(SystemTime::now());
+        for phase in &self.0.phases {
+            phase.pinch.update(|p| p + delta as f32);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn handle_pan(&self, delta: LogicalPosition<f32>) {
+        self.0.last_active.set(SystemTime::now());
+        for phase in &self.0.phases {
+            phase.pan.update(|p| p + vec2(delta.x, delta.y));
+        }
+    }
+
+    #[inline]
+    pub(crate) fn handle_rotation(&self, degrees: f32) {
+        self.0.last_active.set(SystemTime::now());
+        for phase in &self.0.phases {
+            phase.rotation.update(|r| r + degrees);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn handle_double_tap(&self) {
+        self.0.last_active.set(SystemTime::now());
+        for phase in &self.0.phases {
+            phase.double_tapped.set(true);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn clear_phase(&self) {
+        let phase = self.phase();
+        phase.pressed.borrow_mut().clear();
+        phase.released.borrow_mut().clear();
+        phase.pinch.set(0.0);
+        phase.pan.set(Vec2F::ZERO);
+        phase.rotation.set(0.0);
+        phase.double_tapped.set(false);
+    }
+}
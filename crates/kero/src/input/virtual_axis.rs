@@ -1,6 +1,6 @@
 use super::VirtualButton;
-use crate::input::GamepadAxis;
 use crate::input::virtual_source::VirtualSource;
+use crate::input::{AxisResponse, GamepadAxis};
 use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::rc::Rc;
@@ -30,6 +30,7 @@ impl PartialOrd for VirtualAxis {
 struct Inner {
     source: VirtualSource,
     axis: Cell<Option<GamepadAxis>>,
+    response: Cell<AxisResponse>,
     neg: RefCell<VirtualButton>,
     pos: RefCell<VirtualButton>,
 }
@@ -56,6 +57,7 @@ impl VirtualAxis {
         Self(Rc::new(Inner {
             source: source.clone(),
             axis: Cell::new(axis.into()),
+            response: Cell::new(AxisResponse::RAW),
             neg: RefCell::new(neg),
             pos: RefCell::new(pos),
         }))
@@ -84,6 +86,21 @@ impl VirtualAxis {
         self.0.axis.set(axis.into());
     }
 
+    /// The deadzones and response curve applied to the gamepad axis. By default, its value is
+    /// left raw.
+    #[inline]
+    pub fn response(&self) -> AxisResponse {
+        self.0.response.get()
+    }
+
+    /// Set the deadzones and response curve applied to the gamepad axis. If this axis is part of
+    /// a [`VirtualStick`](super::VirtualStick), set the stick's response instead, so that its
+    /// radial deadzone can account for both axes.
+    #[inline]
+    pub fn set_response(&self, response: AxisResponse) {
+        self.0.response.set(response);
+    }
+
     /// Set the input's negative and positive buttons.
     pub fn set_buttons(&self, neg: VirtualButton, pos: VirtualButton) {
         *self.0.neg.borrow_mut() = neg.into();
@@ -107,7 +124,8 @@ impl VirtualAxis {
     pub fn value(&self) -> f32 {
         let mut value = 0.0;
         if let Some(axis) = self.0.axis.get() {
-            value += self.0.source.read(|pad| pad.axis(axis)).unwrap_or(0.0);
+            let raw = self.0.source.read(|pad| pad.axis(axis)).unwrap_or(0.0);
+            value += self.0.response.get().apply_axis(raw);
         }
         value -= self.0.neg.borrow().value();
         value += self.0.pos.borrow().value();
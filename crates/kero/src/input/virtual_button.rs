@@ -1,13 +1,15 @@
+use crate::input::binding;
 use crate::input::virtual_source::VirtualSource;
-use crate::input::{GamepadButton, Key, Keyboard};
-use std::cell::Cell;
+use crate::input::{Binding, BufferWindow, BufferedInput, GamepadButton, Key};
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::rc::Rc;
 
 /// Handle to a virtual button.
 ///
-/// This can be used to simultaneously listen to the state of a key and gamepad button, and
-/// can be cloned and passed around to give objects access to it.
+/// This can be used to simultaneously listen to the state of several [bindings](Binding), such as
+/// keys, key chords, gamepad buttons, mouse buttons, and gamepad axes, and can be cloned and passed
+/// around to give objects access to it.
 #[derive(Clone)]
 pub struct VirtualButton(Rc<Inner>);
 
@@ -27,14 +29,15 @@ impl PartialOrd for VirtualButton {
 
 struct Inner {
     source: VirtualSource,
-    btn: Cell<Option<GamepadButton>>,
-    key: Cell<Option<Key>>,
+    bindings: RefCell<Vec<Binding>>,
+    press: Cell<BufferedInput>,
+    checked_frame: Cell<Option<u64>>,
 }
 
 impl VirtualButton {
     /// Create a button with no mappings.
     pub fn empty(source: &VirtualSource) -> Self {
-        Self::new(source, None, None)
+        Self::with_bindings(source, [])
     }
 
     /// Create a button that listens to the provided key and button.
@@ -43,131 +46,156 @@ impl VirtualButton {
         key: impl Into<Option<Key>>,
         btn: impl Into<Option<GamepadButton>>,
     ) -> Self {
-        Self(Rc::new(Inner {
+        let key = key.into().map(Binding::Key);
+        let btn = btn.into().map(Binding::Button);
+        Self::with_bindings(source, key.into_iter().chain(btn))
+    }
+
+    /// Create a button that listens to all of the provided bindings.
+    pub fn with_bindings(
+        source: &VirtualSource,
+        bindings: impl IntoIterator<Item = Binding>,
+    ) -> Self {
+        let this = Self(Rc::new(Inner {
             source: source.clone(),
-            btn: Cell::new(btn.into()),
-            key: Cell::new(key.into()),
-        }))
+            bindings: RefCell::new(Vec::new()),
+            press: Cell::new(BufferedInput::new(0.0)),
+            checked_frame: Cell::new(None),
+        }));
+        this.set_bindings(bindings);
+        this
+    }
+
+    /// The bindings the button listens to.
+    #[inline]
+    pub fn bindings(&self) -> Vec<Binding> {
+        self.0.bindings.borrow().clone()
     }
 
+    /// Replace all of the button's bindings.
     #[inline]
-    fn keyboard(&self) -> &Keyboard {
-        self.0.source.keyboard()
+    pub fn set_bindings(&self, bindings: impl IntoIterator<Item = Binding>) {
+        self.clear_bindings();
+        for binding in bindings {
+            self.add_binding(binding);
+        }
     }
 
-    /// Set the gamepad button to listen to.
+    /// Add a binding to listen to, if the button isn't already listening to it.
+    #[inline]
+    pub fn add_binding(&self, binding: impl Into<Binding>) {
+        let binding = binding.into();
+        let mut bindings = self.0.bindings.borrow_mut();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Stop listening to a binding.
+    #[inline]
+    pub fn remove_binding(&self, binding: impl Into<Binding>) {
+        let binding = binding.into();
+        self.0.bindings.borrow_mut().retain(|b| *b != binding);
+    }
+
+    /// Remove all of the button's bindings.
+    #[inline]
+    pub fn clear_bindings(&self) {
+        self.0.bindings.borrow_mut().clear();
+    }
+
+    /// Set the gamepad button to listen to, replacing any gamepad button bindings.
     #[inline]
     pub fn set_button(&self, btn: impl Into<Option<GamepadButton>>) {
-        self.0.btn.set(btn.into());
+        self.0
+            .bindings
+            .borrow_mut()
+            .retain(|b| !matches!(b, Binding::Button(_)));
+        if let Some(btn) = btn.into() {
+            self.add_binding(btn);
+        }
     }
 
-    /// Set the key to listen to.
+    /// Set the key to listen to, replacing any key bindings.
     #[inline]
     pub fn set_key(&self, key: impl Into<Option<Key>>) {
-        self.0.key.set(key.into());
+        self.0
+            .bindings
+            .borrow_mut()
+            .retain(|b| !matches!(b, Binding::Key(_)));
+        if let Some(key) = key.into() {
+            self.add_binding(key);
+        }
     }
 
-    /// If the button's key or gamepad button is down.
+    /// If any of the button's bindings are down.
     #[inline]
     pub fn down(&self) -> bool {
-        self.0
-            .btn
-            .get()
-            .is_some_and(|btn| self.0.source.read(|pad| pad.down(btn)).unwrap_or(false))
-            || self
-                .0
-                .key
-                .get()
-                .is_some_and(|key| self.keyboard().down(key))
+        binding::any_down(&self.0.bindings.borrow(), &self.0.source)
     }
 
-    /// If the button's key or gamepad button was pressed this frame.
+    /// If any of the button's bindings were pressed this frame.
     ///
-    /// This treats the mappings as if they were one button, meaning if the gamepad button is held
+    /// This treats the bindings as if they were one button, meaning if the gamepad button is held
     /// down and you press the key, it will not count as a press because the virtual button is
     /// already considered down.
     #[inline]
     pub fn pressed(&self) -> bool {
-        let mut pressed = false;
-        if let Some(btn) = self.0.btn.get() {
-            if let Some((press, down)) = self.0.source.read(|pad| (pad.pressed(btn), pad.down(btn)))
-            {
-                if press {
-                    pressed = true;
-                } else if down {
-                    return false;
-                }
-            }
-        }
-        if let Some(key) = self.0.key.get() {
-            if self.keyboard().pressed(key) {
-                pressed = true;
-            } else if self.keyboard().down(key) {
-                return false;
-            }
+        binding::any_pressed(&self.0.bindings.borrow(), &self.0.source)
+    }
+
+    /// If the button was pressed within the window of time and the press hasn't been
+    /// [consumed](Self::consume_press). This lets a press that happens slightly too early, such as
+    /// jumping just before landing, still count once it's allowed.
+    ///
+    /// The button only notices presses while this is being checked, so call it every update for
+    /// as long as presses should be buffered. If the button's source has no
+    /// [time](VirtualSource::with_time), this is the same as [`pressed`](Self::pressed).
+    pub fn pressed_buffered(&self, window: impl Into<BufferWindow>) -> bool {
+        let Some(time) = self.0.source.time() else {
+            return self.pressed();
+        };
+        let mut press = self.0.press.get();
+
+        // only register each press once, so consuming it sticks for the rest of the frame
+        if self.0.checked_frame.get() != Some(time.frame()) {
+            self.0.checked_frame.set(Some(time.frame()));
+            press.update(time, self.pressed());
+            self.0.press.set(press);
         }
-        pressed
+
+        press.active_within(time, window)
     }
 
-    /// If the button's key or gamepad button was pressed this frame.
+    /// Consume the buffered press, so [`pressed_buffered`](Self::pressed_buffered) returns false
+    /// until the button is pressed again.
+    #[inline]
+    pub fn consume_press(&self) {
+        let mut press = self.0.press.get();
+        press.consume();
+        self.0.press.set(press);
+    }
+
+    /// If any of the button's bindings were released this frame.
     ///
-    /// This treats the mappings as if they were one button, meaning if the gamepad button is
+    /// This treats the bindings as if they were one button, meaning if the gamepad button is
     /// released but the key is still held down, this will not return true until the key is also
     /// released.
     #[inline]
     pub fn released(&self) -> bool {
-        let mut released = false;
-        if let Some(btn) = self.0.btn.get() {
-            if let Some((release, down)) =
-                self.0.source.read(|pad| (pad.released(btn), pad.down(btn)))
-            {
-                if release {
-                    released = true;
-                } else if !down {
-                    return false;
-                }
-            }
-        }
-        if let Some(key) = self.0.key.get() {
-            if self.keyboard().released(key) {
-                released = true;
-            } else if !self.keyboard().down(key) {
-                return false;
-            }
-        }
-        released
+        binding::any_released(&self.0.bindings.borrow(), &self.0.source)
     }
 
     /// If the button state changed this frame.
     #[inline]
     pub fn changed(&self) -> bool {
-        self.0.btn.get().is_some_and(|btn| {
-            self.0
-                .source
-                .read(|pad| pad.btn_changed(btn))
-                .unwrap_or(false)
-        }) || self
-            .0
-            .key
-            .get()
-            .is_some_and(|key| self.keyboard().pressed(key) || self.keyboard().released(key))
-    }
-
-    /// Value of the button.
+        let source = &self.0.source;
+        self.0.bindings.borrow().iter().any(|b| b.changed(source))
+    }
+
+    /// Value of the button, which is the highest value of any of its bindings.
     #[inline]
     pub fn value(&self) -> f32 {
-        if self
-            .0
-            .key
-            .get()
-            .is_some_and(|key| self.keyboard().down(key))
-        {
-            return 1.0;
-        }
-        self.0
-            .btn
-            .get()
-            .and_then(|btn| self.0.source.read(|pad| pad.value(btn)))
-            .unwrap_or(0.0)
+        binding::max_value(&self.0.bindings.borrow(), &self.0.source)
     }
 }
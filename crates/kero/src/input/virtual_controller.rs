@@ -1,7 +1,8 @@
 use crate::core::Context;
 use crate::input::virtual_source::VirtualSource;
 use crate::input::{
-    Gamepad, GamepadAxis, GamepadButton, Key, VirtualAxis, VirtualButton, VirtualStick,
+    Gamepad, GamepadAxis, GamepadButton, Key, RumbleEffect, VirtualAxis, VirtualButton,
+    VirtualStick,
 };
 use std::fmt::{Debug, Formatter};
 
@@ -169,4 +170,13 @@ impl VirtualController {
         self.dpad_up.set_key(Key::ArrowUp);
         self.dpad_down.set_key(Key::ArrowDown);
     }
+
+    /// Rumble the gamepad the controller is currently listening to. See
+    /// [`Gamepad::rumble`] for details.
+    #[inline]
+    pub fn rumble(&self, strong: f32, weak: f32, duration: f32) -> Option<RumbleEffect> {
+        self.source
+            .read(|pad| pad.rumble(strong, weak, duration))
+            .flatten()
+    }
 }
@@ -1,5 +1,5 @@
-use crate::core::Context;
-use crate::input::{Gamepad, Gamepads, Keyboard};
+use crate::core::{Context, Time};
+use crate::input::{Gamepad, Gamepads, Keyboard, Mouse};
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
@@ -34,9 +34,12 @@ impl PartialOrd for VirtualSource {
     }
 }
 
+#[derive(Clone)]
 struct Inner {
     pub keyboard: Keyboard,
+    pub mouse: Option<Mouse>,
     pub gamepads: Gamepads,
+    pub time: Option<Time>,
     pub selector: RefCell<GamepadSelector>,
 }
 
@@ -50,22 +53,51 @@ impl VirtualSource {
     /// Create a new input source.
     pub fn new(ctx: &Context, selector: GamepadSelector) -> Self {
         Self::new_ext(&ctx.keyboard, &ctx.gamepads, selector)
+            .with_mouse(&ctx.mouse)
+            .with_time(&ctx.time)
     }
 
-    /// Create a new input source.
+    /// Create a new input source. Until [`with_mouse`](Self::with_mouse) is used, mouse bindings
+    /// are never down, and until [`with_time`](Self::with_time) is used, presses aren't buffered.
     pub fn new_ext(keyboard: &Keyboard, gamepads: &Gamepads, selector: GamepadSelector) -> Self {
         Self(Rc::new(Inner {
             keyboard: keyboard.clone(),
+            mouse: None,
             gamepads: gamepads.clone(),
+            time: None,
             selector: RefCell::new(selector),
         }))
     }
 
+    /// Returns the source with the mouse that mouse bindings are read from.
+    pub fn with_mouse(self, mouse: &Mouse) -> Self {
+        let mut inner = Rc::unwrap_or_clone(self.0);
+        inner.mouse = Some(mouse.clone());
+        Self(Rc::new(inner))
+    }
+
+    /// Returns the source with the time that buffered presses are measured with.
+    pub fn with_time(self, time: &Time) -> Self {
+        let mut inner = Rc::unwrap_or_clone(self.0);
+        inner.time = Some(time.clone());
+        Self(Rc::new(inner))
+    }
+
     #[inline]
     pub(crate) fn keyboard(&self) -> &Keyboard {
         &self.0.keyboard
     }
 
+    #[inline]
+    pub(crate) fn mouse(&self) -> Option<&Mouse> {
+        self.0.mouse.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn time(&self) -> Option<&Time> {
+        self.0.time.as_ref()
+    }
+
     /// Create an input source that always listens to the most recently active gamepad.
     pub fn last_active(ctx: &Context) -> Self {
         Self::new(ctx, GamepadSelector::LastActive)
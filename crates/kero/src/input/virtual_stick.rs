@@ -1,7 +1,8 @@
 use super::VirtualAxis;
+use crate::input::AxisResponse;
 use crate::input::virtual_source::VirtualSource;
-use crate::math::{Vec2, vec2};
-use std::cell::RefCell;
+use crate::math::{Cardinal, Direction, Octal, Vec2, vec2};
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
@@ -37,6 +38,7 @@ impl PartialOrd for VirtualStick {
 struct Inner {
     pub x_axis: RefCell<VirtualAxis>,
     pub y_axis: RefCell<VirtualAxis>,
+    pub response: Cell<AxisResponse>,
 }
 
 impl VirtualStick {
@@ -56,6 +58,7 @@ impl VirtualStick {
         Self(Rc::new(Inner {
             x_axis: RefCell::new(x_axis),
             y_axis: RefCell::new(y_axis),
+            response: Cell::new(AxisResponse::RAW),
         }))
     }
 
@@ -95,11 +98,36 @@ impl VirtualStick {
         self.0.y_axis.borrow().value()
     }
 
-    /// The stick's normalized value.
+    /// The deadzones and response curve applied to the stick's value. By default, its value is
+    /// left raw.
+    #[inline]
+    pub fn response(&self) -> AxisResponse {
+        self.0.response.get()
+    }
+
+    #[inline]
+    pub fn set_response(&self, response: AxisResponse) {
+        self.0.response.set(response);
+    }
+
+    /// The stick's value with its [response](Self::set_response) applied. Its length is never
+    /// more than `1.0`.
     #[inline]
     pub fn value(&self) -> Vec2<f32> {
-        let value = vec2(self.x(), self.y());
-        let len = value.len();
-        if len > 1.0 { value / len } else { value }
+        self.0.response.get().apply_stick(vec2(self.x(), self.y()))
+    }
+
+    /// The 8-way direction the stick is pushed in, or `None` if it's centered.
+    #[inline]
+    pub fn octal(&self) -> Option<Octal> {
+        let value = self.value();
+        (value != Vec2::ZERO).then(|| Octal::from_vec2(value))
+    }
+
+    /// The 4-way direction the stick is pushed in, or `None` if it's centered.
+    #[inline]
+    pub fn cardinal(&self) -> Option<Cardinal> {
+        let value = self.value();
+        (value != Vec2::ZERO).then(|| Cardinal::from_vec2(value))
     }
 }
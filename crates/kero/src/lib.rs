@@ -14,8 +14,11 @@
 //! own game engines. It provides:
 //!
 //! - 🖥️ a window, game loop, and rendering context out of the box and ready to go
-//! - 🎮 mouse, keyboard, and gamepad input as well as virtual input mapping
+//! - 🎮 mouse, keyboard, gamepad, and touch input as well as virtual input mapping
+//! - ⏺️ deterministic input recording and replay for bug reports and automated playtests
+//! - 🔊 sound effects, streamed music, and a mixer with volume, panning, pitch, and buses
 //! - 🖼️ shaders, surfaces, textures, and other graphics resources
+//! - 📸 screenshots and frame recording to PNG sequences or animated PNGs
 //! - 🖌️ a straightforward but powerful canvas-style drawing API
 //! - 🧮 various math types for vectors, matrices, rotations, etc.
 //! - 📐 geometry types for various shapes, overlap testing, extraction, raycasting, etc.
@@ -93,6 +96,7 @@
 //! in having more contributors. It would be great if this could be polished up, stabilized, and turned
 //! into a reliable game development tool for the Rust ecosystem.
 
+pub mod audio;
 pub mod core;
 pub mod gfx;
 pub mod input;
@@ -127,6 +131,7 @@ pub use new_game::new_game;
 
 ///! Include all types and traits.
 pub mod prelude {
+    pub use crate::audio::*;
     pub use crate::color::*;
     pub use crate::core::*;
     pub use crate::gfx::*;
@@ -0,0 +1,100 @@
+use crate::core::Context;
+use crate::input::{ActionMap, ActionMapRef, ActionMapSet, VirtualSource};
+use crate::lua::LuaModule;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{FromLua, Lua, Table, UserData, UserDataMethods, UserDataRef, Value};
+
+pub struct ActionMapModule;
+
+impl LuaModule for ActionMapModule {
+    const PATH: &'static str = "ActionMap";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for ActionMapModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("load", |lua, path: String| {
+            let set = ActionMapSet::load_from_file(path).map_err(LuaError::external)?;
+            create_maps(lua, &set)
+        });
+        methods.add_function("load_config", |lua, file_name: String| {
+            let set = {
+                let ctx = Context::from_lua(lua);
+                ActionMapSet::load_config(&ctx, &file_name).map_err(LuaError::external)?
+            };
+            create_maps(lua, &set)
+        });
+        methods.add_function("parse", |lua, json: String| {
+            let set = ActionMapSet::load(json.as_bytes()).map_err(LuaError::external)?;
+            create_maps(lua, &set)
+        });
+    }
+}
+
+/// Create every map in the set, listening to the last active gamepad, in a table by name.
+fn create_maps(lua: &Lua, set: &ActionMapSet) -> LuaResult<Table> {
+    let source = VirtualSource::last_active(&Context::from_lua(lua));
+    lua.create_table_from(
+        set.create_all(&source)
+            .into_iter()
+            .map(|map| (map.name().to_string(), map)),
+    )
+}
+
+impl UserData for ActionMap {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("name", |lua, this: ActionMapRef| {
+            lua.create_string(this.name())
+        });
+        methods.add_function("is_active", |_, this: ActionMapRef| Ok(this.is_active()));
+        methods.add_function("blocking", |_, this: ActionMapRef| Ok(this.blocking()));
+        methods.add_function(
+            "set_blocking",
+            |_, (this, blocking): (ActionMapRef, bool)| {
+                this.set_blocking(blocking);
+                Ok(())
+            },
+        );
+        methods.add_function("consumes", |_, this: ActionMapRef| Ok(this.consumes()));
+        methods.add_function(
+            "set_consumes",
+            |_, (this, consumes): (ActionMapRef, bool)| {
+                this.set_consumes(consumes);
+                Ok(())
+            },
+        );
+        methods.add_function("button_names", |_, this: ActionMapRef| {
+            Ok(this.button_names())
+        });
+        methods.add_function("axis_names", |_, this: ActionMapRef| Ok(this.axis_names()));
+        methods.add_function("down", |_, (this, name): (ActionMapRef, String)| {
+            Ok(this.down(&name))
+        });
+        methods.add_function("pressed", |_, (this, name): (ActionMapRef, String)| {
+            Ok(this.pressed(&name))
+        });
+        methods.add_function("released", |_, (this, name): (ActionMapRef, String)| {
+            Ok(this.released(&name))
+        });
+        methods.add_function("value", |_, (this, name): (ActionMapRef, String)| {
+            Ok(this.value(&name))
+        });
+        methods.add_function("axis_value", |_, (this, name): (ActionMapRef, String)| {
+            Ok(this.axis_value(&name))
+        });
+        methods.add_function("consume", |_, (this, name): (ActionMapRef, String)| {
+            this.consume(&name);
+            Ok(())
+        });
+    }
+}
+
+impl FromLua for ActionMap {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
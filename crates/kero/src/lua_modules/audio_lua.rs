@@ -0,0 +1,233 @@
+use crate::audio::{Bus, BusRef, Music, MusicRef, Sound, SoundRef, Voice, VoiceParams, VoiceRef};
+use crate::core::Context;
+use crate::lua::LuaModule;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{Either, FromLua, Lua, Table, UserData, UserDataMethods, UserDataRef, Value};
+
+pub struct AudioModule;
+
+impl LuaModule for AudioModule {
+    const PATH: &'static str = "Audio";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for AudioModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("sample_rate", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.audio.sample_rate())
+        });
+        methods.add_function("is_null", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.audio.is_null())
+        });
+        methods.add_function("master", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.audio.master())
+        });
+        methods.add_function("bus", |lua, name: String| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.audio.bus(&name))
+        });
+        methods.add_function("master_volume", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.audio.master_volume())
+        });
+        methods.add_function("set_master_volume", |lua, volume: f32| {
+            let ctx = Context::from_lua(lua);
+            ctx.audio.set_master_volume(volume);
+            Ok(())
+        });
+        methods.add_function(
+            "play",
+            |lua, (src, params): (Either<SoundRef, MusicRef>, Option<Table>)| {
+                let ctx = Context::from_lua(lua);
+                let params = params.map(voice_params).transpose()?.unwrap_or_default();
+                match src {
+                    Either::Left(sound) => Ok(ctx.audio.play_sound_ext(&sound, params)),
+                    Either::Right(music) => ctx
+                        .audio
+                        .play_music_ext(&music, params)
+                        .map_err(LuaError::external),
+                }
+            },
+        );
+        methods.add_function("stop_all", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            ctx.audio.stop_all();
+            Ok(())
+        });
+        methods.add_function("voice_count", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.audio.voice_count())
+        });
+    }
+}
+
+fn voice_params(t: Table) -> LuaResult<VoiceParams> {
+    let def = VoiceParams::default();
+    Ok(VoiceParams {
+        volume: t.get::<Option<f32>>("volume")?.unwrap_or(def.volume),
+        pan: t.get::<Option<f32>>("pan")?.unwrap_or(def.pan),
+        pitch: t.get::<Option<f32>>("pitch")?.unwrap_or(def.pitch),
+        looping: t.get::<Option<bool>>("looping")?.unwrap_or(def.looping),
+        paused: t.get::<Option<bool>>("paused")?.unwrap_or(def.paused),
+        bus: t.get::<Option<Bus>>("bus")?,
+    })
+}
+
+pub struct SoundModule;
+
+impl LuaModule for SoundModule {
+    const PATH: &'static str = "Sound";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for SoundModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("load", |lua, path: String| {
+            let ctx = Context::from_lua(lua);
+            ctx.audio.load_sound(path).map_err(LuaError::external)
+        });
+        add_sound_methods(methods);
+    }
+}
+
+impl UserData for Sound {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_sound_methods(methods);
+    }
+}
+
+fn add_sound_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("sample_rate", |_, this: SoundRef| Ok(this.sample_rate()));
+    methods.add_function("frame_count", |_, this: SoundRef| Ok(this.frame_count()));
+    methods.add_function("duration", |_, this: SoundRef| Ok(this.duration()));
+}
+
+impl FromLua for Sound {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
+
+pub struct MusicModule;
+
+impl LuaModule for MusicModule {
+    const PATH: &'static str = "Music";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for MusicModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("load", |lua, path: String| {
+            let ctx = Context::from_lua(lua);
+            ctx.audio.load_music(path).map_err(LuaError::external)
+        });
+        add_music_methods(methods);
+    }
+}
+
+impl UserData for Music {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_music_methods(methods);
+    }
+}
+
+fn add_music_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("sample_rate", |_, this: MusicRef| Ok(this.sample_rate()));
+}
+
+impl FromLua for Music {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
+
+impl UserData for Voice {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("is_playing", |_, this: VoiceRef| Ok(this.is_playing()));
+        methods.add_function("stop", |_, this: VoiceRef| {
+            this.stop();
+            Ok(())
+        });
+        methods.add_function("is_paused", |_, this: VoiceRef| Ok(this.is_paused()));
+        methods.add_function("pause", |_, this: VoiceRef| {
+            this.pause();
+            Ok(())
+        });
+        methods.add_function("resume", |_, this: VoiceRef| {
+            this.resume();
+            Ok(())
+        });
+        methods.add_function("set_paused", |_, (this, v): (VoiceRef, bool)| {
+            this.set_paused(v);
+            Ok(())
+        });
+        methods.add_function("volume", |_, this: VoiceRef| Ok(this.volume()));
+        methods.add_function("set_volume", |_, (this, v): (VoiceRef, f32)| {
+            this.set_volume(v);
+            Ok(())
+        });
+        methods.add_function("pan", |_, this: VoiceRef| Ok(this.pan()));
+        methods.add_function("set_pan", |_, (this, v): (VoiceRef, f32)| {
+            this.set_pan(v);
+            Ok(())
+        });
+        methods.add_function("pitch", |_, this: VoiceRef| Ok(this.pitch()));
+        methods.add_function("set_pitch", |_, (this, v): (VoiceRef, f32)| {
+            this.set_pitch(v);
+            Ok(())
+        });
+        methods.add_function("looping", |_, this: VoiceRef| Ok(this.looping()));
+        methods.add_function("set_looping", |_, (this, v): (VoiceRef, bool)| {
+            this.set_looping(v);
+            Ok(())
+        });
+        methods.add_function("position", |_, this: VoiceRef| Ok(this.position()));
+    }
+}
+
+impl UserData for Bus {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("name", |_, this: BusRef| Ok(this.name()));
+        methods.add_function("is_master", |_, this: BusRef| Ok(this.is_master()));
+        methods.add_function("volume", |_, this: BusRef| Ok(this.volume()));
+        methods.add_function("set_volume", |_, (this, v): (BusRef, f32)| {
+            this.set_volume(v);
+            Ok(())
+        });
+        methods.add_function("muted", |_, this: BusRef| Ok(this.muted()));
+        methods.add_function("set_muted", |_, (this, v): (BusRef, bool)| {
+            this.set_muted(v);
+            Ok(())
+        });
+        methods.add_function("paused", |_, this: BusRef| Ok(this.paused()));
+        methods.add_function("set_paused", |_, (this, v): (BusRef, bool)| {
+            this.set_paused(v);
+            Ok(())
+        });
+        methods.add_function("stop_all", |_, this: BusRef| {
+            this.stop_all();
+            Ok(())
+        });
+    }
+}
+
+impl FromLua for Bus {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
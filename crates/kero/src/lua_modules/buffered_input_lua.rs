@@ -0,0 +1,96 @@
+use crate::core::Context;
+use crate::input::{BufferWindow, BufferedInput, BufferedInputMut, BufferedInputRef};
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{FromLua, Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+pub struct BufferedInputModule;
+
+impl LuaModule for BufferedInputModule {
+    const PATH: &'static str = "BufferedInput";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for BufferedInputModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("new", |_, seconds: f32| Ok(BufferedInput::new(seconds)));
+        methods.add_function("new_frames", |_, frames: u32| {
+            Ok(BufferedInput::new(BufferWindow::Frames(frames)))
+        });
+        add_methods(methods);
+    }
+}
+
+impl UserData for BufferedInput {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("window", |_, this: BufferedInputRef| {
+        Ok(match this.window() {
+            BufferWindow::Seconds(seconds) => (Some(seconds), None),
+            BufferWindow::Frames(frames) => (None, Some(frames)),
+        })
+    });
+    methods.add_function(
+        "set_window",
+        |_, (mut this, seconds): (BufferedInputMut, f32)| {
+            this.set_window(seconds);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "set_window_frames",
+        |_, (mut this, frames): (BufferedInputMut, u32)| {
+            this.set_window(frames);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "update",
+        |lua, (mut this, condition): (BufferedInputMut, bool)| {
+            let ctx = Context::from_lua(lua);
+            this.update(&ctx.time, condition);
+            Ok(())
+        },
+    );
+    methods.add_function("trigger", |lua, mut this: BufferedInputMut| {
+        let ctx = Context::from_lua(lua);
+        this.trigger(&ctx.time);
+        Ok(())
+    });
+    methods.add_function("active", |lua, this: BufferedInputRef| {
+        let ctx = Context::from_lua(lua);
+        Ok(this.active(&ctx.time))
+    });
+    methods.add_function(
+        "active_within",
+        |lua, (this, seconds): (BufferedInputRef, f32)| {
+            let ctx = Context::from_lua(lua);
+            Ok(this.active_within(&ctx.time, seconds))
+        },
+    );
+    methods.add_function(
+        "active_within_frames",
+        |lua, (this, frames): (BufferedInputRef, u32)| {
+            let ctx = Context::from_lua(lua);
+            Ok(this.active_within(&ctx.time, frames))
+        },
+    );
+    methods.add_function("consume", |_, mut this: BufferedInputMut| {
+        this.consume();
+        Ok(())
+    });
+}
+
+impl FromLua for BufferedInput {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| *h)
+    }
+}
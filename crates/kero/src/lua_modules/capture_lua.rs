@@ -0,0 +1,66 @@
+use crate::core::Context;
+use crate::gfx::{Recorder, Surface};
+use crate::lua::LuaModule;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{Lua, Table, UserData, UserDataMethods, Value};
+
+pub struct CaptureModule;
+
+impl LuaModule for CaptureModule {
+    const PATH: &'static str = "Capture";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for CaptureModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function(
+            "screenshot",
+            |lua, (path, surface): (String, Option<Surface>)| {
+                let ctx = Context::from_lua(lua);
+                match surface {
+                    Some(surface) => ctx
+                        .graphics
+                        .capture_surface_screenshot(&surface, path)
+                        .map_err(LuaError::external),
+                    None => {
+                        ctx.graphics.capture_screenshot(path);
+                        Ok(())
+                    }
+                }
+            },
+        );
+        methods.add_function("start_recording", |lua, t: Table| {
+            let ctx = Context::from_lua(lua);
+            let path: String = t.get("path")?;
+            let mut recorder = match t.get::<Option<String>>("format")?.as_deref() {
+                None | Some("png") => Recorder::png_sequence(path),
+                Some("apng") => Recorder::apng(path, t.get::<Option<u16>>("fps")?.unwrap_or(60)),
+                Some(format) => {
+                    return Err(LuaError::runtime(format!(
+                        "invalid recording format: {format:?}"
+                    )));
+                }
+            };
+            if let Some(every) = t.get::<Option<u32>>("every")? {
+                recorder = recorder.with_every(every);
+            }
+            if let Some(source) = t.get::<Option<Surface>>("source")? {
+                recorder = recorder.with_source(&source);
+            }
+            ctx.graphics
+                .start_recording(recorder)
+                .map_err(LuaError::external)
+        });
+        methods.add_function("stop_recording", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            ctx.graphics.stop_recording().map_err(LuaError::external)
+        });
+        methods.add_function("is_recording", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.graphics.is_recording())
+        });
+    }
+}
@@ -0,0 +1,48 @@
+use crate::core::Context;
+use crate::lua::LuaModule;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{Lua, Value};
+
+pub struct ClipboardModule;
+
+impl LuaModule for ClipboardModule {
+    const PATH: &'static str = "Clipboard";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+
+        m.set(
+            "text",
+            lua.create_function(|lua, _: ()| {
+                Context::from_lua(lua)
+                    .clipboard
+                    .text()
+                    .map_err(LuaError::external)
+            })?,
+        )?;
+        m.set(
+            "is_system",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).clipboard.is_system()))?,
+        )?;
+        m.set(
+            "set_text",
+            lua.create_function(|lua, text: String| {
+                Context::from_lua(lua)
+                    .clipboard
+                    .set_text(text)
+                    .map_err(LuaError::external)
+            })?,
+        )?;
+        m.set(
+            "clear",
+            lua.create_function(|lua, _: ()| {
+                Context::from_lua(lua)
+                    .clipboard
+                    .clear()
+                    .map_err(LuaError::external)
+            })?,
+        )?;
+
+        Ok(Value::Table(m))
+    }
+}
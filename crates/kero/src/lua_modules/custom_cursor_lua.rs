@@ -0,0 +1,30 @@
+use crate::core::CustomCursor;
+use crate::img::DynImageRef;
+use crate::lua::LuaModule;
+use crate::math::vec2;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{Lua, UserData, UserDataMethods, Value};
+
+pub struct CustomCursorModule;
+
+impl LuaModule for CustomCursorModule {
+    const PATH: &'static str = "CustomCursor";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for CustomCursorModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function(
+            "new",
+            |_, (img, hotspot_x, hotspot_y): (DynImageRef, u32, u32)| {
+                let img = img.clone().to_rgba8();
+                CustomCursor::new(&img, vec2(hotspot_x, hotspot_y)).map_err(LuaError::external)
+            },
+        );
+    }
+}
+
+impl UserData for CustomCursor {}
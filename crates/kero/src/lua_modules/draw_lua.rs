@@ -1,12 +1,13 @@
 use crate::gfx::{
-    BlendMode, ColorMode, Draw, FontRef, IndexBufferRef, Sampler, ShaderRef, SubTextureRef,
-    SurfaceRef, Texture, TextureRef, Topology, Vertex, VertexBufferRef,
+    BlendMode, ColorMode, Draw, FontRef, IndexBufferRef, InstanceBufferRef, MaskMode, Sampler,
+    ShaderRef, Stroke, SubTextureRef, SurfaceRef, Texture, TextureRef, Topology, Vertex,
+    VertexBufferRef,
 };
 use fey_color::{Rgba8, rgba};
 use fey_lua::LuaModule;
 use fey_math::{
-    Affine2F, CircleF, LineF, Mat2F, Mat3F, Mat4F, Mat4Ref, PolygonRef, QuadF, RadiansF, RectF,
-    RectU, TriangleF, Vec2F, Vec3F, Vec4F, circle, line, vec2,
+    Affine2F, CircleF, LineF, Mat2F, Mat3F, Mat4F, Mat4Ref, PathRef, PolygonF, PolygonRef, QuadF,
+    RadiansF, RectF, RectU, TriangleF, Vec2F, Vec3F, Vec4F, circle, line, vec2,
 };
 use mlua::prelude::{LuaError, LuaResult};
 use mlua::{BorrowedStr, Either, IntoLua, Lua, Number, Table, UserData, UserDataMethods, Value};
@@ -155,6 +156,25 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         Draw::from_lua(lua)?.set_clip_rect(value);
         Ok(())
     });
+    methods.add_function("begin_mask", |lua, _: ()| {
+        Draw::from_lua(lua)?.begin_mask();
+        Ok(())
+    });
+    methods.add_function("end_mask", |lua, mode: MaskMode| {
+        Draw::from_lua(lua)?.end_mask(mode);
+        Ok(())
+    });
+    methods.add_function("mask_mode", |lua, _: ()| {
+        Ok(Draw::from_lua(lua)?.mask_mode())
+    });
+    methods.add_function("set_mask_mode", |lua, mode: Option<MaskMode>| {
+        Draw::from_lua(lua)?.set_mask_mode(mode);
+        Ok(())
+    });
+    methods.add_function("clear_mask", |lua, _: ()| {
+        Draw::from_lua(lua)?.clear_mask();
+        Ok(())
+    });
     methods.add_function("transform", |lua, _: ()| {
         Ok(*Draw::from_lua(lua)?.transform())
     });
@@ -335,6 +355,20 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "triangle_outline_thick",
+        |lua, (a, b, c, col, thickness): (Vec2F, Vec2F, Vec2F, Rgba8, f32)| {
+            Draw::from_lua(lua)?.triangle_outline_thick((a, b, c), col, thickness);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "triangle_obj_outline_thick",
+        |lua, (tri, col, thickness): (TriangleF, Rgba8, f32)| {
+            Draw::from_lua(lua)?.triangle_outline_thick(tri, col, thickness);
+            Ok(())
+        },
+    );
     methods.add_function(
         "quad",
         |lua, (a, b, c, d, col): (Vec2F, Vec2F, Vec2F, Vec2F, Rgba8)| {
@@ -357,6 +391,20 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         Draw::from_lua(lua)?.quad_outline(quad, col);
         Ok(())
     });
+    methods.add_function(
+        "quad_outline_thick",
+        |lua, (a, b, c, d, col, thickness): (Vec2F, Vec2F, Vec2F, Vec2F, Rgba8, f32)| {
+            Draw::from_lua(lua)?.quad_outline_thick((a, b, c, d), col, thickness);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "quad_obj_outline_thick",
+        |lua, (quad, col, thickness): (QuadF, Rgba8, f32)| {
+            Draw::from_lua(lua)?.quad_outline_thick(quad, col, thickness);
+            Ok(())
+        },
+    );
     methods.add_function(
         "rect",
         |lua, (x, y, w, h, col): (f32, f32, f32, f32, Rgba8)| {
@@ -379,10 +427,32 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         Draw::from_lua(lua)?.rect_outline(rect, col);
         Ok(())
     });
+    methods.add_function(
+        "rect_outline_thick",
+        |lua, (x, y, w, h, col, thickness): (f32, f32, f32, f32, Rgba8, f32)| {
+            Draw::from_lua(lua)?.rect_outline_thick((x, y, w, h), col, thickness);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "rect_obj_outline_thick",
+        |lua, (rect, col, thickness): (RectF, Rgba8, f32)| {
+            Draw::from_lua(lua)?.rect_outline_thick(rect, col, thickness);
+            Ok(())
+        },
+    );
     methods.add_function("polygon", |lua, (poly, col): (PolygonRef, Rgba8)| {
         Draw::from_lua(lua)?.polygon(&poly, col);
         Ok(())
     });
+    methods.add_function(
+        "polygon_with_holes",
+        |lua, (poly, holes, col): (PolygonRef, Vec<PolygonRef>, Rgba8)| {
+            let holes: Vec<PolygonF> = holes.into_iter().map(|h| h.clone()).collect();
+            Draw::from_lua(lua)?.polygon_with_holes(&poly, &holes, col);
+            Ok(())
+        },
+    );
     methods.add_function(
         "polygon_outline",
         |lua, (poly, col): (PolygonRef, Rgba8)| {
@@ -390,6 +460,13 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "polygon_outline_thick",
+        |lua, (poly, col, thickness): (PolygonRef, Rgba8, f32)| {
+            Draw::from_lua(lua)?.polygon_outline_thick(&poly, col, thickness);
+            Ok(())
+        },
+    );
     methods.add_function(
         "circle",
         |lua,
@@ -440,6 +517,67 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "circle_outline_thick",
+        |lua,
+         (
+            a, // x         | center
+            b, // y         | radius
+            c, // radius    | color
+            d, // color     | thickness
+            e, // thickness | segs
+            f, // segs
+        ): (
+            Either<f32, Vec2F>,
+            f32,
+            Number,
+            Number,
+            Option<Number>,
+            Option<u32>,
+        )| {
+            let (circ, col, thickness, segs) = match a {
+                Either::Left(a) => (
+                    circle(vec2(a, b), c as f32),
+                    rgba(d as u32),
+                    e.unwrap() as f32,
+                    f,
+                ),
+                Either::Right(a) => (circle(a, b), rgba(c as u32), d as f32, e.map(|e| e as u32)),
+            };
+            Draw::from_lua(lua)?.circle_outline_thick(circ, col, thickness, segs);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "circle_obj_outline_thick",
+        |lua, (circ, col, thickness, segs): (CircleF, Rgba8, f32, Option<u32>)| {
+            Draw::from_lua(lua)?.circle_outline_thick(circ, col, thickness, segs);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "stroke_lines",
+        |lua, (points, stroke, col, loops): (Table, Stroke, Rgba8, Option<bool>)| {
+            Draw::from_lua(lua)?.stroke_lines(
+                points.sequence_values::<Vec2F>().filter_map(Result::ok),
+                &stroke,
+                col,
+                loops.unwrap_or(false),
+            );
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "stroke_path",
+        |lua, (path, stroke, col): (PathRef, Stroke, Rgba8)| {
+            Draw::from_lua(lua)?.stroke_path(&path, &stroke, col);
+            Ok(())
+        },
+    );
+    methods.add_function("fill_path", |lua, (path, col): (PathRef, Rgba8)| {
+        Draw::from_lua(lua)?.fill_path(&path, col);
+        Ok(())
+    });
     methods.add_function(
         "subtextured_quad",
         |lua,
@@ -538,4 +676,18 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "instanced",
+        |lua,
+         (tex, topo, verts, inds, insts): (
+            Option<Texture>,
+            Topology,
+            VertexBufferRef,
+            IndexBufferRef,
+            InstanceBufferRef,
+        )| {
+            Draw::from_lua(lua)?.instanced(tex, topo, &verts, &inds, &insts);
+            Ok(())
+        },
+    );
 }
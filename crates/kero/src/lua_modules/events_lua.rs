@@ -0,0 +1,90 @@
+use crate::core::{AppEvent, Context, Theme};
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{IntoLua, Lua, Result, Value};
+
+pub struct EventsModule;
+
+impl LuaModule for EventsModule {
+    const PATH: &'static str = "Events";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+
+        m.set(
+            "all",
+            lua.create_function(|lua, _: ()| {
+                lua.create_sequence_from(Context::from_lua(lua).events.all())
+            })?,
+        )?;
+        m.set(
+            "dropped_files",
+            lua.create_function(|lua, _: ()| {
+                lua.create_sequence_from(Context::from_lua(lua).events.dropped_files())
+            })?,
+        )?;
+        m.set(
+            "hovered_files",
+            lua.create_function(|lua, _: ()| {
+                lua.create_sequence_from(Context::from_lua(lua).events.hovered_files())
+            })?,
+        )?;
+        m.set(
+            "focus_gained",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).events.focus_gained()))?,
+        )?;
+        m.set(
+            "focus_lost",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).events.focus_lost()))?,
+        )?;
+        m.set(
+            "occluded",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).events.occluded()))?,
+        )?;
+
+        Ok(Value::Table(m))
+    }
+}
+
+impl IntoLua for AppEvent {
+    fn into_lua(self, lua: &Lua) -> Result<Value> {
+        let t = lua.create_table()?;
+        match self {
+            AppEvent::FileHovered(path) => {
+                t.set("type", "file_hovered")?;
+                t.set("path", path)?;
+            }
+            AppEvent::FileHoverCancelled => {
+                t.set("type", "file_hover_cancelled")?;
+            }
+            AppEvent::FileDropped(path) => {
+                t.set("type", "file_dropped")?;
+                t.set("path", path)?;
+            }
+            AppEvent::Focused(focused) => {
+                t.set("type", "focused")?;
+                t.set("focused", focused)?;
+            }
+            AppEvent::Occluded(occluded) => {
+                t.set("type", "occluded")?;
+                t.set("occluded", occluded)?;
+            }
+            AppEvent::ThemeChanged(theme) => {
+                t.set("type", "theme_changed")?;
+                t.set("theme", theme)?;
+            }
+        }
+        Ok(Value::Table(t))
+    }
+}
+
+impl IntoLua for Theme {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> Result<Value> {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+        .into_lua(lua)
+    }
+}
@@ -1,5 +1,8 @@
 use crate::core::Context;
-use crate::input::{Gamepad, GamepadAxis, GamepadButton, GamepadStatus};
+use crate::input::{
+    AxisResponse, Gamepad, GamepadAxis, GamepadButton, GamepadStatus, GamepadStick, ResponseCurve,
+    RumbleEffect, RumbleEffectRef,
+};
 use crate::lua::LuaModule;
 use fey_lua::{UserDataOf, create_fill};
 use mlua::prelude::{LuaError, LuaResult};
@@ -53,6 +56,20 @@ impl UserData for GamepadModule {
             let ctx = Context::from_lua(lua);
             Ok(ctx.gamepads.last_active_lua())
         });
+        methods.add_function("rumble_scale", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.gamepads.rumble_scale())
+        });
+        methods.add_function("set_rumble_scale", |lua, scale: f32| {
+            let ctx = Context::from_lua(lua);
+            ctx.gamepads.set_rumble_scale(scale);
+            Ok(())
+        });
+        methods.add_function("stop_all_rumble", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            ctx.gamepads.stop_rumble();
+            Ok(())
+        });
         add_methods(methods);
     }
 }
@@ -103,6 +120,60 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         "axis_changed",
         |_, (this, axis): (GamepadRef, GamepadAxis)| Ok(this.axis_changed(axis)),
     );
+    methods.add_function("stick", |_, (this, stick): (GamepadRef, GamepadStick)| {
+        Ok(this.stick(stick))
+    });
+    methods.add_function("supports_rumble", |_, this: GamepadRef| {
+        Ok(this.supports_rumble())
+    });
+    methods.add_function(
+        "rumble",
+        |_, (this, strong, weak, duration): (GamepadRef, f32, f32, f32)| {
+            Ok(this.rumble(strong, weak, duration))
+        },
+    );
+    methods.add_function("stop_rumble", |_, this: GamepadRef| {
+        this.stop_rumble();
+        Ok(())
+    });
+    methods.add_function(
+        "set_stick_response",
+        |_, (this, stick, t): (GamepadRef, GamepadStick, Table)| {
+            this.set_stick_response(stick, response_from_table(&t)?);
+            Ok(())
+        },
+    );
+}
+
+impl UserData for RumbleEffect {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("is_playing", |_, this: RumbleEffectRef| {
+            Ok(this.is_playing())
+        });
+        methods.add_function("stop", |_, this: RumbleEffectRef| {
+            this.stop();
+            Ok(())
+        });
+    }
+}
+
+fn response_from_table(t: &Table) -> LuaResult<AxisResponse> {
+    let curve = match t.get::<Option<String>>("curve")?.as_deref() {
+        None | Some("linear") => ResponseCurve::Linear,
+        Some("quadratic") => ResponseCurve::Quadratic,
+        Some("cubic") => ResponseCurve::Cubic,
+        Some(curve) => {
+            return Err(LuaError::runtime(format!(
+                "invalid response curve: {curve:?}"
+            )));
+        }
+    };
+    Ok(AxisResponse {
+        radial_deadzone: t.get::<Option<f32>>("radial")?.unwrap_or(0.0),
+        axial_deadzone: t.get::<Option<f32>>("axial")?.unwrap_or(0.0),
+        outer_deadzone: t.get::<Option<f32>>("outer")?.unwrap_or(0.0),
+        curve,
+    })
 }
 
 pub struct GamepadButtonModule;
@@ -150,6 +221,19 @@ impl LuaModule for GamepadAxisModule {
     }
 }
 
+pub struct GamepadStickModule;
+
+impl LuaModule for GamepadStickModule {
+    const PATH: &'static str = "GamepadStick";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+        m.raw_set("LEFT", GamepadStick::Left)?;
+        m.raw_set("RIGHT", GamepadStick::Right)?;
+        Ok(Value::Table(m))
+    }
+}
+
 impl FromLua for GamepadButton {
     #[inline]
     fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
@@ -179,3 +263,18 @@ impl IntoLua for GamepadAxis {
         Ok(Value::Integer(self as _))
     }
 }
+
+impl FromLua for GamepadStick {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        Self::from_repr(usize::from_lua(value, lua)?)
+            .ok_or_else(|| LuaError::runtime("invalid gamepad stick"))
+    }
+}
+
+impl IntoLua for GamepadStick {
+    #[inline]
+    fn into_lua(self, _lua: &Lua) -> LuaResult<Value> {
+        Ok(Value::Integer(self as _))
+    }
+}
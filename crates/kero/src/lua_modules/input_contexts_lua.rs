@@ -0,0 +1,69 @@
+use crate::core::Context;
+use crate::input::ActionMapRef;
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{Lua, Value};
+
+pub struct InputContextsModule;
+
+impl LuaModule for InputContextsModule {
+    const PATH: &'static str = "InputContexts";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+
+        m.set(
+            "push",
+            lua.create_function(|lua, map: ActionMapRef| {
+                Context::from_lua(lua).input_contexts.push(&map);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "pop",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).input_contexts.pop()))?,
+        )?;
+        m.set(
+            "remove",
+            lua.create_function(|lua, map: ActionMapRef| {
+                Ok(Context::from_lua(lua).input_contexts.remove(&map))
+            })?,
+        )?;
+        m.set(
+            "clear",
+            lua.create_function(|lua, _: ()| {
+                Context::from_lua(lua).input_contexts.clear();
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "top",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).input_contexts.top()))?,
+        )?;
+        m.set(
+            "get",
+            lua.create_function(|lua, name: String| {
+                Ok(Context::from_lua(lua).input_contexts.get(&name))
+            })?,
+        )?;
+        m.set(
+            "contains",
+            lua.create_function(|lua, map: ActionMapRef| {
+                Ok(Context::from_lua(lua).input_contexts.contains(&map))
+            })?,
+        )?;
+        m.set(
+            "len",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).input_contexts.len()))?,
+        )?;
+        m.set(
+            "maps",
+            lua.create_function(|lua, _: ()| {
+                let maps = Context::from_lua(lua).input_contexts.maps();
+                lua.create_sequence_from(maps)
+            })?,
+        )?;
+
+        Ok(Value::Table(m))
+    }
+}
@@ -0,0 +1,57 @@
+use crate::core::Context;
+use crate::gfx::{Instance, InstanceBuffer, InstanceBufferRef};
+use crate::lua::LuaModule;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{FromLua, Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+pub struct InstanceBufferModule;
+
+impl LuaModule for InstanceBufferModule {
+    const PATH: &'static str = "InstanceBuffer";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for InstanceBufferModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("new", |lua, cap: usize| {
+            let ctx = Context::from_lua(lua);
+            Ok(InstanceBuffer::new(
+                ctx.graphics.device(),
+                ctx.graphics.queue().clone(),
+                cap,
+            ))
+        });
+        methods.add_function("with", |lua, instances: Vec<Instance>| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.graphics.create_instance_buffer(&instances))
+        });
+        add_methods(methods);
+    }
+}
+
+impl UserData for InstanceBuffer {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("len", |_, this: InstanceBufferRef| Ok(this.count()));
+    methods.add_function("capacity", |_, this: InstanceBufferRef| Ok(this.capacity()));
+    methods.add_function(
+        "upload",
+        |_, (this, instances): (InstanceBufferRef, Vec<Instance>)| {
+            this.upload(&instances).map_err(LuaError::external)
+        },
+    );
+}
+
+impl FromLua for InstanceBuffer {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::color::Rgba8;
+use crate::gfx::Instance;
+use crate::lua::{Handle, LuaModule, Temp};
+use crate::math::{Affine2F, RectF};
+use mlua::prelude::LuaResult;
+use mlua::{FromLua, IntoLua, Lua, Value};
+
+pub struct InstanceModule;
+
+impl LuaModule for InstanceModule {
+    const PATH: &'static str = "Instance";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let module = Temp::<Instance>::register(lua, "Instance", |members| {
+            members.getter("transform", |this| this.transform)?;
+            members.setter("transform", |this, val: Affine2F| {
+                this.transform = val;
+            })?;
+            members.getter("tex", |this| this.tex)?;
+            members.setter("tex", |this, val: RectF| {
+                this.tex = val;
+            })?;
+            members.getter("col", |this| this.col)?;
+            members.setter("col", |this, val: Rgba8| {
+                this.col = val;
+            })?;
+            members.method_mut("set_transform", |this, val: Affine2F| {
+                this.transform = val;
+            })?;
+            members.method_mut("set_tex", |this, val: RectF| {
+                this.tex = val;
+            })?;
+            members.method_mut("set_col", |this, val: Rgba8| {
+                this.col = val;
+            })?;
+            Ok(())
+        })?;
+        module.set(
+            "new",
+            lua.create_function(|_, (transform, tex, col): (Affine2F, RectF, Rgba8)| {
+                Ok(Instance::new(transform, tex, col))
+            })?,
+        )?;
+        module.set(
+            "simple",
+            lua.create_function(|_, transform: Affine2F| Ok(Instance::simple(transform)))?,
+        )?;
+        module.set(
+            "tinted",
+            lua.create_function(|_, (transform, col): (Affine2F, Rgba8)| {
+                Ok(Instance::tinted(transform, col))
+            })?,
+        )?;
+        Ok(Value::Table(module))
+    }
+}
+
+impl FromLua for Instance {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        Handle::from_lua(value, lua).and_then(|h| h.get(lua))
+    }
+}
+
+impl IntoLua for Instance {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        Temp::<Self>::new(lua, self).map(Value::from)
+    }
+}
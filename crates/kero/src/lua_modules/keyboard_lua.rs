@@ -39,6 +39,16 @@ impl LuaModule for KeyboardModule {
                 lua.create_string(Context::from_lua(lua).keyboard.text_input())
             })?,
         )?;
+        m.set(
+            "ime_enabled",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).keyboard.ime_enabled()))?,
+        )?;
+        m.set(
+            "preedit",
+            lua.create_function(|lua, _: ()| {
+                lua.create_string(Context::from_lua(lua).keyboard.preedit())
+            })?,
+        )?;
         m.set(
             "ctrl",
             lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).keyboard.ctrl()))?,
@@ -0,0 +1,32 @@
+use crate::gfx::MaskMode;
+use mlua::prelude::LuaError;
+use mlua::{BorrowedStr, FromLua, IntoLua, Lua, Value};
+
+impl FromLua for MaskMode {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        let s = BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "inside" => MaskMode::Inside,
+            "outside" => MaskMode::Outside,
+            s => return Err(LuaError::runtime(format!("invalid mask mode {s:?}"))),
+        })
+    }
+}
+
+impl MaskMode {
+    #[inline]
+    pub fn lua_str(self) -> &'static str {
+        match self {
+            Self::Inside => "inside",
+            Self::Outside => "outside",
+        }
+    }
+}
+
+impl IntoLua for MaskMode {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+        self.lua_str().into_lua(lua)
+    }
+}
@@ -1,35 +1,59 @@
+mod action_map_lua;
 mod app_lua;
+mod audio_lua;
 mod blend_mode_lua;
+mod buffered_input_lua;
+mod capture_lua;
+mod clipboard_lua;
 mod color_mode_lua;
+mod custom_cursor_lua;
 mod draw_lua;
+mod events_lua;
 mod font_lua;
 mod gamepad_lua;
 mod index_buffer_lua;
+mod input_contexts_lua;
+mod instance_buffer_lua;
+mod instance_lua;
 mod key_lua;
 mod keyboard_lua;
+mod mask_mode_lua;
 mod monitor_lua;
 mod mouse_button_lua;
 mod mouse_lua;
 mod sampler_lua;
 mod screen_lua;
 mod shader_lua;
+mod stroke_lua;
 mod sub_texture_lua;
 mod surface_lua;
+mod text_edit_lua;
 mod texture_format_lua;
 mod texture_lua;
 mod time_lua;
 mod topology_lua;
+mod touches_lua;
 mod vertex_buffer_lua;
 mod vertex_lua;
 mod video_mode_lua;
 mod window_lua;
 
+pub use action_map_lua::*;
 pub use app_lua::*;
+pub use audio_lua::*;
+pub use buffered_input_lua::*;
+pub use capture_lua::*;
+pub use clipboard_lua::*;
 pub use color_mode_lua::*;
+pub use custom_cursor_lua::*;
 pub use draw_lua::*;
+pub use events_lua::*;
 pub use font_lua::*;
 pub use gamepad_lua::*;
 pub use index_buffer_lua::*;
+pub use input_contexts_lua::*;
+pub use instance_buffer_lua::*;
+pub use instance_lua::*;
 pub use key_lua::*;
 pub use keyboard_lua::*;
 use mlua::prelude::LuaError;
@@ -41,8 +65,10 @@ pub use screen_lua::*;
 pub use shader_lua::*;
 pub use sub_texture_lua::*;
 pub use surface_lua::*;
+pub use text_edit_lua::*;
 pub use texture_lua::*;
 pub use time_lua::*;
+pub use touches_lua::*;
 pub use vertex_buffer_lua::*;
 pub use vertex_lua::*;
 pub use video_mode_lua::*;
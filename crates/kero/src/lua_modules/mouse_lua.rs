@@ -28,6 +28,10 @@ impl LuaModule for MouseModule {
             "scroll",
             lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.scroll_delta().y))?,
         )?;
+        m.set(
+            "motion",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.motion()))?,
+        )?;
         m.set(
             "down",
             lua.create_function(|lua, btn: MouseButton| {
@@ -22,7 +22,13 @@ impl UserData for ShaderModule {
         });
         methods.add_function("new", |lua, source: BorrowedStr| {
             let ctx = Context::from_lua(lua);
-            Ok(ctx.graphics.create_shader(source.as_ref()))
+            ctx.graphics
+                .create_shader(source.as_ref())
+                .map_err(LuaError::external)
+        });
+        methods.add_function("watch", |lua, path: String| {
+            let ctx = Context::from_lua(lua);
+            ctx.graphics.watch_shader(path).map_err(LuaError::external)
         });
         add_methods(methods);
     }
@@ -50,6 +56,10 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             .find(|p| p.name == name.as_ref())
             .map(|p| p.ty))
     });
+    methods.add_function("path", |lua, this: ShaderRef| {
+        this.path().as_deref().into_lua(lua)
+    });
+    methods.add_function("reload_error", |_, this: ShaderRef| Ok(this.reload_error()));
 }
 
 impl FromLua for Shader {
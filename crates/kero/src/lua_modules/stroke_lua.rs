@@ -0,0 +1,95 @@
+use crate::gfx::{LineCap, LineJoin, Stroke};
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{BorrowedStr, FromLua, IntoLua, Lua, Value};
+
+impl FromLua for Stroke {
+    fn from_lua(value: Value, _: &Lua) -> LuaResult<Self> {
+        match value {
+            Value::Integer(width) => Ok(Stroke::new(width as f32)),
+            Value::Number(width) => Ok(Stroke::new(width as f32)),
+            Value::Table(table) => {
+                let mut stroke = Stroke::new(table.get::<Option<f32>>("width")?.unwrap_or(1.0));
+                if let Some(join) = table.get("join")? {
+                    stroke.join = join;
+                }
+                if let Some(cap) = table.get("cap")? {
+                    stroke.cap = cap;
+                }
+                if let Some(miter_limit) = table.get("miter_limit")? {
+                    stroke.miter_limit = miter_limit;
+                }
+                if let Some(dashes) = table.get("dashes")? {
+                    stroke.dashes = dashes;
+                }
+                if let Some(dash_offset) = table.get("dash_offset")? {
+                    stroke.dash_offset = dash_offset;
+                }
+                Ok(stroke)
+            }
+            value => Err(LuaError::runtime(format!(
+                "invalid stroke [{}], expected a width or table",
+                value.type_name()
+            ))),
+        }
+    }
+}
+
+impl FromLua for LineJoin {
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        let s = BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "miter" => Self::Miter,
+            "round" => Self::Round,
+            "bevel" => Self::Bevel,
+            s => return Err(LuaError::runtime(format!("invalid line join [{s}]"))),
+        })
+    }
+}
+
+impl FromLua for LineCap {
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        let s = BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "butt" => Self::Butt,
+            "round" => Self::Round,
+            "square" => Self::Square,
+            s => return Err(LuaError::runtime(format!("invalid line cap [{s}]"))),
+        })
+    }
+}
+
+impl LineJoin {
+    #[inline]
+    pub fn lua_str(self) -> &'static str {
+        match self {
+            Self::Miter => "miter",
+            Self::Round => "round",
+            Self::Bevel => "bevel",
+        }
+    }
+}
+
+impl LineCap {
+    #[inline]
+    pub fn lua_str(self) -> &'static str {
+        match self {
+            Self::Butt => "butt",
+            Self::Round => "round",
+            Self::Square => "square",
+        }
+    }
+}
+
+impl IntoLua for LineJoin {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        self.lua_str().into_lua(lua)
+    }
+}
+
+impl IntoLua for LineCap {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        self.lua_str().into_lua(lua)
+    }
+}
@@ -18,10 +18,14 @@ impl UserData for SurfaceModule {
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_function(
             "new",
-            |lua, (w, h, fmt): (u32, u32, Option<TextureFormat>)| {
+            |lua, (w, h, fmt, stencil): (u32, u32, Option<TextureFormat>, Option<bool>)| {
                 let ctx = Context::from_lua(lua);
                 let fmt = fmt.unwrap_or(TextureFormat::Rgba8);
-                Ok(ctx.graphics.create_surface((w, h), fmt))
+                Ok(if stencil.unwrap_or(false) {
+                    ctx.graphics.create_surface_with_stencil((w, h), fmt)
+                } else {
+                    ctx.graphics.create_surface((w, h), fmt)
+                })
             },
         );
         add_methods(methods);
@@ -40,6 +44,7 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
     methods.add_function("width", |_, this: SurfaceRef| Ok(this.width()));
     methods.add_function("height", |_, this: SurfaceRef| Ok(this.height()));
     methods.add_function("format", |_, this: SurfaceRef| Ok(this.format()));
+    methods.add_function("has_stencil", |_, this: SurfaceRef| Ok(this.has_stencil()));
     methods.add_function("texture", |_, this: SurfaceRef| Ok(this.texture().clone()));
 }
 
@@ -0,0 +1,191 @@
+use crate::core::Context;
+use crate::input::{TextEdit, TextEditMut, TextEditRef};
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{Lua, UserData, UserDataMethods, Value};
+
+pub struct TextEditModule;
+
+impl LuaModule for TextEditModule {
+    const PATH: &'static str = "TextEdit";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for TextEditModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function(
+            "new",
+            |_, (text, multiline, max_chars): (Option<String>, Option<bool>, Option<usize>)| {
+                Ok(TextEdit::new()
+                    .with_text(text.unwrap_or_default())
+                    .with_multiline(multiline.unwrap_or(false))
+                    .with_max_chars(max_chars))
+            },
+        );
+        add_methods(methods);
+    }
+}
+
+impl UserData for TextEdit {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("text", |lua, this: TextEditRef| {
+        lua.create_string(this.text())
+    });
+    methods.add_function("set_text", |_, (mut this, text): (TextEditMut, String)| {
+        this.set_text(text);
+        Ok(())
+    });
+    methods.add_function("caret", |_, this: TextEditRef| Ok(this.caret()));
+    methods.add_function("anchor", |_, this: TextEditRef| Ok(this.anchor()));
+    methods.add_function("selection", |_, this: TextEditRef| {
+        let sel = this.selection();
+        Ok((sel.start, sel.end))
+    });
+    methods.add_function("has_selection", |_, this: TextEditRef| {
+        Ok(this.has_selection())
+    });
+    methods.add_function("selected_text", |lua, this: TextEditRef| {
+        lua.create_string(this.selected_text())
+    });
+    methods.add_function(
+        "set_caret",
+        |_, (mut this, pos, select): (TextEditMut, usize, Option<bool>)| {
+            this.set_caret(pos, select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "select",
+        |_, (mut this, start, end): (TextEditMut, usize, usize)| {
+            this.select(start..end);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_left",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_left(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_right",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_right(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_word_left",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_word_left(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_word_right",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_word_right(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_up",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_up(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_down",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_down(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_line_start",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_line_start(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_line_end",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_line_end(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_to_start",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_to_start(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "move_to_end",
+        |_, (mut this, select): (TextEditMut, Option<bool>)| {
+            this.move_to_end(select.unwrap_or(false));
+            Ok(())
+        },
+    );
+    methods.add_function("insert", |_, (mut this, text): (TextEditMut, String)| {
+        this.insert(&text);
+        Ok(())
+    });
+    methods.add_function("backspace", |_, mut this: TextEditMut| {
+        this.backspace();
+        Ok(())
+    });
+    methods.add_function("delete", |_, mut this: TextEditMut| {
+        this.delete();
+        Ok(())
+    });
+    methods.add_function("delete_word_left", |_, mut this: TextEditMut| {
+        this.delete_word_left();
+        Ok(())
+    });
+    methods.add_function("delete_word_right", |_, mut this: TextEditMut| {
+        this.delete_word_right();
+        Ok(())
+    });
+    methods.add_function("clear", |_, mut this: TextEditMut| {
+        this.clear();
+        Ok(())
+    });
+    methods.add_function("select_all", |_, mut this: TextEditMut| {
+        this.select_all();
+        Ok(())
+    });
+    methods.add_function("copy", |_, this: TextEditRef| Ok(this.copy()));
+    methods.add_function("cut", |_, mut this: TextEditMut| Ok(this.cut()));
+    methods.add_function("paste", |_, (mut this, text): (TextEditMut, String)| {
+        this.paste(&text);
+        Ok(())
+    });
+    methods.add_function("undo", |_, mut this: TextEditMut| Ok(this.undo()));
+    methods.add_function("redo", |_, mut this: TextEditMut| Ok(this.redo()));
+    methods.add_function("preedit", |lua, this: TextEditRef| {
+        lua.create_string(this.preedit())
+    });
+    methods.add_function("display_text", |_, this: TextEditRef| {
+        Ok(this.display_text())
+    });
+    methods.add_function("display_caret", |_, this: TextEditRef| {
+        Ok(this.display_caret())
+    });
+    methods.add_function("update", |lua, mut this: TextEditMut| {
+        let ctx = Context::from_lua(lua);
+        Ok(this.update(&ctx))
+    });
+}
@@ -24,6 +24,20 @@ impl LuaModule for TimeModule {
                 Ok(ctx.time.delta())
             })?,
         )?;
+        m.set(
+            "fixed_delta",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.fixed_delta())
+            })?,
+        )?;
+        m.set(
+            "alpha",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.alpha())
+            })?,
+        )?;
         m.set(
             "since_startup",
             lua.create_function(|lua, _: ()| {
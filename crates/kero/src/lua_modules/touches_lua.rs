@@ -0,0 +1,97 @@
+use crate::core::Context;
+use crate::input::{Touch, TouchPhase};
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{IntoLua, Lua, Result, Value};
+
+pub struct TouchesModule;
+
+impl LuaModule for TouchesModule {
+    const PATH: &'static str = "Touches";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+
+        m.set(
+            "count",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.count()))?,
+        )?;
+        m.set(
+            "all",
+            lua.create_function(|lua, _: ()| {
+                lua.create_sequence_from(Context::from_lua(lua).touches.all())
+            })?,
+        )?;
+        m.set(
+            "get",
+            lua.create_function(|lua, id: u64| Ok(Context::from_lua(lua).touches.get(id)))?,
+        )?;
+        m.set(
+            "first",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.first()))?,
+        )?;
+        m.set(
+            "pressed",
+            lua.create_function(|lua, _: ()| {
+                lua.create_sequence_from(Context::from_lua(lua).touches.pressed())
+            })?,
+        )?;
+        m.set(
+            "released",
+            lua.create_function(|lua, _: ()| {
+                lua.create_sequence_from(Context::from_lua(lua).touches.released())
+            })?,
+        )?;
+        m.set(
+            "pinch",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.pinch_delta()))?,
+        )?;
+        m.set(
+            "pan",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.pan_delta()))?,
+        )?;
+        m.set(
+            "rotation",
+            lua.create_function(|lua, _: ()| {
+                Ok(Context::from_lua(lua).touches.rotation_delta().0)
+            })?,
+        )?;
+        m.set(
+            "double_tapped",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.double_tapped()))?,
+        )?;
+        m.set(
+            "mouse_emulation",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.mouse_emulation()))?,
+        )?;
+        m.set(
+            "set_mouse_emulation",
+            lua.create_function(|lua, enabled: bool| {
+                Context::from_lua(lua).touches.set_mouse_emulation(enabled);
+                Ok(())
+            })?,
+        )?;
+
+        Ok(Value::Table(m))
+    }
+}
+
+impl IntoLua for Touch {
+    fn into_lua(self, lua: &Lua) -> Result<Value> {
+        let t = lua.create_table()?;
+        t.set("id", self.id)?;
+        t.set("pos", self.pos)?;
+        t.set("start_pos", self.start_pos)?;
+        t.set(
+            "phase",
+            match self.phase {
+                TouchPhase::Started => "started",
+                TouchPhase::Moved => "moved",
+                TouchPhase::Ended => "ended",
+                TouchPhase::Cancelled => "cancelled",
+            },
+        )?;
+        t.set("pressure", self.pressure)?;
+        Ok(Value::Table(t))
+    }
+}
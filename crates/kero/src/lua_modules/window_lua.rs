@@ -1,7 +1,7 @@
 use super::VideoModeRef;
-use crate::core::{Context, CursorIcon, DisplayMode, MonitorRef};
+use crate::core::{Context, CursorGrab, CursorIcon, CustomCursorRef, DisplayMode, MonitorRef};
 use crate::lua::LuaModule;
-use crate::math::Numeric;
+use crate::math::{Numeric, RectF};
 use fey_math::Vec2;
 use mlua::prelude::LuaResult;
 use mlua::{BorrowedStr, Lua, Value};
@@ -245,6 +245,52 @@ impl LuaModule for WindowModule {
                 Ok(())
             })?,
         )?;
+        m.set(
+            "set_custom_cursor",
+            lua.create_function(|lua, cursor: CustomCursorRef| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_custom_cursor(&cursor);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "set_cursor_visible",
+            lua.create_function(|lua, visible: bool| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_cursor_visible(visible);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "set_cursor_grab",
+            lua.create_function(|lua, grab: CursorGrab| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.window.set_cursor_grab(grab))
+            })?,
+        )?;
+        m.set(
+            "theme",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.window.theme())
+            })?,
+        )?;
+        m.set(
+            "set_ime_allowed",
+            lua.create_function(|lua, allowed: bool| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_ime_allowed(allowed);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "set_ime_cursor_area",
+            lua.create_function(|lua, area: RectF| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_ime_cursor_area(area);
+                Ok(())
+            })?,
+        )?;
         Ok(Value::Table(m))
     }
 }
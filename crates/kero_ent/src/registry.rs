@@ -1,5 +1,6 @@
 use super::{ComponentOf, ComponentType, EntityObj, WorldObj};
 use fnv::FnvHashMap;
+use kero::core::LuaReloadHooks;
 use kero::math::Vec2F;
 use mlua::prelude::{LuaError, LuaResult};
 use mlua::{AnyUserData, AppDataRef, Function, Lua, Table};
@@ -32,6 +33,12 @@ impl Registry {
             name_lookup: HashMap::new(),
             module_lookup: FnvHashMap::default(),
         });
+
+        // Lua-defined components are registered again when the scripts reload
+        LuaReloadHooks::add(lua, |lua| {
+            lua.app_data_mut::<Self>().unwrap().clear_lua();
+            Ok(())
+        });
         Ok(())
     }
 
@@ -40,12 +47,12 @@ impl Registry {
         lua.app_data_ref::<Self>().unwrap()
     }
 
-    // TODO: call this when Lua reloads to clear Lua-defined components
-    // pub fn clear_lua(&mut self) {
-    //     self.lua_types.clear();
-    //     self.name_lookup.retain(|_, i| matches!(i, Index::Rust(_)));
-    //     self.module_lookup.clear();
-    // }
+    /// Clear all Lua-defined components, called when Lua reloads.
+    pub fn clear_lua(&mut self) {
+        self.lua_types.clear();
+        self.name_lookup.retain(|_, i| matches!(i, Index::Rust(_)));
+        self.module_lookup.clear();
+    }
 
     pub fn register_rust<T: ComponentType>(&mut self) {
         let idx = self.rust_types.len();